@@ -1,6 +1,7 @@
+use itertools::Itertools;
 use tombi_future::{BoxFuture, Boxable};
 use tombi_schema_store::{
-    AllOfSchema, AnyOfSchema, OneOfSchema, PropertySchema, SchemaAccessor, ValueSchema,
+    Accessor, AllOfSchema, AnyOfSchema, OneOfSchema, PropertySchema, SchemaAccessor, ValueSchema,
 };
 use tombi_validator::Validate;
 
@@ -21,15 +22,37 @@ pub trait Edit {
     ) -> BoxFuture<'b, Vec<crate::Change>>;
 }
 
-async fn get_schema<'a: 'b, 'b>(
+pub(crate) async fn get_schema(
+    value: &tombi_document_tree::Value,
+    accessors: &[SchemaAccessor],
+    current_schema: &tombi_schema_store::CurrentSchema<'_>,
+    schema_context: &tombi_schema_store::SchemaContext<'_>,
+) -> Option<ValueSchema> {
+    // NOTE: This is fine. This function is only used for Table/ArrayOfTable or Keys of KeyValues,
+    //       so there is only one element in the array.
+    let accessors = accessors
+        .iter()
+        .map(|accessor| match accessor {
+            SchemaAccessor::Key(key) => Accessor::Key(key.clone()),
+            SchemaAccessor::Index => Accessor::Index(0),
+        })
+        .collect_vec();
+
+    get_value_schema(value, &accessors, current_schema, schema_context).await
+}
+
+/// Returns the schema of the value at the accessors.
+///
+/// Among `oneOf`, `anyOf` and `allOf`, the first schema the value is valid for is chosen.
+pub async fn get_value_schema<'a: 'b, 'b>(
     value: &'a tombi_document_tree::Value,
-    accessors: &'a [tombi_schema_store::SchemaAccessor],
+    accessors: &'a [Accessor],
     current_schema: &'a tombi_schema_store::CurrentSchema<'a>,
     schema_context: &'a tombi_schema_store::SchemaContext<'a>,
 ) -> Option<ValueSchema> {
     fn inner_get_schema<'a: 'b, 'b>(
         value: &'a tombi_document_tree::Value,
-        accessors: &'a [tombi_schema_store::SchemaAccessor],
+        accessors: &'a [Accessor],
         validation_accessors: &'a [tombi_schema_store::SchemaAccessor],
         current_schema: &'a tombi_schema_store::CurrentSchema<'a>,
         schema_context: &'a tombi_schema_store::SchemaContext<'a>,
@@ -77,20 +100,20 @@ async fn get_schema<'a: 'b, 'b>(
             }
 
             match &accessors[0] {
-                SchemaAccessor::Key(key) => {
+                Accessor::Key(key) => {
                     if let (
                         tombi_document_tree::Value::Table(table),
                         ValueSchema::Table(table_schema),
                     ) = (value, current_schema.value_schema.as_ref())
                     {
-                        if let Some(value) = table.get(&key.to_string()) {
+                        if let Some(value) = table.get(key) {
                             if let Some(PropertySchema {
                                 property_schema, ..
                             }) = table_schema
                                 .properties
                                 .write()
                                 .await
-                                .get_mut(&SchemaAccessor::Key(key.to_string()))
+                                .get_mut(&SchemaAccessor::Key(key.clone()))
                             {
                                 if let Ok(Some(current_schema)) = property_schema
                                     .resolve(
@@ -119,7 +142,7 @@ async fn get_schema<'a: 'b, 'b>(
                                 ) in pattern_properties.write().await.iter_mut()
                                 {
                                     if let Ok(pattern) = regex::Regex::new(property_key) {
-                                        if pattern.is_match(&key.to_string()) {
+                                        if pattern.is_match(key) {
                                             if let Ok(Some(current_schema)) = property_schema
                                                 .resolve(
                                                     current_schema.schema_url.clone(),
@@ -172,38 +195,33 @@ async fn get_schema<'a: 'b, 'b>(
                         }
                     }
                 }
-                SchemaAccessor::Index => {
+                Accessor::Index(index) => {
                     if let (
                         tombi_document_tree::Value::Array(array),
                         ValueSchema::Array(array_schema),
                     ) = (value, current_schema.value_schema.as_ref())
                     {
-                        // NOTE: This is fine. This function is only used for Table/ArrayOfTable or Keys of KeyValues,
-                        //       so there is only one element in the array.
-                        if let Some(value) = array.first() {
-                            if let Some(item_schema) = &array_schema.items {
-                                if let Ok(Some(current_schema)) = item_schema
-                                    .write()
-                                    .await
-                                    .resolve(
-                                        current_schema.schema_url.clone(),
-                                        current_schema.definitions.clone(),
-                                        schema_context.store,
-                                    )
-                                    .await
-                                {
-                                    return inner_get_schema(
-                                        value,
-                                        &accessors[1..],
-                                        validation_accessors,
-                                        &current_schema,
-                                        schema_context,
-                                    )
-                                    .await;
-                                }
+                        let value = array.get(*index)?;
+                        if let Some(item_schema) = &array_schema.items {
+                            if let Ok(Some(current_schema)) = item_schema
+                                .write()
+                                .await
+                                .resolve(
+                                    current_schema.schema_url.clone(),
+                                    current_schema.definitions.clone(),
+                                    schema_context.store,
+                                )
+                                .await
+                            {
+                                return inner_get_schema(
+                                    value,
+                                    &accessors[1..],
+                                    validation_accessors,
+                                    &current_schema,
+                                    schema_context,
+                                )
+                                .await;
                             }
-                        } else {
-                            return None;
                         }
                     }
                 }
@@ -214,5 +232,14 @@ async fn get_schema<'a: 'b, 'b>(
         .boxed()
     }
 
-    inner_get_schema(value, accessors, accessors, current_schema, schema_context).await
+    let validation_accessors = accessors.iter().map(SchemaAccessor::from).collect_vec();
+
+    inner_get_schema(
+        value,
+        accessors,
+        &validation_accessors,
+        current_schema,
+        schema_context,
+    )
+    .await
 }
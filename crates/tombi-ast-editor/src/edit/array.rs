@@ -2,25 +2,37 @@ use std::borrow::Cow;
 
 use itertools::Itertools;
 use tombi_future::{BoxFuture, Boxable};
-use tombi_schema_store::ValueSchema;
+use tombi_schema_store::{ArraySchema, SchemaAccessor, ValueSchema};
 
-use crate::rule::{array_comma_tailing_comment, array_values_order};
+use crate::rule::{array_comma_tailing_comment, array_values_order, array_values_unique};
 
 impl crate::Edit for tombi_ast::Array {
     fn edit<'a: 'b, 'b>(
         &'a self,
-        _accessors: &'a [tombi_schema_store::SchemaAccessor],
+        accessors: &'a [tombi_schema_store::SchemaAccessor],
         current_schema: Option<&'a tombi_schema_store::CurrentSchema<'a>>,
         schema_context: &'a tombi_schema_store::SchemaContext<'a>,
     ) -> BoxFuture<'b, Vec<crate::Change>> {
         async move {
             let mut changes = vec![];
+            let mut values_with_comma = self.values_with_comma().collect_vec();
+            let value_accessors = accessors
+                .iter()
+                .cloned()
+                .chain(std::iter::once(SchemaAccessor::Index))
+                .collect_vec();
 
             if let Some(current_schema) = current_schema {
                 if let ValueSchema::Array(array_schema) = current_schema.value_schema.as_ref() {
+                    let (unique_values_with_comma, unique_changes) =
+                        array_values_unique(values_with_comma, array_schema, schema_context);
+                    changes.extend(unique_changes);
+                    values_with_comma = unique_values_with_comma;
+
                     changes.extend(
                         array_values_order(
-                            self.values_with_comma().collect_vec(),
+                            values_with_comma.clone(),
+                            accessors,
                             array_schema,
                             schema_context,
                         )
@@ -38,9 +50,15 @@ impl crate::Edit for tombi_ast::Array {
                             )
                             .await
                         {
-                            for value in self.values() {
+                            for (value, _) in &values_with_comma {
                                 changes.extend(
-                                    value.edit(&[], Some(&current_schema), schema_context).await,
+                                    value
+                                        .edit(
+                                            &value_accessors,
+                                            Some(&current_schema),
+                                            schema_context,
+                                        )
+                                        .await,
                                 );
                             }
 
@@ -50,13 +68,31 @@ impl crate::Edit for tombi_ast::Array {
                 }
             }
 
-            for (value, comma) in self.values_with_comma() {
+            // NOTE: Without the schema, only the values order of the config overrides applies.
+            if current_schema.is_none() && schema_context.array_values_order(accessors).is_some() {
+                changes.extend(
+                    array_values_order(
+                        values_with_comma.clone(),
+                        accessors,
+                        &ArraySchema::default(),
+                        schema_context,
+                    )
+                    .await,
+                );
+                for (value, _) in &values_with_comma {
+                    changes.extend(value.edit(&value_accessors, None, schema_context).await);
+                }
+
+                return changes;
+            }
+
+            for (value, comma) in &values_with_comma {
                 changes.extend(array_comma_tailing_comment(
-                    &value,
+                    value,
                     comma.as_ref(),
                     schema_context,
                 ));
-                changes.extend(value.edit(&[], None, schema_context).await);
+                changes.extend(value.edit(&value_accessors, None, schema_context).await);
             }
 
             changes
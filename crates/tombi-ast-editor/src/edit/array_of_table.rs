@@ -71,6 +71,7 @@ impl crate::Edit for tombi_ast::ArrayOfTable {
             changes.extend(
                 table_keys_order(
                     value,
+                    &header_accessors,
                     self.key_values().collect_vec(),
                     current_schema.as_ref(),
                     schema_context,
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 use tombi_future::{BoxFuture, Boxable};
-use tombi_schema_store::ValueSchema;
+use tombi_schema_store::{TableSchema, ValueSchema};
 
 use crate::rule::{inline_table_comma_tailing_comment, inline_table_keys_order};
 
@@ -19,6 +19,7 @@ impl crate::Edit for tombi_ast::InlineTable {
                     changes.extend(
                         inline_table_keys_order(
                             self.key_values_with_comma().collect_vec(),
+                            accessors,
                             table_schema,
                             schema_context,
                         )
@@ -37,6 +38,24 @@ impl crate::Edit for tombi_ast::InlineTable {
                 }
             }
 
+            // NOTE: Without the schema, only the table keys order of the config overrides applies.
+            if current_schema.is_none() && schema_context.table_keys_order(accessors).is_some() {
+                changes.extend(
+                    inline_table_keys_order(
+                        self.key_values_with_comma().collect_vec(),
+                        accessors,
+                        &TableSchema::default(),
+                        schema_context,
+                    )
+                    .await,
+                );
+                for key_value in self.key_values() {
+                    changes.extend(key_value.edit(accessors, None, schema_context).await);
+                }
+
+                return changes;
+            }
+
             for (key_value, comma) in self.key_values_with_comma() {
                 changes.extend(inline_table_comma_tailing_comment(
                     &key_value,
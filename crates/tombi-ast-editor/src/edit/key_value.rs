@@ -10,7 +10,7 @@ use super::get_schema;
 impl crate::Edit for tombi_ast::KeyValue {
     fn edit<'a: 'b, 'b>(
         &'a self,
-        _accessors: &'a [tombi_schema_store::SchemaAccessor],
+        accessors: &'a [tombi_schema_store::SchemaAccessor],
         current_schema: Option<&'a tombi_schema_store::CurrentSchema<'a>>,
         schema_context: &'a tombi_schema_store::SchemaContext<'a>,
     ) -> BoxFuture<'b, Vec<crate::Change>> {
@@ -30,6 +30,12 @@ impl crate::Edit for tombi_ast::KeyValue {
                 })
                 .collect_vec();
 
+            let value_accessors = accessors
+                .iter()
+                .cloned()
+                .chain(keys_accessors.iter().cloned())
+                .collect_vec();
+
             if let Some(current_schema) = current_schema {
                 if let Some(value_schema) = get_schema(
                     &tombi_document_tree::Value::Table(
@@ -47,7 +53,7 @@ impl crate::Edit for tombi_ast::KeyValue {
                         changes.extend(
                             value
                                 .edit(
-                                    &[],
+                                    &value_accessors,
                                     Some(&CurrentSchema {
                                         value_schema: Cow::Owned(value_schema),
                                         schema_url: current_schema.schema_url.clone(),
@@ -63,7 +69,7 @@ impl crate::Edit for tombi_ast::KeyValue {
             }
 
             if let Some(value) = self.value() {
-                changes.extend(value.edit(&[], None, schema_context).await);
+                changes.extend(value.edit(&value_accessors, None, schema_context).await);
             }
 
             changes
@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use tombi_ast::AstNode;
 use tombi_schema_store::{CurrentSchema, SchemaContext};
 
-use crate::{change::Change, Edit};
+use crate::{change::Change, rule::table_style, Edit};
 
 pub struct Editor<'a> {
     root: tombi_ast::Root,
@@ -34,42 +34,49 @@ impl<'a> Editor<'a> {
                 })
         });
 
+        let changes = table_style(&new_root, current_schema.as_ref(), self.schema_context).await;
+        apply_changes(&new_root, changes);
+
         let changes = new_root
             .edit(&[], current_schema.as_ref(), self.schema_context)
             .await;
+        apply_changes(&new_root, changes);
 
-        for change in changes {
-            match change {
-                Change::AppendTop { new } => {
-                    new_root.syntax().splice_children(0..0, new);
-                }
-                Change::Append { base, new } => {
-                    let index = base.index() + 1;
-                    if let Some(node) = base.parent().as_ref().or_else(|| base.as_node()) {
-                        node.splice_children(index..index, new);
-                    }
+        new_root
+    }
+}
+
+/// Apply the changes to the mutable syntax tree in order.
+pub fn apply_changes(root: &tombi_ast::Root, changes: Vec<Change>) {
+    for change in changes {
+        match change {
+            Change::AppendTop { new } => {
+                root.syntax().splice_children(0..0, new);
+            }
+            Change::Append { base, new } => {
+                let index = base.index() + 1;
+                if let Some(node) = base.parent().as_ref().or_else(|| base.as_node()) {
+                    node.splice_children(index..index, new);
                 }
-                Change::Remove { target } => {
-                    let index = target.index();
-                    if let Some(node) = target.parent().as_ref().or_else(|| target.as_node()) {
-                        node.splice_children(index..index + 1, Vec::with_capacity(0));
-                    }
+            }
+            Change::Remove { target } => {
+                let index = target.index();
+                if let Some(node) = target.parent().as_ref().or_else(|| target.as_node()) {
+                    node.splice_children(index..index + 1, Vec::with_capacity(0));
                 }
-                Change::ReplaceRange { old, new } => {
-                    let start = old.start().index();
-                    let end = old.end().index();
-                    if let Some(node) = old
-                        .start()
-                        .parent()
-                        .as_ref()
-                        .or_else(|| old.start().as_node())
-                    {
-                        node.splice_children(start..end + 1, new);
-                    }
+            }
+            Change::ReplaceRange { old, new } => {
+                let start = old.start().index();
+                let end = old.end().index();
+                if let Some(node) = old
+                    .start()
+                    .parent()
+                    .as_ref()
+                    .or_else(|| old.start().as_node())
+                {
+                    node.splice_children(start..end + 1, new);
                 }
             }
         }
-
-        new_root
     }
 }
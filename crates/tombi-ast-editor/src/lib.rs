@@ -3,7 +3,10 @@ mod edit;
 mod editor;
 mod node;
 mod rule;
+pub mod text;
 
-use change::Change;
+pub use change::Change;
+pub use edit::get_value_schema;
 use edit::Edit;
-pub use editor::Editor;
+pub use editor::{apply_changes, Editor};
+pub use rule::array_values_unique;
@@ -1,13 +1,19 @@
 mod array_comma_tailing_comment;
 mod array_values_order;
+mod array_values_order_by;
+mod array_values_unique;
 mod inline_table_comma_tailing_comment;
 mod inline_table_keys_order;
 mod root_table_keys_order;
 mod table_keys_order;
+mod table_style;
 
 pub use array_comma_tailing_comment::array_comma_tailing_comment;
 pub use array_values_order::array_values_order;
+pub use array_values_order_by::{sorted_array_of_tables, sorted_by_keys};
+pub use array_values_unique::array_values_unique;
 pub use inline_table_comma_tailing_comment::inline_table_comma_tailing_comment;
 pub use inline_table_keys_order::inline_table_keys_order;
 pub use root_table_keys_order::root_table_keys_order;
 pub use table_keys_order::table_keys_order;
+pub use table_style::table_style;
@@ -1,16 +1,17 @@
 use itertools::Itertools;
 use tombi_ast::AstNode;
-use tombi_document_tree::TryIntoDocumentTree;
-use tombi_schema_store::{ArraySchema, SchemaContext};
+use tombi_document_tree::{IntoDocumentTreeAndErrors, TryIntoDocumentTree};
+use tombi_schema_store::{ArraySchema, SchemaAccessor, SchemaContext};
 use tombi_syntax::SyntaxElement;
 use tombi_x_keyword::ArrayValuesOrder;
 
 use crate::node::make_comma;
 
-use super::array_comma_tailing_comment;
+use super::{array_comma_tailing_comment, sorted_by_keys};
 
 pub async fn array_values_order<'a>(
     values_with_comma: Vec<(tombi_ast::Value, Option<tombi_ast::Comma>)>,
+    accessors: &'a [SchemaAccessor],
     array_schema: &'a ArraySchema,
     schema_context: &'a SchemaContext<'a>,
 ) -> Vec<crate::Change> {
@@ -18,9 +19,13 @@ pub async fn array_values_order<'a>(
         return Vec::with_capacity(0);
     }
 
-    let Some(values_order) = &array_schema.values_order else {
+    let values_order = schema_context
+        .array_values_order(accessors)
+        .or_else(|| array_schema.values_order.clone());
+
+    if values_order.is_none() && array_schema.values_order_by.is_none() {
         return Vec::with_capacity(0);
-    };
+    }
 
     let mut changes = vec![];
 
@@ -34,32 +39,47 @@ pub async fn array_values_order<'a>(
         SyntaxElement::Node(values_with_comma.last().unwrap().0.syntax().clone()),
     );
 
-    let sortable_values =
-        match SortableValues::new(values_with_comma.clone(), schema_context.toml_version) {
-            Ok(sortable_values) => sortable_values,
-            Err(warning) => {
-                tracing::debug!("{warning}");
+    let mut sorted_values_with_comma = if let Some(keys) = &array_schema.values_order_by {
+        let values = values_with_comma
+            .iter()
+            .map(|(value, _)| {
+                value
+                    .clone()
+                    .into_document_tree_and_errors(schema_context.toml_version)
+                    .tree
+            })
+            .collect_vec();
+
+        match sorted_by_keys(
+            values
+                .iter()
+                .zip(values_with_comma.iter().cloned().map(|(value, comma)| {
+                    (
+                        value,
+                        Some(comma.unwrap_or(tombi_ast::Comma::cast(make_comma()).unwrap())),
+                    )
+                }))
+                .collect_vec(),
+            keys,
+            values_order.as_ref(),
+        ) {
+            Some(sorted_values_with_comma) => sorted_values_with_comma,
+            None => {
+                tracing::debug!("{}", Warning::NotTables);
                 return Vec::with_capacity(0);
             }
-        };
+        }
+    } else {
+        let sortable_values =
+            match SortableValues::new(values_with_comma.clone(), schema_context.toml_version) {
+                Ok(sortable_values) => sortable_values,
+                Err(warning) => {
+                    tracing::debug!("{warning}");
+                    return Vec::with_capacity(0);
+                }
+            };
 
-    let mut sorted_values_with_comma = match values_order {
-        ArrayValuesOrder::Ascending => sortable_values
-            .sorted()
-            .into_iter()
-            .map(|(value, comma)| (value, Some(comma)))
-            .collect_vec(),
-        ArrayValuesOrder::Descending => sortable_values
-            .sorted()
-            .into_iter()
-            .rev()
-            .map(|(value, comma)| (value, Some(comma)))
-            .collect_vec(),
-        ArrayValuesOrder::VersionSort => sortable_values
-            .sorted_version()
-            .into_iter()
-            .map(|(value, comma)| (value, Some(comma)))
-            .collect_vec(),
+        sorted_values(sortable_values, values_order.as_ref())
     };
 
     if let Some((_, comma)) = sorted_values_with_comma.last_mut() {
@@ -115,6 +135,30 @@ pub async fn array_values_order<'a>(
     changes
 }
 
+fn sorted_values(
+    sortable_values: SortableValues,
+    values_order: Option<&ArrayValuesOrder>,
+) -> Vec<(tombi_ast::Value, Option<tombi_ast::Comma>)> {
+    match values_order.unwrap_or(&ArrayValuesOrder::Ascending) {
+        ArrayValuesOrder::Ascending => sortable_values
+            .sorted()
+            .into_iter()
+            .map(|(value, comma)| (value, Some(comma)))
+            .collect_vec(),
+        ArrayValuesOrder::Descending => sortable_values
+            .sorted()
+            .into_iter()
+            .rev()
+            .map(|(value, comma)| (value, Some(comma)))
+            .collect_vec(),
+        ArrayValuesOrder::VersionSort => sortable_values
+            .sorted_version()
+            .into_iter()
+            .map(|(value, comma)| (value, Some(comma)))
+            .collect_vec(),
+    }
+}
+
 enum SortableType {
     Boolean,
     Integer,
@@ -148,6 +192,9 @@ enum Warning {
 
     #[error("Cannot sort array values because the values have different types.")]
     DifferentTypes,
+
+    #[error("Cannot sort array values by keys because the values are not tables.")]
+    NotTables,
 }
 
 impl SortableValues {
@@ -0,0 +1,152 @@
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+use tombi_schema_store::SchemaAccessor;
+use tombi_x_keyword::ArrayValuesOrder;
+
+/// Sort the targets by the values of the keys in their tables.
+///
+/// The keys are compared in priority order, and tables without the key are placed last.
+/// Returns `None` if any of the values is not a table.
+pub fn sorted_by_keys<T>(
+    values_with_targets: Vec<(&tombi_document_tree::Value, T)>,
+    keys: &[String],
+    values_order: Option<&ArrayValuesOrder>,
+) -> Option<Vec<T>> {
+    let values_order = values_order.unwrap_or(&ArrayValuesOrder::Ascending);
+
+    let mut sortable_targets = Vec::with_capacity(values_with_targets.len());
+    for (value, target) in values_with_targets {
+        let tombi_document_tree::Value::Table(table) = value else {
+            return None;
+        };
+        let sort_keys = keys
+            .iter()
+            .map(|key| table.get(key).and_then(SortKey::new))
+            .collect_vec();
+        sortable_targets.push((sort_keys, target));
+    }
+
+    sortable_targets.sort_by(|(a, _), (b, _)| compare_sort_keys(a, b, values_order));
+
+    Some(
+        sortable_targets
+            .into_iter()
+            .map(|(_, target)| target)
+            .collect_vec(),
+    )
+}
+
+/// Sort the `[[array.of.tables]]` by the keys, keeping their sub tables after them.
+///
+/// The targets with empty accessors are the headers of the array of tables,
+/// and the following targets are their sub tables.
+pub fn sorted_array_of_tables<T: Clone>(
+    array: &tombi_document_tree::Array,
+    targets: &[(Vec<SchemaAccessor>, T)],
+    keys: &[String],
+    values_order: Option<&ArrayValuesOrder>,
+) -> Option<Vec<T>> {
+    let mut entries: Vec<Vec<T>> = Vec::with_capacity(array.values().len());
+    for (accessors, target) in targets {
+        if accessors.is_empty() {
+            entries.push(vec![target.clone()]);
+        } else {
+            entries.last_mut()?.push(target.clone());
+        }
+    }
+
+    if entries.len() != array.values().len() {
+        return None;
+    }
+
+    sorted_by_keys(
+        array.values().iter().zip(entries).collect_vec(),
+        keys,
+        values_order,
+    )
+    .map(|entries| entries.into_iter().flatten().collect_vec())
+}
+
+#[derive(Debug)]
+enum SortKey {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl SortKey {
+    fn new(value: &tombi_document_tree::Value) -> Option<Self> {
+        match value {
+            tombi_document_tree::Value::Boolean(boolean) => Some(Self::Boolean(boolean.value())),
+            tombi_document_tree::Value::Integer(integer) => Some(Self::Integer(integer.value())),
+            tombi_document_tree::Value::Float(float) => Some(Self::Float(float.value())),
+            tombi_document_tree::Value::String(string) => {
+                Some(Self::String(string.value().to_owned()))
+            }
+            tombi_document_tree::Value::OffsetDateTime(date_time) => {
+                Some(Self::String(date_time.value().to_string()))
+            }
+            tombi_document_tree::Value::LocalDateTime(date_time) => {
+                Some(Self::String(date_time.value().to_string()))
+            }
+            tombi_document_tree::Value::LocalDate(date) => {
+                Some(Self::String(date.value().to_string()))
+            }
+            tombi_document_tree::Value::LocalTime(time) => {
+                Some(Self::String(time.value().to_string()))
+            }
+            tombi_document_tree::Value::Array(_)
+            | tombi_document_tree::Value::Table(_)
+            | tombi_document_tree::Value::Incomplete { .. } => None,
+        }
+    }
+
+    fn type_order(&self) -> u8 {
+        match self {
+            Self::Boolean(_) => 0,
+            Self::Integer(_) | Self::Float(_) => 1,
+            Self::String(_) => 2,
+        }
+    }
+
+    fn compare(&self, other: &Self, values_order: &ArrayValuesOrder) -> Ordering {
+        match (self, other) {
+            (Self::Boolean(a), Self::Boolean(b)) => a.cmp(b),
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::Integer(a), Self::Float(b)) => (*a as f64).total_cmp(b),
+            (Self::Float(a), Self::Integer(b)) => a.total_cmp(&(*b as f64)),
+            (Self::Float(a), Self::Float(b)) => a.total_cmp(b),
+            (Self::String(a), Self::String(b)) => match values_order {
+                ArrayValuesOrder::VersionSort => tombi_version_sort::version_sort(a, b),
+                ArrayValuesOrder::Ascending | ArrayValuesOrder::Descending => a.cmp(b),
+            },
+            _ => self.type_order().cmp(&other.type_order()),
+        }
+    }
+}
+
+fn compare_sort_keys(
+    a: &[Option<SortKey>],
+    b: &[Option<SortKey>],
+    values_order: &ArrayValuesOrder,
+) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a, b) {
+            (Some(a), Some(b)) => match values_order {
+                ArrayValuesOrder::Descending => a.compare(b, values_order).reverse(),
+                ArrayValuesOrder::Ascending | ArrayValuesOrder::VersionSort => {
+                    a.compare(b, values_order)
+                }
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
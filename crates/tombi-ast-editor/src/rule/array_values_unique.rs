@@ -0,0 +1,94 @@
+use itertools::Itertools;
+use tombi_ast::AstNode;
+use tombi_document_tree::IntoDocumentTreeAndErrors;
+use tombi_schema_store::{ArraySchema, SchemaContext};
+use tombi_syntax::{SyntaxElement, SyntaxKind};
+
+use crate::Change;
+
+/// Remove the duplicate values of the array, keeping the first occurrence.
+///
+/// Returns the remaining values and the changes removing the duplicates,
+/// so that the following rules can work on the remaining values only.
+pub fn array_values_unique(
+    values_with_comma: Vec<(tombi_ast::Value, Option<tombi_ast::Comma>)>,
+    array_schema: &ArraySchema,
+    schema_context: &SchemaContext,
+) -> (
+    Vec<(tombi_ast::Value, Option<tombi_ast::Comma>)>,
+    Vec<Change>,
+) {
+    if !array_schema.is_values_unique() || values_with_comma.len() < 2 {
+        return (values_with_comma, Vec::with_capacity(0));
+    }
+
+    let mut values = Vec::with_capacity(values_with_comma.len());
+    for (value, _) in &values_with_comma {
+        let result = value
+            .clone()
+            .into_document_tree_and_errors(schema_context.toml_version);
+        if !result.errors.is_empty() {
+            tracing::debug!(
+                "Cannot remove duplicate array values because the values are incomplete."
+            );
+            return (values_with_comma, Vec::with_capacity(0));
+        }
+        values.push(result.tree);
+    }
+
+    let is_last_comma = values_with_comma
+        .last()
+        .map(|(_, comma)| comma.is_some())
+        .unwrap_or(false);
+
+    let mut changes = vec![];
+    let mut unique_values_with_comma = Vec::with_capacity(values_with_comma.len());
+    for (index, (value, comma)) in values_with_comma.into_iter().enumerate() {
+        if values[..index]
+            .iter()
+            .any(|other| other.is_same_value(&values[index]))
+        {
+            // NOTE: The whitespace before the value on the same line is removed together.
+            if let Some(whitespace) = value
+                .syntax()
+                .prev_sibling_or_token()
+                .filter(|element| element.kind() == SyntaxKind::WHITESPACE)
+            {
+                changes.push(Change::Remove { target: whitespace });
+            }
+            changes.push(Change::Remove {
+                target: SyntaxElement::Node(value.syntax().clone()),
+            });
+            if let Some(comma) = comma {
+                changes.push(Change::Remove {
+                    target: SyntaxElement::Node(comma.syntax().clone()),
+                });
+            }
+        } else {
+            unique_values_with_comma.push((value, comma));
+        }
+    }
+
+    if changes.is_empty() {
+        return (unique_values_with_comma, changes);
+    }
+
+    // NOTE: When the last value is removed, the comma of the new last value
+    //       becomes a trailing comma that did not exist before.
+    if !is_last_comma {
+        if let Some((_, comma)) = unique_values_with_comma.last_mut() {
+            if let Some(last_comma) = comma {
+                if last_comma.tailing_comment().is_none()
+                    && last_comma.leading_comments().collect_vec().is_empty()
+                {
+                    changes.push(Change::Remove {
+                        target: SyntaxElement::Node(last_comma.syntax().clone()),
+                    });
+                    *comma = None;
+                }
+            }
+        }
+    }
+
+    (unique_values_with_comma, changes)
+}
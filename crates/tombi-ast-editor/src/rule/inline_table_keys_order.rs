@@ -1,13 +1,14 @@
 use crate::rule::inline_table_comma_tailing_comment;
 use itertools::Itertools;
 use tombi_ast::AstNode;
-use tombi_schema_store::{SchemaContext, TableSchema};
+use tombi_schema_store::{SchemaAccessor, SchemaContext, TableSchema};
 use tombi_syntax::SyntaxElement;
 use tombi_version_sort::version_sort;
 use tombi_x_keyword::TableKeysOrder;
 
 pub async fn inline_table_keys_order<'a>(
     key_values_with_comma: Vec<(tombi_ast::KeyValue, Option<tombi_ast::Comma>)>,
+    accessors: &'a [SchemaAccessor],
     table_schema: &'a TableSchema,
     schema_context: &'a SchemaContext<'a>,
 ) -> Vec<crate::Change> {
@@ -15,7 +16,10 @@ pub async fn inline_table_keys_order<'a>(
         return Vec::with_capacity(0);
     }
 
-    let Some(keys_order) = table_schema.keys_order else {
+    let Some(keys_order) = schema_context
+        .table_keys_order(accessors)
+        .or(table_schema.keys_order)
+    else {
         return Vec::with_capacity(0);
     };
 
@@ -23,6 +23,7 @@ pub async fn root_table_keys_order<'a>(
                 .into_document_tree_and_errors(schema_context.toml_version)
                 .tree,
         ),
+        &[],
         key_values,
         current_schema,
         schema_context,
@@ -38,6 +39,11 @@ pub async fn root_table_keys_order<'a>(
         SyntaxElement::Node(table_or_array_of_tables.last().unwrap().syntax().clone()),
     );
 
+    let old_nodes = table_or_array_of_tables
+        .iter()
+        .map(|table| table.syntax().clone())
+        .collect_vec();
+
     let targets = table_or_array_of_tables
         .clone()
         .into_iter()
@@ -76,7 +82,14 @@ pub async fn root_table_keys_order<'a>(
     .map(|kv| SyntaxElement::Node(kv.syntax().clone()))
     .collect_vec();
 
-    changes.push(crate::Change::ReplaceRange { old, new });
+    // NOTE: Keep the line breaks between the tables when the order does not change.
+    if !new
+        .iter()
+        .map(|element| element.as_node())
+        .eq(old_nodes.iter().map(Some))
+    {
+        changes.push(crate::Change::ReplaceRange { old, new });
+    }
 
     changes
 }
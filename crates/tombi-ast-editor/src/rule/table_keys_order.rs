@@ -5,15 +5,18 @@ use itertools::Itertools;
 use tombi_ast::AstNode;
 use tombi_future::{BoxFuture, Boxable};
 use tombi_schema_store::{
-    AllOfSchema, AnyOfSchema, CurrentSchema, OneOfSchema, PropertySchema, SchemaAccessor,
-    SchemaContext, ValueSchema,
+    AllOfSchema, AnyOfSchema, ArraySchema, CurrentSchema, OneOfSchema, PropertySchema,
+    SchemaAccessor, SchemaContext, TableSchema, ValueSchema,
 };
 use tombi_syntax::SyntaxElement;
 use tombi_validator::Validate;
 use tombi_x_keyword::TableKeysOrder;
 
+use crate::rule::sorted_array_of_tables;
+
 pub async fn table_keys_order<'a>(
     value: &'a tombi_document_tree::Value,
+    accessors: &'a [SchemaAccessor],
     key_values: Vec<tombi_ast::KeyValue>,
     current_schema: Option<&'a CurrentSchema<'a>>,
     schema_context: &'a SchemaContext<'a>,
@@ -27,6 +30,11 @@ pub async fn table_keys_order<'a>(
         SyntaxElement::Node(key_values.last().unwrap().syntax().clone()),
     );
 
+    let old_nodes = key_values
+        .iter()
+        .map(|kv| kv.syntax().clone())
+        .collect_vec();
+
     let targets = key_values
         .into_iter()
         .map(|kv| {
@@ -47,12 +55,21 @@ pub async fn table_keys_order<'a>(
         })
         .collect_vec();
 
-    let new = sorted_accessors(value, &[], targets, current_schema, schema_context)
+    let new = sorted_accessors(value, accessors, targets, current_schema, schema_context)
         .await
         .into_iter()
         .map(|kv| SyntaxElement::Node(kv.syntax().clone()))
         .collect_vec();
 
+    // NOTE: Keep the line breaks between the key-values when the order does not change.
+    if new
+        .iter()
+        .map(|element| element.as_node())
+        .eq(old_nodes.iter().map(Some))
+    {
+        return Vec::with_capacity(0);
+    }
+
     vec![crate::Change::ReplaceRange { old, new }]
 }
 
@@ -108,6 +125,22 @@ where
                     }
                     return targets.into_iter().map(|(_, target)| target).collect_vec();
                 }
+                ValueSchema::Array(ArraySchema {
+                    values_order_by: Some(keys),
+                    values_order,
+                    ..
+                }) => {
+                    if let tombi_document_tree::Value::Array(array) = value {
+                        let values_order = schema_context
+                            .array_values_order(validation_accessors)
+                            .or_else(|| values_order.clone());
+                        if let Some(sorted_targets) =
+                            sorted_array_of_tables(array, &targets, keys, values_order.as_ref())
+                        {
+                            return sorted_targets;
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -132,48 +165,12 @@ where
                         .iter()
                         .all(|(accessor, _)| matches!(accessor, SchemaAccessor::Key(_)))
                     {
-                        let sorted_targets = match table_schema.keys_order {
-                            Some(TableKeysOrder::Ascending) => new_targets_map
-                                .into_iter()
-                                .sorted_by(|(a_accessor, _), (b_accessor, _)| {
-                                    a_accessor.partial_cmp(b_accessor).unwrap()
-                                })
-                                .collect_vec(),
-                            Some(TableKeysOrder::Descending) => new_targets_map
-                                .into_iter()
-                                .sorted_by(|(a_accessor, _), (b_accessor, _)| {
-                                    b_accessor.partial_cmp(a_accessor).unwrap()
-                                })
-                                .rev()
-                                .collect_vec(),
-                            Some(TableKeysOrder::Schema) => {
-                                let mut sorted_targets = Vec::with_capacity(new_targets_map.len());
-
-                                for accessor in table_schema.properties.read().await.keys() {
-                                    if let Some(targets) = new_targets_map.shift_remove(accessor) {
-                                        sorted_targets.push((accessor.to_owned(), targets));
-                                    }
-                                }
-                                sorted_targets.extend(new_targets_map);
-                                sorted_targets
-                            }
-                            Some(TableKeysOrder::VersionSort) => {
-                                let mut sorted_targets = new_targets_map.into_iter().collect_vec();
-                                sorted_targets.sort_by(|(a_accessor, _), (b_accessor, _)| {
-                                    match (a_accessor, b_accessor) {
-                                        (
-                                            SchemaAccessor::Key(a_key),
-                                            SchemaAccessor::Key(b_key),
-                                        ) => tombi_version_sort::version_sort(a_key, b_key),
-                                        _ => unreachable!(
-                                            "Unexpected accessor type in table keys order sorting"
-                                        ),
-                                    }
-                                });
-                                sorted_targets
-                            }
-                            None => new_targets_map.into_iter().collect_vec(),
-                        };
+                        let keys_order = schema_context
+                            .table_keys_order(validation_accessors)
+                            .or(table_schema.keys_order);
+                        let sorted_targets =
+                            sorted_targets_map(new_targets_map, keys_order, Some(table_schema))
+                                .await;
 
                         for (accessor, targets) in sorted_targets {
                             if let Some(value) = table.get(&accessor.to_string()) {
@@ -264,11 +261,16 @@ where
                                 )
                                 .await
                             {
+                                let validation_accessors = validation_accessors
+                                    .iter()
+                                    .cloned()
+                                    .chain(std::iter::once(SchemaAccessor::Index))
+                                    .collect_vec();
                                 for (value, (_, targets)) in array.iter().zip(new_targets_map) {
                                     results.extend(
                                         sorted_accessors(
                                             value,
-                                            validation_accessors,
+                                            &validation_accessors,
                                             targets,
                                             Some(&current_schema),
                                             schema_context,
@@ -292,6 +294,44 @@ where
             }
         }
 
+        // NOTE: Without the schema, only the table keys order of the config overrides applies.
+        if current_schema.is_none()
+            && schema_context
+                .order_overrides
+                .is_some_and(|order_overrides| !order_overrides.is_empty())
+        {
+            if let tombi_document_tree::Value::Table(table) = value {
+                if new_targets_map
+                    .iter()
+                    .all(|(accessor, _)| matches!(accessor, SchemaAccessor::Key(_)))
+                {
+                    let keys_order = schema_context.table_keys_order(validation_accessors);
+                    for (accessor, targets) in
+                        sorted_targets_map(new_targets_map, keys_order, None).await
+                    {
+                        match table.get(&accessor.to_string()) {
+                            Some(value) => results.extend(
+                                sorted_accessors(
+                                    value,
+                                    &validation_accessors
+                                        .iter()
+                                        .cloned()
+                                        .chain(std::iter::once(accessor))
+                                        .collect_vec(),
+                                    targets,
+                                    None,
+                                    schema_context,
+                                )
+                                .await,
+                            ),
+                            None => results.extend(targets.into_iter().map(|(_, target)| target)),
+                        }
+                    }
+                    return results;
+                }
+            }
+        }
+
         for (_, targets) in new_targets_map {
             results.extend(targets.into_iter().map(|(_, target)| target));
         }
@@ -300,3 +340,51 @@ where
     }
     .boxed()
 }
+
+async fn sorted_targets_map<T>(
+    mut new_targets_map: IndexMap<SchemaAccessor, T>,
+    keys_order: Option<TableKeysOrder>,
+    table_schema: Option<&TableSchema>,
+) -> Vec<(SchemaAccessor, T)> {
+    match keys_order {
+        Some(TableKeysOrder::Ascending) => new_targets_map
+            .into_iter()
+            .sorted_by(|(a_accessor, _), (b_accessor, _)| {
+                a_accessor.partial_cmp(b_accessor).unwrap()
+            })
+            .collect_vec(),
+        Some(TableKeysOrder::Descending) => new_targets_map
+            .into_iter()
+            .sorted_by(|(a_accessor, _), (b_accessor, _)| {
+                b_accessor.partial_cmp(a_accessor).unwrap()
+            })
+            .rev()
+            .collect_vec(),
+        Some(TableKeysOrder::Schema) => {
+            let mut sorted_targets = Vec::with_capacity(new_targets_map.len());
+
+            if let Some(table_schema) = table_schema {
+                for accessor in table_schema.properties.read().await.keys() {
+                    if let Some(targets) = new_targets_map.shift_remove(accessor) {
+                        sorted_targets.push((accessor.to_owned(), targets));
+                    }
+                }
+            }
+            sorted_targets.extend(new_targets_map);
+            sorted_targets
+        }
+        Some(TableKeysOrder::VersionSort) => {
+            let mut sorted_targets = new_targets_map.into_iter().collect_vec();
+            sorted_targets.sort_by(|(a_accessor, _), (b_accessor, _)| {
+                match (a_accessor, b_accessor) {
+                    (SchemaAccessor::Key(a_key), SchemaAccessor::Key(b_key)) => {
+                        tombi_version_sort::version_sort(a_key, b_key)
+                    }
+                    _ => unreachable!("Unexpected accessor type in table keys order sorting"),
+                }
+            });
+            sorted_targets
+        }
+        None => new_targets_map.into_iter().collect_vec(),
+    }
+}
@@ -0,0 +1,605 @@
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+use tombi_ast::AstNode;
+use tombi_document_tree::IntoDocumentTreeAndErrors;
+use tombi_schema_store::{
+    CurrentSchema, GetHeaderSchemarAccessors, SchemaAccessor, SchemaContext, ValueSchema,
+};
+use tombi_syntax::SyntaxElement;
+use tombi_toml_version::TomlVersion;
+use tombi_x_keyword::TableStyle;
+
+use crate::edit::get_schema;
+use crate::text::{
+    key_value_text, keys_text, leading_comments_text, tailing_comment_text, value_text,
+};
+
+/// Rewrites tables into the style specified by `x-tombi-table-style`.
+///
+/// ```toml
+/// serde = { version = "1", features = ["derive"] }  # inline
+/// serde.version = "1"                                # dotted
+///
+/// [dependencies.serde]                               # section
+/// version = "1"
+/// ```
+///
+/// Because this rule moves key-values between tables,
+/// it is applied before the other rules.
+/// Tables whose comments cannot be preserved are left as they are.
+pub async fn table_style<'a>(
+    root: &'a tombi_ast::Root,
+    current_schema: Option<&'a CurrentSchema<'a>>,
+    schema_context: &'a SchemaContext<'a>,
+) -> Vec<crate::Change> {
+    let Some(current_schema) = current_schema else {
+        return Vec::with_capacity(0);
+    };
+    let toml_version = schema_context.toml_version;
+
+    let items = root
+        .table_or_array_of_tables()
+        .filter_map(|item| {
+            let keys = header_raw_keys(&item, toml_version)?;
+            Some((keys, item))
+        })
+        .collect_vec();
+
+    let mut appends = vec![];
+    let mut changes = vec![];
+    let mut parent_key_values: IndexMap<Option<usize>, Vec<String>> = IndexMap::new();
+    let mut converted_items = vec![];
+
+    // [parent.key] -> key = { ... } or key.x = ...
+    for (index, (keys, item)) in items.iter().enumerate() {
+        let tombi_ast::TableOrArrayOfTable::Table(table) = item else {
+            continue;
+        };
+        let Some(style) = section_table_style(table, current_schema, schema_context).await else {
+            continue;
+        };
+        if style == TableStyle::Section {
+            continue;
+        }
+        if items
+            .iter()
+            .any(|(other_keys, _)| other_keys.len() > keys.len() && other_keys.starts_with(keys))
+        {
+            continue;
+        }
+
+        let parent_keys = &keys[..keys.len() - 1];
+        let parent = if parent_keys.is_empty() {
+            None
+        } else {
+            let Some(parent_index) = items[..index]
+                .iter()
+                .rposition(|(other_keys, _)| other_keys == parent_keys)
+                .or_else(|| {
+                    items
+                        .iter()
+                        .enumerate()
+                        .skip(index + 1)
+                        .find(|(_, (other_keys, other))| {
+                            other_keys == parent_keys
+                                && matches!(other, tombi_ast::TableOrArrayOfTable::Table(_))
+                        })
+                        .map(|(other_index, _)| other_index)
+                })
+            else {
+                continue;
+            };
+            Some(parent_index)
+        };
+
+        let Some(text) = (match style {
+            TableStyle::Inline => section_to_inline_text(table),
+            TableStyle::Dotted => section_to_dotted_text(table),
+            TableStyle::Section => None,
+        }) else {
+            continue;
+        };
+
+        parent_key_values.entry(parent).or_default().push(text);
+        changes.push(crate::Change::Remove {
+            target: SyntaxElement::Node(table.syntax().clone()),
+        });
+        converted_items.push(index);
+    }
+
+    // key = { ... } <-> key.x = ... -> [parent.key]
+    let mut body_tables = vec![];
+    let root_key_values = root.key_values().collect_vec();
+    let root_value = tombi_document_tree::Value::Table(
+        root_key_values
+            .clone()
+            .into_document_tree_and_errors(toml_version)
+            .tree,
+    );
+    let tables = key_values_table_style(
+        &root_key_values,
+        None,
+        &root_value,
+        current_schema,
+        schema_context,
+        &mut changes,
+    )
+    .await;
+    if let (false, Some(last_key_value)) = (tables.is_empty(), root_key_values.last()) {
+        body_tables.push((SyntaxElement::Node(last_key_value.syntax().clone()), tables));
+    }
+
+    for (index, (_, item)) in items.iter().enumerate() {
+        if converted_items.contains(&index) {
+            continue;
+        }
+        let Some(header_accessors) = (match item {
+            tombi_ast::TableOrArrayOfTable::Table(table) => {
+                table.get_header_schema_accessor(toml_version)
+            }
+            tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => {
+                array_of_table.get_header_schema_accessor(toml_version)
+            }
+        }) else {
+            continue;
+        };
+        let Some(header) = item.header() else {
+            continue;
+        };
+
+        let item_value = tombi_document_tree::Value::Table(match item {
+            tombi_ast::TableOrArrayOfTable::Table(table) => {
+                table
+                    .clone()
+                    .into_document_tree_and_errors(toml_version)
+                    .tree
+            }
+            tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => {
+                array_of_table
+                    .clone()
+                    .into_document_tree_and_errors(toml_version)
+                    .tree
+            }
+        });
+        let Some(value_schema) = get_schema(
+            &item_value,
+            &header_accessors,
+            current_schema,
+            schema_context,
+        )
+        .await
+        else {
+            continue;
+        };
+        let item_schema = CurrentSchema {
+            value_schema: Cow::Owned(value_schema),
+            schema_url: current_schema.schema_url.clone(),
+            definitions: current_schema.definitions.clone(),
+        };
+        let Some(value) = dig_value(&item_value, &header_accessors) else {
+            continue;
+        };
+
+        let key_values = match item {
+            tombi_ast::TableOrArrayOfTable::Table(table) => table.key_values().collect_vec(),
+            tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => {
+                array_of_table.key_values().collect_vec()
+            }
+        };
+        let tables = key_values_table_style(
+            &key_values,
+            Some(&keys_text(&header)),
+            value,
+            &item_schema,
+            schema_context,
+            &mut changes,
+        )
+        .await;
+        if !tables.is_empty() {
+            body_tables.push((SyntaxElement::Node(item.syntax().clone()), tables));
+        }
+    }
+
+    for (base, tables) in body_tables {
+        appends.push(crate::Change::Append {
+            base,
+            new: make_nodes(&tables.join("\n\n"), toml_version),
+        });
+    }
+
+    // NOTE: Key-values are appended after the tables,
+    //       so that they are placed before the tables of the root.
+    for (parent, texts) in parent_key_values {
+        let new = make_nodes(&texts.join("\n"), toml_version);
+        match parent {
+            Some(parent_index) => {
+                let base = match &items[parent_index].1 {
+                    tombi_ast::TableOrArrayOfTable::Table(table) => table
+                        .key_values()
+                        .last()
+                        .map(|key_value| SyntaxElement::Node(key_value.syntax().clone()))
+                        .or_else(|| {
+                            table
+                                .header_tailing_comment()
+                                .map(|comment| SyntaxElement::Token(comment.syntax().clone()))
+                        })
+                        .or_else(|| table.bracket_end().map(SyntaxElement::Token)),
+                    tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => array_of_table
+                        .key_values()
+                        .last()
+                        .map(|key_value| SyntaxElement::Node(key_value.syntax().clone()))
+                        .or_else(|| {
+                            array_of_table
+                                .header_tailing_comment()
+                                .map(|comment| SyntaxElement::Token(comment.syntax().clone()))
+                        })
+                        .or_else(|| {
+                            array_of_table
+                                .double_bracket_end()
+                                .map(SyntaxElement::Token)
+                        }),
+                };
+                if let Some(base) = base {
+                    appends.push(crate::Change::Append { base, new });
+                }
+            }
+            None => {
+                let base = root_key_values
+                    .last()
+                    .map(|key_value| SyntaxElement::Node(key_value.syntax().clone()))
+                    .or_else(|| {
+                        items
+                            .first()
+                            .and_then(|(_, item)| item.syntax().prev_sibling_or_token())
+                    });
+                match base {
+                    Some(base) => appends.push(crate::Change::Append { base, new }),
+                    None => appends.push(crate::Change::AppendTop { new }),
+                }
+            }
+        }
+    }
+
+    appends.extend(changes);
+    appends
+}
+
+/// Rewrites the inline tables and the dotted keys of the key-values,
+/// and returns the texts of the tables to be added after the body.
+async fn key_values_table_style<'a>(
+    key_values: &[tombi_ast::KeyValue],
+    header_text: Option<&str>,
+    value: &tombi_document_tree::Value,
+    current_schema: &CurrentSchema<'a>,
+    schema_context: &SchemaContext<'a>,
+    changes: &mut Vec<crate::Change>,
+) -> Vec<String> {
+    let toml_version = schema_context.toml_version;
+    let mut groups: IndexMap<String, Vec<&tombi_ast::KeyValue>> = IndexMap::new();
+    for key_value in key_values {
+        let Some(first_key) = key_value.keys().and_then(|keys| keys.keys().next()) else {
+            continue;
+        };
+        let Ok(first_key) = first_key.try_to_raw_text(toml_version) else {
+            continue;
+        };
+        groups.entry(first_key).or_default().push(key_value);
+    }
+
+    let mut tables = vec![];
+    for (key, key_values) in groups {
+        let Some(form) = current_style(&key_values) else {
+            continue;
+        };
+        let Some(ValueSchema::Table(table_schema)) = get_schema(
+            value,
+            &[SchemaAccessor::Key(key)],
+            current_schema,
+            schema_context,
+        )
+        .await
+        else {
+            continue;
+        };
+        let Some(style) = table_schema.style else {
+            continue;
+        };
+        if style == form {
+            continue;
+        }
+
+        let Some(key_text) = key_values[0]
+            .keys()
+            .and_then(|keys| keys.keys().next())
+            .map(|key| key.syntax().to_string())
+        else {
+            continue;
+        };
+
+        match (form, style) {
+            (TableStyle::Inline, TableStyle::Dotted) => {
+                let Some(text) = inline_to_dotted_text(key_values[0], &key_text) else {
+                    continue;
+                };
+                changes.push(crate::Change::ReplaceRange {
+                    old: SyntaxElement::Node(key_values[0].syntax().clone())
+                        ..=SyntaxElement::Node(key_values[0].syntax().clone()),
+                    new: make_nodes(&text, toml_version),
+                });
+            }
+            (TableStyle::Dotted, TableStyle::Inline) => {
+                let Some(text) = dotted_to_inline_text(&key_values, &key_text) else {
+                    continue;
+                };
+                changes.push(crate::Change::ReplaceRange {
+                    old: SyntaxElement::Node(key_values[0].syntax().clone())
+                        ..=SyntaxElement::Node(key_values[0].syntax().clone()),
+                    new: make_nodes(&text, toml_version),
+                });
+                for key_value in &key_values[1..] {
+                    changes.push(crate::Change::Remove {
+                        target: SyntaxElement::Node(key_value.syntax().clone()),
+                    });
+                }
+            }
+            (_, TableStyle::Section) => {
+                let table_header = match header_text {
+                    Some(header_text) => format!("{header_text}.{key_text}"),
+                    None => key_text,
+                };
+                let Some(text) = (match form {
+                    TableStyle::Inline => inline_to_section_text(key_values[0], &table_header),
+                    _ => Some(dotted_to_section_text(&key_values, &table_header)),
+                }) else {
+                    continue;
+                };
+                tables.push(text);
+                for key_value in &key_values {
+                    changes.push(crate::Change::Remove {
+                        target: SyntaxElement::Node(key_value.syntax().clone()),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tables
+}
+
+async fn section_table_style<'a>(
+    table: &tombi_ast::Table,
+    current_schema: &CurrentSchema<'a>,
+    schema_context: &SchemaContext<'a>,
+) -> Option<TableStyle> {
+    let header_accessors = table.get_header_schema_accessor(schema_context.toml_version)?;
+    let value = tombi_document_tree::Value::Table(
+        table
+            .clone()
+            .into_document_tree_and_errors(schema_context.toml_version)
+            .tree,
+    );
+    match get_schema(&value, &header_accessors, current_schema, schema_context).await {
+        Some(ValueSchema::Table(table_schema)) => table_schema.style,
+        _ => None,
+    }
+}
+
+/// Returns the current style of the key-values sharing the same first key.
+fn current_style(key_values: &[&tombi_ast::KeyValue]) -> Option<TableStyle> {
+    let keys_counts = key_values
+        .iter()
+        .map(|key_value| key_value.keys().map(|keys| keys.keys().count()))
+        .collect::<Option<Vec<_>>>()?;
+
+    if keys_counts.iter().all(|count| *count > 1) {
+        Some(TableStyle::Dotted)
+    } else if keys_counts == [1]
+        && matches!(
+            key_values[0].value(),
+            Some(tombi_ast::Value::InlineTable(_))
+        )
+    {
+        Some(TableStyle::Inline)
+    } else {
+        None
+    }
+}
+
+fn header_raw_keys(
+    item: &tombi_ast::TableOrArrayOfTable,
+    toml_version: TomlVersion,
+) -> Option<Vec<String>> {
+    item.header()?
+        .keys()
+        .map(|key| key.try_to_raw_text(toml_version).ok())
+        .collect()
+}
+
+fn dig_value<'a>(
+    mut value: &'a tombi_document_tree::Value,
+    accessors: &[SchemaAccessor],
+) -> Option<&'a tombi_document_tree::Value> {
+    for accessor in accessors {
+        value = match (value, accessor) {
+            (tombi_document_tree::Value::Table(table), SchemaAccessor::Key(key)) => {
+                table.get(key)?
+            }
+            (tombi_document_tree::Value::Array(array), SchemaAccessor::Index) => array.get(0)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn has_comments(key_value: &tombi_ast::KeyValue) -> bool {
+    key_value.leading_comments().next().is_some() || key_value.tailing_comment().is_some()
+}
+
+fn has_dangling_comments(table: &tombi_ast::Table) -> bool {
+    !table.key_values_begin_dangling_comments().is_empty()
+        || !table.key_values_dangling_comments().is_empty()
+        || !table.key_values_end_dangling_comments().is_empty()
+}
+
+fn section_to_inline_text(table: &tombi_ast::Table) -> Option<String> {
+    if has_dangling_comments(table) {
+        return None;
+    }
+    let key_text = table.header()?.keys().last()?.syntax().to_string();
+    let key_values = table.key_values().collect_vec();
+    if key_values.iter().any(has_comments) {
+        return None;
+    }
+    let inner = key_values
+        .iter()
+        .map(key_value_text)
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!(
+        "{}{key_text} = {}{}",
+        leading_comments_text(table.header_leading_comments()),
+        if inner.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{ {} }}", inner.join(", "))
+        },
+        tailing_comment_text(table.header_tailing_comment()),
+    ))
+}
+
+fn section_to_dotted_text(table: &tombi_ast::Table) -> Option<String> {
+    if has_dangling_comments(table) || table.header_tailing_comment().is_some() {
+        return None;
+    }
+    let key_text = table.header()?.keys().last()?.syntax().to_string();
+    let key_values = table.key_values().collect_vec();
+    if key_values.is_empty() {
+        return None;
+    }
+    let lines = key_values
+        .iter()
+        .map(|key_value| {
+            Some(format!(
+                "{}{key_text}.{} = {}",
+                leading_comments_text(key_value.leading_comments()),
+                keys_text(&key_value.keys()?),
+                key_value.value()?.syntax().to_string().trim(),
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!(
+        "{}{}",
+        leading_comments_text(table.header_leading_comments()),
+        lines.join("\n")
+    ))
+}
+
+fn inline_table_key_values(key_value: &tombi_ast::KeyValue) -> Option<Vec<tombi_ast::KeyValue>> {
+    let Some(tombi_ast::Value::InlineTable(inline_table)) = key_value.value() else {
+        return None;
+    };
+    if inline_table.has_inner_comments() {
+        return None;
+    }
+    Some(inline_table.key_values().collect_vec())
+}
+
+fn inline_to_dotted_text(key_value: &tombi_ast::KeyValue, key_text: &str) -> Option<String> {
+    let inner = inline_table_key_values(key_value)?;
+    if inner.is_empty() {
+        return None;
+    }
+    let lines = inner
+        .iter()
+        .map(|inner| key_value_text(inner).map(|text| format!("{key_text}.{text}")))
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!(
+        "{}{}{}",
+        leading_comments_text(key_value.leading_comments()),
+        lines.join("\n"),
+        tailing_comment_text(key_value.tailing_comment()),
+    ))
+}
+
+fn inline_to_section_text(key_value: &tombi_ast::KeyValue, table_header: &str) -> Option<String> {
+    let inner = inline_table_key_values(key_value)?;
+    let lines = inner
+        .iter()
+        .map(key_value_text)
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!(
+        "{}[{table_header}]{}\n{}",
+        leading_comments_text(key_value.leading_comments()),
+        tailing_comment_text(key_value.tailing_comment()),
+        lines.join("\n"),
+    ))
+}
+
+fn dotted_to_inline_text(key_values: &[&tombi_ast::KeyValue], key_text: &str) -> Option<String> {
+    let (first, last) = (key_values.first()?, key_values.last()?);
+    if key_values
+        .iter()
+        .skip(1)
+        .any(|key_value| key_value.leading_comments().next().is_some())
+        || key_values[..key_values.len() - 1]
+            .iter()
+            .any(|key_value| key_value.tailing_comment().is_some())
+    {
+        return None;
+    }
+    let inner = key_values
+        .iter()
+        .map(|key_value| {
+            let keys = key_value.keys()?;
+            Some(format!(
+                "{} = {}",
+                keys.keys()
+                    .skip(1)
+                    .map(|key| key.syntax().to_string())
+                    .join("."),
+                value_text(&key_value.value()?),
+            ))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!(
+        "{}{key_text} = {{ {} }}{}",
+        leading_comments_text(first.leading_comments()),
+        inner.join(", "),
+        tailing_comment_text(last.tailing_comment()),
+    ))
+}
+
+fn dotted_to_section_text(key_values: &[&tombi_ast::KeyValue], table_header: &str) -> String {
+    let lines = key_values
+        .iter()
+        .filter_map(|key_value| {
+            let keys = key_value.keys()?;
+            Some(format!(
+                "{}{} = {}",
+                leading_comments_text(key_value.leading_comments()),
+                keys.keys()
+                    .skip(1)
+                    .map(|key| key.syntax().to_string())
+                    .join("."),
+                key_value.value()?.syntax().to_string().trim(),
+            ))
+        })
+        .collect_vec();
+
+    format!("[{table_header}]\n{}", lines.join("\n"))
+}
+
+fn make_nodes(text: &str, toml_version: TomlVersion) -> Vec<SyntaxElement> {
+    tombi_parser::parse(text, toml_version)
+        .into_syntax_node_mut()
+        .children()
+        .map(SyntaxElement::Node)
+        .collect_vec()
+}
@@ -0,0 +1,43 @@
+//! The texts of the AST nodes to rebuild them in another form,
+//! such as a table section into an inline table.
+
+use itertools::Itertools;
+use tombi_ast::AstNode;
+
+/// Returns the dotted keys, such as `a.b.c`.
+pub fn keys_text(keys: &tombi_ast::Keys) -> String {
+    keys.keys().map(|key| key.syntax().to_string()).join(".")
+}
+
+/// Returns the leading comments, each followed by a line break.
+pub fn leading_comments_text(comments: impl Iterator<Item = tombi_ast::LeadingComment>) -> String {
+    comments
+        .map(|comment| format!("{}\n", comment.syntax().text()))
+        .collect()
+}
+
+/// Returns the tailing comment with a leading space, or an empty string.
+pub fn tailing_comment_text(comment: Option<tombi_ast::TailingComment>) -> String {
+    comment
+        .map(|comment| format!(" {}", comment.syntax().text()))
+        .unwrap_or_default()
+}
+
+/// Returns `keys = value` of the key-value, without its comments.
+pub fn key_value_text(key_value: &tombi_ast::KeyValue) -> Option<String> {
+    let keys = key_value.keys()?;
+    let value = key_value.value()?;
+
+    Some(format!("{} = {}", keys_text(&keys), value_text(&value)))
+}
+
+/// Returns the text of the value, without its tailing comment.
+pub fn value_text(value: &tombi_ast::Value) -> String {
+    let text = value.syntax().to_string();
+    match value.tailing_comment() {
+        Some(comment) => text[..text.len() - comment.syntax().text().len()]
+            .trim()
+            .to_string(),
+        None => text.trim().to_string(),
+    }
+}
@@ -2,26 +2,121 @@
 //!
 //! Options for adjusting the formatting of TOML files.
 //! Initially, this structure contained settings related to `line-width`, etc.,
-//! but to avoid unnecessary discussions about the format, all settings have been moved to [formatter::FormatDefinition].
-//! In the future, there is a possibility that options will be added to this structure,
-//! but considering the recent trend of formatters to avoid such discussions by restricting the settings and its results,
-//! this structure is currently empty.
+//! but to avoid unnecessary discussions about the format, most settings have been moved to [formatter::FormatDefinition].
+//! The blank line policy is configurable here,
+//! because the meaningful grouping of sections differs from file to file.
+//! The opt-in styles, which are off by default, are also configurable here.
 
 /// # Formatter options.
 ///
 /// To avoid needless discussion of formatting rules,
-/// we do not currently have a configuration item for formatting.
+/// only the blank line policy and the opt-in styles are configurable.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 #[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "jsonschema", schemars(extend("x-tombi-table-keys-order" = tombi_x_keyword::TableKeysOrder::Schema)))]
 #[derive(Debug, Clone, PartialEq)]
-pub struct FormatOptions {}
+pub struct FormatOptions {
+    /// # The maximum number of consecutive blank lines to preserve.
+    ///
+    /// The blank lines between key-values and before table headers are preserved up to this number.
+    /// If `0`, the blank lines between key-values are removed.
+    #[cfg_attr(feature = "jsonschema", schemars(default = "max_blank_lines_default"))]
+    pub max_blank_lines: Option<u8>,
+
+    /// # Insert a blank line before each table header.
+    ///
+    /// If `true`, a blank line is inserted even between an empty table and its subtable.
+    pub blank_line_before_table_header: Option<bool>,
+
+    /// # Remove the blank line before subtables.
+    ///
+    /// If `true`, no blank line is inserted between a table
+    /// and its immediately-following subtables.
+    ///
+    /// ```toml
+    /// [package]
+    /// name = "tombi"
+    /// [package.metadata]
+    /// key = "value"
+    /// ```
+    pub compact_subtables: Option<bool>,
+
+    /// # Remove the blank lines between array of tables.
+    ///
+    /// If `true`, the consecutive entries of the same array of tables are grouped without blank lines.
+    ///
+    /// ```toml
+    /// [[bin]]
+    /// name = "tombi"
+    /// [[bin]]
+    /// name = "tombi-lsp"
+    /// ```
+    pub compact_array_of_tables: Option<bool>,
+
+    /// # Group the digits of decimal integers.
+    ///
+    /// If `true`, decimal integers with five or more digits are grouped by three digits with `_`.
+    ///
+    /// - Example: `1000000` -> `1_000_000`
+    /// - Example: `1_0_0` -> `100`
+    pub integer_digit_grouping: Option<bool>,
+
+    /// # The letter case of hexadecimal digits.
+    ///
+    /// The `0x`, `0o` and `0b` prefixes are always written in lowercase, as TOML requires.
+    #[cfg_attr(feature = "jsonschema", schemars(default = "crate::HexCase::default"))]
+    pub hex_case: Option<crate::HexCase>,
+
+    /// # Normalize the exponent of floats.
+    ///
+    /// If `true`, the exponent is written with a lowercase `e`, without `+` and leading zeros.
+    ///
+    /// - Example: `5E+022` -> `5e22`
+    pub float_exponent_normalization: Option<bool>,
+
+    /// # Wrap long strings.
+    ///
+    /// If `true`, a basic string of a key-value exceeding the line width is converted
+    /// to a multi-line basic string, split at spaces with line ending backslashes.
+    pub string_line_wrap: Option<bool>,
+
+    /// # Align the equal signs of key-values.
+    ///
+    /// If `true`, `=` is aligned within a run of single-line key-values
+    /// that is not separated by blank lines or comments.
+    pub key_value_equal_alignment: Option<bool>,
+
+    /// # Align the tailing comments.
+    ///
+    /// If `true`, the tailing comments are aligned within a run of single-line key-values
+    /// and within the single-line values of a multi-line array.
+    pub tailing_comment_alignment: Option<bool>,
+
+    /// # Reflow the leading comments.
+    ///
+    /// If `true`, the paragraphs and the bullet list items of the leading comments
+    /// exceeding the line width are wrapped.
+    /// Directive comments like `#:schema` and commented-out TOML lines are never changed.
+    pub comment_reflow: Option<bool>,
+}
 
 impl FormatOptions {
     pub const fn default() -> Self {
-        Self {}
+        Self {
+            max_blank_lines: None,
+            blank_line_before_table_header: None,
+            compact_subtables: None,
+            compact_array_of_tables: None,
+            integer_digit_grouping: None,
+            hex_case: None,
+            float_exponent_normalization: None,
+            string_line_wrap: None,
+            key_value_equal_alignment: None,
+            tailing_comment_alignment: None,
+            comment_reflow: None,
+        }
     }
 }
 
@@ -30,3 +125,8 @@ impl Default for FormatOptions {
         Self::default()
     }
 }
+
+#[cfg(feature = "jsonschema")]
+fn max_blank_lines_default() -> Option<u8> {
+    Some(0)
+}
@@ -1,6 +1,7 @@
 mod error;
 pub mod format;
 mod lint;
+mod overrides;
 mod schema;
 mod server;
 mod types;
@@ -8,8 +9,9 @@ mod types;
 pub use error::Error;
 pub use format::FormatOptions;
 pub use lint::{LintOptions, SeverityLevel};
-pub use schema::SchemaOptions;
+pub use overrides::OverrideItem;
 pub use schema::{RootSchema, Schema, SubSchema};
+pub use schema::{SchemaCache, SchemaOptions, DEFAULT_SCHEMA_CACHE_TTL};
 pub use server::{LspCompletion, LspOptions};
 pub use tombi_toml_version::TomlVersion;
 pub use types::*;
@@ -70,6 +72,11 @@ pub struct Config {
 
     /// # Schema catalog items.
     pub schemas: Option<Vec<Schema>>,
+
+    /// # Override items.
+    ///
+    /// Overrides the ordering of the keys and values of the matched files.
+    pub overrides: Option<Vec<OverrideItem>>,
 }
 
 impl Config {
@@ -0,0 +1,38 @@
+use tombi_x_keyword::{ArrayValuesOrder, TableKeysOrder};
+
+/// # The override for the files and keys.
+///
+/// Overrides the ordering of the JSON Schema without editing it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "jsonschema", schemars(extend("x-tombi-table-keys-order" = tombi_x_keyword::TableKeysOrder::Schema)))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverrideItem {
+    /// # The file match pattern of the override.
+    ///
+    /// The file match pattern to include the target to apply the override.
+    /// Supports glob pattern.
+    #[cfg_attr(feature = "jsonschema", schemars(length(min = 1)))]
+    pub include: Vec<String>,
+
+    /// # The accessors to apply the override.
+    ///
+    /// An empty string targets the root table,
+    /// which orders the top-level tables of the file.
+    #[cfg_attr(feature = "jsonschema", schemars(length(min = 1)))]
+    #[cfg_attr(feature = "jsonschema", schemars(example = ["project.dependencies"]))]
+    #[cfg_attr(feature = "jsonschema", schemars(example = ["tool.ruff.lint.select"]))]
+    pub keys: Vec<String>,
+
+    /// # The order of the table keys.
+    ///
+    /// Takes precedence over `x-tombi-table-keys-order` of the JSON Schema.
+    pub table_keys_order: Option<TableKeysOrder>,
+
+    /// # The order of the array values.
+    ///
+    /// Takes precedence over `x-tombi-array-values-order` of the JSON Schema.
+    pub array_values_order: Option<ArrayValuesOrder>,
+}
@@ -23,6 +23,11 @@ pub struct SchemaOptions {
 
     /// # Schema catalog options.
     pub catalog: Option<SchemaCatalog>,
+
+    /// # Schema cache options.
+    ///
+    /// Remote schemas and catalogs are cached in the user cache directory.
+    pub cache: Option<SchemaCache>,
 }
 
 impl SchemaOptions {
@@ -31,6 +36,7 @@ impl SchemaOptions {
             enabled: None,
             strict: None,
             catalog: None,
+            cache: None,
         }
     }
 
@@ -50,7 +56,47 @@ impl SchemaOptions {
     pub fn strict(&self) -> Option<bool> {
         self.strict.as_ref().map(|strict| strict.value())
     }
+
+    pub fn cache_enabled(&self) -> bool {
+        self.cache
+            .as_ref()
+            .and_then(|cache| cache.enabled)
+            .unwrap_or_default()
+            .value()
+    }
+
+    pub fn cache_ttl(&self) -> Option<u64> {
+        self.cache.as_ref().and_then(|cache| cache.ttl)
+    }
 }
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "jsonschema", schemars(extend("x-tombi-table-keys-order" = tombi_x_keyword::TableKeysOrder::Schema)))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SchemaCache {
+    /// # Enable or disable the cache of remote schemas and catalogs.
+    ///
+    /// The cache is also used as a fallback in offline mode.
+    pub enabled: Option<BoolDefaultTrue>,
+
+    /// # The time to live of the cache in seconds.
+    ///
+    /// After the TTL has passed, the cached file is revalidated
+    /// with the remote server using `ETag` and `Last-Modified`.
+    #[cfg_attr(feature = "jsonschema", schemars(default = "cache_ttl_default"))]
+    pub ttl: Option<u64>,
+}
+
+#[cfg(feature = "jsonschema")]
+fn cache_ttl_default() -> Option<u64> {
+    Some(DEFAULT_SCHEMA_CACHE_TTL)
+}
+
+/// Default time to live of the schema cache in seconds.
+pub const DEFAULT_SCHEMA_CACHE_TTL: u64 = 60 * 60 * 24;
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
@@ -1,5 +1,6 @@
 mod bool_default_true;
 mod date_time_delimiter;
+mod hex_case;
 mod indent_style;
 mod indent_width;
 mod line_ending;
@@ -10,6 +11,7 @@ mod schema_catalog_path;
 
 pub use bool_default_true::BoolDefaultTrue;
 pub use date_time_delimiter::DateTimeDelimiter;
+pub use hex_case::HexCase;
 pub use indent_style::IndentStyle;
 pub use indent_width::IndentWidth;
 pub use line_ending::LineEnding;
@@ -0,0 +1,16 @@
+/// The letter case of hexadecimal digits.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum HexCase {
+    /// Example: `0xDEADBEEF`
+    Upper,
+
+    /// Example: `0xdeadbeef`
+    Lower,
+
+    /// Preserve the source case
+    #[default]
+    Preserve,
+}
@@ -15,6 +15,7 @@ pub struct Key {
     kind: KeyKind,
     value: String,
     range: tombi_text::Range,
+    span: tombi_text::Span,
 }
 
 impl std::borrow::Borrow<String> for Key {
@@ -34,9 +35,15 @@ impl Key {
         kind: KeyKind,
         value: String,
         range: tombi_text::Range,
+        span: tombi_text::Span,
         toml_version: TomlVersion,
     ) -> Result<Self, crate::Error> {
-        let key = Self { kind, value, range };
+        let key = Self {
+            kind,
+            value,
+            range,
+            span,
+        };
         key.try_to_raw_string(toml_version)?;
 
         Ok(key)
@@ -77,6 +84,12 @@ impl Key {
         self.range
     }
 
+    /// The byte span of the key in the source text, including the quotes.
+    #[inline]
+    pub fn span(&self) -> tombi_text::Span {
+        self.span
+    }
+
     #[inline]
     pub fn unquoted_range(&self) -> tombi_text::Range {
         match self.kind {
@@ -147,6 +160,7 @@ impl IntoDocumentTreeAndErrors<Option<Key>> for tombi_ast::Key {
             },
             token.text().to_string(),
             token.range(),
+            token.span(),
             toml_version,
         ) {
             Ok(key) => DocumentTreeAndErrors {
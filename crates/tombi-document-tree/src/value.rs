@@ -66,6 +66,41 @@ impl Value {
             Value::Incomplete { range } => *range,
         }
     }
+
+    /// Compare the values structurally, ignoring their ranges and notations.
+    ///
+    /// `0xff` and `255` or `"a"` and `'a'` are the same value.
+    /// Incomplete values are never the same as anything.
+    pub fn is_same_value(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Boolean(lhs), Value::Boolean(rhs)) => lhs.value() == rhs.value(),
+            (Value::Integer(lhs), Value::Integer(rhs)) => lhs.value() == rhs.value(),
+            (Value::Float(lhs), Value::Float(rhs)) => lhs.value() == rhs.value(),
+            (Value::String(lhs), Value::String(rhs)) => lhs.value() == rhs.value(),
+            (Value::OffsetDateTime(lhs), Value::OffsetDateTime(rhs)) => lhs.value() == rhs.value(),
+            (Value::LocalDateTime(lhs), Value::LocalDateTime(rhs)) => lhs.value() == rhs.value(),
+            (Value::LocalDate(lhs), Value::LocalDate(rhs)) => lhs.value() == rhs.value(),
+            (Value::LocalTime(lhs), Value::LocalTime(rhs)) => lhs.value() == rhs.value(),
+            (Value::Array(lhs), Value::Array(rhs)) => {
+                lhs.values().len() == rhs.values().len()
+                    && lhs
+                        .values()
+                        .iter()
+                        .zip(rhs.values())
+                        .all(|(lhs, rhs)| lhs.is_same_value(rhs))
+            }
+            (Value::Table(lhs), Value::Table(rhs)) => {
+                lhs.key_values().len() == rhs.key_values().len()
+                    && lhs.key_values().iter().all(|(key, lhs)| {
+                        rhs.key_values()
+                            .iter()
+                            .find(|(other_key, _)| other_key.value() == key.value())
+                            .is_some_and(|(_, rhs)| lhs.is_same_value(rhs))
+                    })
+            }
+            _ => false,
+        }
+    }
 }
 
 impl crate::ValueImpl for Value {
@@ -1,7 +1,61 @@
+/// The segment of the key path to the value that failed to deserialize.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl std::fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathSegment::Key(key) => write!(f, "{key}"),
+            PathSegment::Index(index) => write!(f, "[{index}]"),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("{0}")]
     Custom(String),
+
+    #[error("{error}")]
+    WithPath {
+        path: Vec<PathSegment>,
+        error: Box<Error>,
+    },
+}
+
+impl Error {
+    /// The key path to the value that failed to deserialize, such as `server.port`.
+    pub fn path(&self) -> &[PathSegment] {
+        match self {
+            Self::Custom(_) => &[],
+            Self::WithPath { path, .. } => path,
+        }
+    }
+
+    /// The error message without the key path.
+    pub fn message(&self) -> String {
+        match self {
+            Self::Custom(message) => message.clone(),
+            Self::WithPath { error, .. } => error.message(),
+        }
+    }
+
+    /// Prepend the segment to the key path of the error.
+    pub fn with_path_segment(self, segment: PathSegment) -> Self {
+        match self {
+            Self::WithPath { mut path, error } => {
+                path.insert(0, segment);
+                Self::WithPath { path, error }
+            }
+            error => Self::WithPath {
+                path: vec![segment],
+                error: Box::new(error),
+            },
+        }
+    }
 }
 
 impl serde::de::Error for Error {
@@ -9,3 +63,15 @@ impl serde::de::Error for Error {
         Self::Custom(msg.to_string())
     }
 }
+
+/// Join the key path into a dotted string, such as `server.port` or `bin[0].name`.
+pub fn join_path(path: &[PathSegment]) -> String {
+    let mut joined = String::new();
+    for segment in path {
+        if matches!(segment, PathSegment::Key(_)) && !joined.is_empty() {
+            joined.push('.');
+        }
+        joined.push_str(&segment.to_string());
+    }
+    joined
+}
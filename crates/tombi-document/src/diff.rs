@@ -0,0 +1,212 @@
+use crate::{Document, Table, Value};
+
+/// A semantic difference between two documents.
+///
+/// The notation of the values, such as the quote style of strings, the radix of integers,
+/// the style of tables and the order of keys, is not a difference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    /// The key path of the value, such as `server.port` or `bin[0].name`.
+    pub path: String,
+    pub kind: DifferenceKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DifferenceKind {
+    Added { new: Value },
+    Removed { old: Value },
+    Changed { old: Value, new: Value },
+}
+
+impl std::fmt::Display for Difference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            DifferenceKind::Added { new } => {
+                write!(f, "`{}` was added: {}", self.path, display_value(new))
+            }
+            DifferenceKind::Removed { old } => {
+                write!(f, "`{}` was removed: {}", self.path, display_value(old))
+            }
+            DifferenceKind::Changed { old, new } => write!(
+                f,
+                "`{}` was changed: {} -> {}",
+                self.path,
+                display_value(old),
+                display_value(new)
+            ),
+        }
+    }
+}
+
+impl Document {
+    /// Returns the semantic differences from the other document.
+    pub fn diff(&self, other: &Document) -> Vec<Difference> {
+        let mut differences = vec![];
+        diff_tables("", &self.0, &other.0, &mut differences);
+
+        differences
+    }
+}
+
+fn diff_tables(path: &str, old: &Table, new: &Table, differences: &mut Vec<Difference>) {
+    for (key, old_value) in old.key_values() {
+        let key_path = join_key(path, &key.to_string());
+        match new.key_values().get(key) {
+            Some(new_value) => diff_values(&key_path, old_value, new_value, differences),
+            None => differences.push(Difference {
+                path: key_path,
+                kind: DifferenceKind::Removed {
+                    old: old_value.clone(),
+                },
+            }),
+        }
+    }
+
+    for (key, new_value) in new.key_values() {
+        if !old.key_values().contains_key(key) {
+            differences.push(Difference {
+                path: join_key(path, &key.to_string()),
+                kind: DifferenceKind::Added {
+                    new: new_value.clone(),
+                },
+            });
+        }
+    }
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, differences: &mut Vec<Difference>) {
+    let is_same = match (old, new) {
+        (Value::Table(old), Value::Table(new)) => {
+            diff_tables(path, old, new, differences);
+            return;
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for (index, (old, new)) in old.values().iter().zip(new.values()).enumerate() {
+                diff_values(&format!("{path}[{index}]"), old, new, differences);
+            }
+            for (index, old) in old.values().iter().enumerate().skip(new.values().len()) {
+                differences.push(Difference {
+                    path: format!("{path}[{index}]"),
+                    kind: DifferenceKind::Removed { old: old.clone() },
+                });
+            }
+            for (index, new) in new.values().iter().enumerate().skip(old.values().len()) {
+                differences.push(Difference {
+                    path: format!("{path}[{index}]"),
+                    kind: DifferenceKind::Added { new: new.clone() },
+                });
+            }
+            return;
+        }
+        (Value::Boolean(old), Value::Boolean(new)) => old.value() == new.value(),
+        (Value::Integer(old), Value::Integer(new)) => old.value() == new.value(),
+        (Value::Float(old), Value::Float(new)) => {
+            old.value() == new.value() || old.value().is_nan() && new.value().is_nan()
+        }
+        (Value::String(old), Value::String(new)) => old.value() == new.value(),
+        (Value::OffsetDateTime(old), Value::OffsetDateTime(new)) => old == new,
+        (Value::LocalDateTime(old), Value::LocalDateTime(new)) => old == new,
+        (Value::LocalDate(old), Value::LocalDate(new)) => old == new,
+        (Value::LocalTime(old), Value::LocalTime(new)) => old == new,
+        _ => false,
+    };
+
+    if !is_same {
+        differences.push(Difference {
+            path: path.to_string(),
+            kind: DifferenceKind::Changed {
+                old: old.clone(),
+                new: new.clone(),
+            },
+        });
+    }
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Boolean(boolean) => boolean.value().to_string(),
+        Value::Integer(integer) => integer.value().to_string(),
+        Value::Float(float) => float.value().to_string(),
+        Value::String(string) => format!("{:?}", string.value()),
+        Value::OffsetDateTime(date_time) => date_time.to_string(),
+        Value::LocalDateTime(date_time) => date_time.to_string(),
+        Value::LocalDate(date) => date.to_string(),
+        Value::LocalTime(time) => time.to_string(),
+        Value::Array(_) => "array".to_string(),
+        Value::Table(_) => "table".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_document;
+
+    #[test]
+    fn same_document_with_different_notations() {
+        let old = parse_document(
+            r#"
+            a = 0xFF
+            b = 'string'
+            c = { d = 1, e = [1, 2] }
+            "#,
+        );
+        let new = parse_document(
+            r#"
+            b = "string"
+            a = 255
+
+            [c]
+            e = [
+              1,
+              2,
+            ]
+            d = 1
+            "#,
+        );
+
+        pretty_assertions::assert_eq!(old.diff(&new), vec![]);
+    }
+
+    #[test]
+    fn changed_document() {
+        let old = parse_document(
+            r#"
+            a = 1
+            b = "b"
+
+            [[c]]
+            d = [1, 2]
+            "#,
+        );
+        let new = parse_document(
+            r#"
+            a = 2
+            e = true
+
+            [[c]]
+            d = [1]
+            "#,
+        );
+
+        pretty_assertions::assert_eq!(
+            old.diff(&new)
+                .into_iter()
+                .map(|difference| difference.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "`a` was changed: 1 -> 2",
+                "`b` was removed: \"b\"",
+                "`c[0].d[1]` was removed: 2",
+                "`e` was added: true",
+            ]
+        );
+    }
+}
@@ -0,0 +1,625 @@
+//! Conversion between the TOML document and [tombi_json_value::Value].
+
+use crate::de::{Error, PathSegment};
+use crate::{
+    Array, ArrayKind, Boolean, Document, Float, Integer, Key, LocalDate, LocalDateTime, LocalTime,
+    OffsetDateTime, String, StringKind, Table, TableKind, Value,
+};
+
+/// How the TOML values without the JSON equivalent are encoded in JSON.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum JsonEncoding {
+    /// The values are the JSON values, and the date-times are RFC 3339 strings.
+    ///
+    /// The TOML types of the date-times are lost,
+    /// so they are converted back to TOML as strings.
+    /// The floats `nan`, `inf` and `-inf` are encoded as the strings.
+    #[default]
+    Plain,
+
+    /// The values are tagged objects, such as `{"type": "integer", "value": "42"}`,
+    /// as in the [toml-test](https://github.com/toml-lang/toml-test) encoding.
+    ///
+    /// The integers are encoded as the strings,
+    /// so the JSON tools with the 64-bit floats, such as `jq`, keep their precision.
+    Tagged,
+}
+
+const TYPE_FIELD: &str = "type";
+const VALUE_FIELD: &str = "value";
+
+impl Value {
+    /// Convert the value into the JSON value.
+    pub fn to_json_value(&self, encoding: JsonEncoding) -> tombi_json_value::Value {
+        match encoding {
+            JsonEncoding::Plain => match self {
+                Value::Boolean(boolean) => tombi_json_value::Value::Bool(boolean.value()),
+                Value::Integer(integer) => integer.value().into(),
+                Value::Float(float) if float.value().is_finite() => {
+                    tombi_json_value::Value::Number(tombi_json_value::Number::Float(float.value()))
+                }
+                Value::Float(float) => float_to_string(float.value()).into(),
+                Value::String(string) => string.value().into(),
+                Value::OffsetDateTime(date_time) => date_time.to_string().into(),
+                Value::LocalDateTime(date_time) => date_time.to_string().into(),
+                Value::LocalDate(date) => date.to_string().into(),
+                Value::LocalTime(time) => time.to_string().into(),
+                Value::Array(array) => array_to_json_value(array, encoding),
+                Value::Table(table) => table_to_json_value(table, encoding),
+            },
+            JsonEncoding::Tagged => match self {
+                Value::Boolean(boolean) => tagged("bool", boolean.value().to_string()),
+                Value::Integer(integer) => tagged("integer", integer.value().to_string()),
+                Value::Float(float) => tagged("float", float_to_string(float.value())),
+                Value::String(string) => tagged("string", string.value().to_string()),
+                Value::OffsetDateTime(date_time) => tagged("datetime", date_time.to_string()),
+                Value::LocalDateTime(date_time) => tagged("datetime-local", date_time.to_string()),
+                Value::LocalDate(date) => tagged("date-local", date.to_string()),
+                Value::LocalTime(time) => tagged("time-local", time.to_string()),
+                Value::Array(array) => array_to_json_value(array, encoding),
+                Value::Table(table) => table_to_json_value(table, encoding),
+            },
+        }
+    }
+}
+
+impl Document {
+    /// Convert the document into the JSON object.
+    pub fn to_json_value(&self, encoding: JsonEncoding) -> tombi_json_value::Value {
+        table_to_json_value(self, encoding)
+    }
+
+    /// Write the document to the writer as the pretty-printed JSON.
+    ///
+    /// The values are written while serializing, without building the JSON value.
+    #[cfg(feature = "serde")]
+    pub fn to_json_writer<W: std::io::Write>(
+        &self,
+        writer: W,
+        encoding: JsonEncoding,
+    ) -> Result<(), serde_json::Error> {
+        serde_json::to_writer_pretty(
+            writer,
+            &JsonTable {
+                table: self,
+                encoding,
+            },
+        )
+    }
+
+    /// Parse the JSON text into the document.
+    ///
+    /// See [`Document::try_from_json_reader`] for the conversion.
+    #[cfg(feature = "serde")]
+    pub fn try_from_json_str(source: &str, encoding: JsonEncoding) -> Result<Self, Error> {
+        read_json(&mut serde_json::Deserializer::from_str(source), encoding)
+    }
+
+    /// Read the JSON stream into the document.
+    ///
+    /// The values are converted while reading, without building the JSON value.
+    /// The integers keep their precision up to the range of `u64`,
+    /// and the numbers with a fraction or an exponent, such as `1.0`, stay floats.
+    ///
+    /// Fails on `null`, on the integers beyond the range of `i64`,
+    /// and on the tagged values that TOML cannot represent.
+    /// The error has the key path to the value.
+    #[cfg(feature = "serde")]
+    pub fn try_from_json_reader<R: std::io::Read>(
+        reader: R,
+        encoding: JsonEncoding,
+    ) -> Result<Self, Error> {
+        read_json(&mut serde_json::Deserializer::from_reader(reader), encoding)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn read_json<'de, R: serde_json::de::Read<'de>>(
+    deserializer: &mut serde_json::Deserializer<R>,
+    encoding: JsonEncoding,
+) -> Result<Document, Error> {
+    use serde::de::DeserializeSeed;
+
+    let error = std::cell::RefCell::new(None);
+    let value = JsonValueSeed {
+        encoding,
+        error: &error,
+    }
+    .deserialize(&mut *deserializer)
+    .and_then(|value| deserializer.end().map(|()| value));
+
+    match value {
+        Ok(Value::Table(table)) => Ok(Document(table)),
+        Ok(_) => Err(Error::Custom(
+            "the root of the TOML document must be an object".to_string(),
+        )),
+        Err(json_error) => Err(error
+            .into_inner()
+            .unwrap_or_else(|| Error::Custom(json_error.to_string()))),
+    }
+}
+
+/// Convert the JSON values into the values while deserializing.
+///
+/// The conversion error is kept aside with its key path,
+/// since the error of the deserializer only has the message.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy)]
+struct JsonValueSeed<'a> {
+    encoding: JsonEncoding,
+    error: &'a std::cell::RefCell<Option<Error>>,
+}
+
+#[cfg(feature = "serde")]
+impl JsonValueSeed<'_> {
+    fn fail<E: serde::de::Error>(&self, error: Error) -> E {
+        let json_error = E::custom(&error);
+        *self.error.borrow_mut() = Some(error);
+        json_error
+    }
+
+    fn with_path_segment<E>(&self, segment: PathSegment, json_error: E) -> E {
+        let error = self.error.borrow_mut().take();
+        *self.error.borrow_mut() = error.map(|error| error.with_path_segment(segment));
+        json_error
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::DeserializeSeed<'de> for JsonValueSeed<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for JsonValueSeed<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(Value::Boolean(Boolean::new(value)))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(Value::Integer(Integer::new(value)))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::Integer(Integer::new(value))),
+            Err(_) => Err(self.fail(Error::Custom(format!(
+                "integer {value} is out of the range of TOML integers"
+            )))),
+        }
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(Value::Float(Float::new(value)))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        self.visit_string(value.to_string())
+    }
+
+    fn visit_string<E: serde::de::Error>(
+        self,
+        value: std::string::String,
+    ) -> Result<Self::Value, E> {
+        Ok(Value::String(String::new(StringKind::BasicString, value)))
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Err(self.fail(Error::Custom(
+            "null cannot be represented in TOML".to_string(),
+        )))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq
+            .next_element_seed(self)
+            .map_err(|error| self.with_path_segment(PathSegment::Index(values.len()), error))?
+        {
+            values.push(value);
+        }
+
+        let mut array = Array::new(
+            if !values.is_empty() && values.iter().all(|value| matches!(value, Value::Table(_))) {
+                ArrayKind::ArrayOfTable
+            } else {
+                ArrayKind::Array
+            },
+        );
+        for value in values {
+            array.push(value);
+        }
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut table = Table::new(TableKind::Table);
+        while let Some(key) = map.next_key::<std::string::String>()? {
+            let value = map
+                .next_value_seed(self)
+                .map_err(|error| self.with_path_segment(PathSegment::Key(key.clone()), error))?;
+            table.insert(Key::from(String::new(StringKind::BasicString, key)), value);
+        }
+
+        if self.encoding == JsonEncoding::Tagged {
+            if let Some((r#type, value)) = as_tagged(&table) {
+                return try_from_tagged(r#type, value).map_err(|error| self.fail(error));
+            }
+        }
+
+        Ok(Value::Table(table))
+    }
+}
+
+fn array_to_json_value(array: &Array, encoding: JsonEncoding) -> tombi_json_value::Value {
+    tombi_json_value::Value::Array(
+        array
+            .values()
+            .iter()
+            .map(|value| value.to_json_value(encoding))
+            .collect(),
+    )
+}
+
+fn table_to_json_value(table: &Table, encoding: JsonEncoding) -> tombi_json_value::Value {
+    let mut object = tombi_json_value::Object::new();
+    for (key, value) in table.key_values() {
+        object.insert(key.value().to_string(), value.to_json_value(encoding));
+    }
+    tombi_json_value::Value::Object(object)
+}
+
+fn tagged(r#type: &str, value: std::string::String) -> tombi_json_value::Value {
+    let mut object = tombi_json_value::Object::new();
+    object.insert(TYPE_FIELD.to_string(), r#type.into());
+    object.insert(VALUE_FIELD.to_string(), value.into());
+    tombi_json_value::Value::Object(object)
+}
+
+/// Serialize the table as the JSON object without building the JSON value.
+#[cfg(feature = "serde")]
+struct JsonTable<'a> {
+    table: &'a Table,
+    encoding: JsonEncoding,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for JsonTable<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.table.key_values().iter().map(|(key, value)| {
+            (
+                key.value(),
+                JsonValue {
+                    value,
+                    encoding: self.encoding,
+                },
+            )
+        }))
+    }
+}
+
+/// Serialize the value as [`Value::to_json_value`] without building the JSON value.
+#[cfg(feature = "serde")]
+struct JsonValue<'a> {
+    value: &'a Value,
+    encoding: JsonEncoding,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for JsonValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let (r#type, value) = match (self.encoding, self.value) {
+            (_, Value::Array(array)) => {
+                return serializer.collect_seq(array.values().iter().map(|value| JsonValue {
+                    value,
+                    encoding: self.encoding,
+                }));
+            }
+            (_, Value::Table(table)) => {
+                return JsonTable {
+                    table,
+                    encoding: self.encoding,
+                }
+                .serialize(serializer);
+            }
+            (JsonEncoding::Plain, Value::Boolean(boolean)) => {
+                return serializer.serialize_bool(boolean.value());
+            }
+            (JsonEncoding::Plain, Value::Integer(integer)) => {
+                return serializer.serialize_i64(integer.value());
+            }
+            (JsonEncoding::Plain, Value::Float(float)) if float.value().is_finite() => {
+                return serializer.serialize_f64(float.value());
+            }
+            (JsonEncoding::Plain, Value::Float(float)) => {
+                return serializer.serialize_str(&float_to_string(float.value()));
+            }
+            (JsonEncoding::Plain, Value::String(string)) => {
+                return serializer.serialize_str(string.value());
+            }
+            (JsonEncoding::Plain, Value::OffsetDateTime(date_time)) => {
+                return serializer.collect_str(date_time);
+            }
+            (JsonEncoding::Plain, Value::LocalDateTime(date_time)) => {
+                return serializer.collect_str(date_time);
+            }
+            (JsonEncoding::Plain, Value::LocalDate(date)) => return serializer.collect_str(date),
+            (JsonEncoding::Plain, Value::LocalTime(time)) => return serializer.collect_str(time),
+            (JsonEncoding::Tagged, Value::Boolean(boolean)) => {
+                ("bool", boolean.value().to_string())
+            }
+            (JsonEncoding::Tagged, Value::Integer(integer)) => {
+                ("integer", integer.value().to_string())
+            }
+            (JsonEncoding::Tagged, Value::Float(float)) => {
+                ("float", float_to_string(float.value()))
+            }
+            (JsonEncoding::Tagged, Value::String(string)) => ("string", string.value().to_string()),
+            (JsonEncoding::Tagged, Value::OffsetDateTime(date_time)) => {
+                ("datetime", date_time.to_string())
+            }
+            (JsonEncoding::Tagged, Value::LocalDateTime(date_time)) => {
+                ("datetime-local", date_time.to_string())
+            }
+            (JsonEncoding::Tagged, Value::LocalDate(date)) => ("date-local", date.to_string()),
+            (JsonEncoding::Tagged, Value::LocalTime(time)) => ("time-local", time.to_string()),
+        };
+
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry(TYPE_FIELD, r#type)?;
+        map.serialize_entry(VALUE_FIELD, &value)?;
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+fn as_tagged(table: &Table) -> Option<(&str, &str)> {
+    let key_values = table.key_values();
+    if key_values.len() != 2 {
+        return None;
+    }
+    let as_str = |field: &str| {
+        key_values.iter().find_map(|(key, value)| match value {
+            Value::String(string) if key.value() == field => Some(string.value()),
+            _ => None,
+        })
+    };
+    Some((as_str(TYPE_FIELD)?, as_str(VALUE_FIELD)?))
+}
+
+#[cfg(feature = "serde")]
+fn try_from_tagged(r#type: &str, value: &str) -> Result<Value, Error> {
+    fn parse<T: std::str::FromStr>(r#type: &str, value: &str) -> Result<T, Error> {
+        value
+            .parse()
+            .map_err(|_| Error::Custom(format!("invalid {type} value: {value:?}")))
+    }
+
+    match r#type {
+        "bool" => Ok(Value::Boolean(Boolean::new(parse(r#type, value)?))),
+        "integer" => Ok(Value::Integer(Integer::new(parse(r#type, value)?))),
+        "float" => Ok(Value::Float(Float::new(match value {
+            "nan" | "+nan" | "-nan" => f64::NAN,
+            "inf" | "+inf" => f64::INFINITY,
+            "-inf" => f64::NEG_INFINITY,
+            _ => parse(r#type, value)?,
+        }))),
+        "string" => Ok(Value::String(String::new(
+            StringKind::BasicString,
+            value.to_string(),
+        ))),
+        "datetime" => Ok(Value::OffsetDateTime(parse::<OffsetDateTime>(
+            r#type, value,
+        )?)),
+        "datetime-local" => Ok(Value::LocalDateTime(parse::<LocalDateTime>(r#type, value)?)),
+        "date-local" => Ok(Value::LocalDate(parse::<LocalDate>(r#type, value)?)),
+        "time-local" => Ok(Value::LocalTime(parse::<LocalTime>(r#type, value)?)),
+        _ => Err(Error::Custom(format!("unknown type: {type:?}"))),
+    }
+}
+
+fn float_to_string(value: f64) -> std::string::String {
+    if value.is_nan() {
+        "nan".to_string()
+    } else if value.is_infinite() {
+        if value.is_sign_positive() {
+            "inf"
+        } else {
+            "-inf"
+        }
+        .to_string()
+    } else {
+        format!("{value:?}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_document;
+
+    const SOURCE: &str = r#"
+name = "tombi"
+count = 42
+ratio = 0.5
+infinity = inf
+released = 1979-05-27T07:32:00Z
+date = 1979-05-27
+[[items]]
+enabled = true
+"#;
+
+    #[test]
+    fn plain_encoding() {
+        let json = parse_document(SOURCE).to_json_value(JsonEncoding::Plain);
+
+        pretty_assertions::assert_eq!(
+            serde_json::to_value(&json).unwrap(),
+            serde_json::json!({
+                "name": "tombi",
+                "count": 42,
+                "ratio": 0.5,
+                "infinity": "inf",
+                "released": "1979-05-27T07:32:00Z",
+                "date": "1979-05-27",
+                "items": [{"enabled": true}]
+            })
+        );
+    }
+
+    #[test]
+    fn tagged_encoding_round_trip() {
+        let document = parse_document(SOURCE);
+        let json = document.to_json_value(JsonEncoding::Tagged);
+
+        pretty_assertions::assert_eq!(
+            serde_json::to_value(&json).unwrap(),
+            serde_json::json!({
+                "name": {"type": "string", "value": "tombi"},
+                "count": {"type": "integer", "value": "42"},
+                "ratio": {"type": "float", "value": "0.5"},
+                "infinity": {"type": "float", "value": "inf"},
+                "released": {"type": "datetime", "value": "1979-05-27T07:32:00Z"},
+                "date": {"type": "date-local", "value": "1979-05-27"},
+                "items": [{"enabled": {"type": "bool", "value": "true"}}]
+            })
+        );
+
+        let converted = Document::try_from_json_str(
+            &serde_json::to_string(&json).unwrap(),
+            JsonEncoding::Tagged,
+        )
+        .unwrap();
+        pretty_assertions::assert_eq!(converted.to_json_value(JsonEncoding::Tagged), json);
+    }
+
+    #[test]
+    fn plain_numbers() {
+        let document = Document::try_from_json_str(
+            r#"{"integer": 9007199254740993, "float": 1.0, "large": 1e300, "negative": -0.5}"#,
+            JsonEncoding::Plain,
+        )
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_json_value(JsonEncoding::Tagged),
+            tombi_json_value::Value::Object(
+                [
+                    ("integer", tagged("integer", "9007199254740993".to_string())),
+                    ("float", tagged("float", "1.0".to_string())),
+                    ("large", tagged("float", "1e300".to_string())),
+                    ("negative", tagged("float", "-0.5".to_string())),
+                ]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect()
+            )
+        );
+    }
+
+    #[test]
+    fn plain_integer_out_of_range() {
+        let error = Document::try_from_json_str(
+            r#"{"server": {"big": 18446744073709551615}}"#,
+            JsonEncoding::Plain,
+        )
+        .unwrap_err();
+        pretty_assertions::assert_eq!(crate::de::join_path(error.path()), "server.big");
+        pretty_assertions::assert_eq!(
+            error.message(),
+            "integer 18446744073709551615 is out of the range of TOML integers"
+        );
+    }
+
+    #[test]
+    fn tagged_integer_out_of_range() {
+        let error = Document::try_from_json_str(
+            r#"{"big": {"type": "integer", "value": "18446744073709551615"}}"#,
+            JsonEncoding::Tagged,
+        )
+        .unwrap_err();
+        pretty_assertions::assert_eq!(crate::de::join_path(error.path()), "big");
+        pretty_assertions::assert_eq!(
+            error.message(),
+            r#"invalid integer value: "18446744073709551615""#
+        );
+    }
+
+    #[test]
+    fn null_with_path() {
+        let error =
+            Document::try_from_json_str(r#"{"server": [null]}"#, JsonEncoding::Plain).unwrap_err();
+        pretty_assertions::assert_eq!(crate::de::join_path(error.path()), "server[0]");
+        pretty_assertions::assert_eq!(error.message(), "null cannot be represented in TOML");
+    }
+
+    #[test]
+    fn parse_error_with_position() {
+        let error =
+            Document::try_from_json_str("{\n  \"a\": [1,\n}", JsonEncoding::Plain).unwrap_err();
+        assert!(error.message().contains("at line 3"), "{}", error.message());
+    }
+
+    #[test]
+    fn json_writer() {
+        let document = parse_document(SOURCE);
+
+        for encoding in [JsonEncoding::Plain, JsonEncoding::Tagged] {
+            let mut output = Vec::new();
+            document.to_json_writer(&mut output, encoding).unwrap();
+
+            pretty_assertions::assert_eq!(
+                std::string::String::from_utf8(output).unwrap(),
+                serde_json::to_string_pretty(&document.to_json_value(encoding)).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn json_reader() {
+        let document = Document::try_from_json_reader(
+            std::io::Cursor::new(r#"{"items": [{"name": "a"}, {"name": "b"}], "values": [1, 2]}"#),
+            JsonEncoding::Plain,
+        )
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            serde_json::to_value(document.to_json_value(JsonEncoding::Plain)).unwrap(),
+            serde_json::json!({"items": [{"name": "a"}, {"name": "b"}], "values": [1, 2]})
+        );
+
+        let error = Document::try_from_json_reader(
+            std::io::Cursor::new(r#"{"items": [{"name": "a"}, {"name": null}]}"#),
+            JsonEncoding::Plain,
+        )
+        .unwrap_err();
+        pretty_assertions::assert_eq!(crate::de::join_path(error.path()), "items[1].name");
+    }
+}
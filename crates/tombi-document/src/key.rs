@@ -49,11 +49,14 @@ impl From<crate::String> for Key {
     fn from(value: crate::String) -> Self {
         Self {
             kind: match value.kind() {
-                _ if !value.value.contains("'") && !value.value.contains("\"") => KeyKind::BareKey,
-                crate::StringKind::BasicString => KeyKind::BasicString,
-                crate::StringKind::LiteralString => KeyKind::LiteralString,
-                crate::StringKind::MultiLineBasicString => KeyKind::BasicString,
-                crate::StringKind::MultiLineLiteralString => KeyKind::LiteralString,
+                _ if is_bare_key(&value.value) => KeyKind::BareKey,
+                crate::StringKind::LiteralString | crate::StringKind::MultiLineLiteralString
+                    if !value.value.contains('\'')
+                        && !value.value.chars().any(char::is_control) =>
+                {
+                    KeyKind::LiteralString
+                }
+                _ => KeyKind::BasicString,
             },
             value: value.value,
         }
@@ -70,12 +73,24 @@ impl std::fmt::Display for Key {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.kind {
             KeyKind::BareKey => write!(f, "{}", self.value),
-            KeyKind::BasicString => write!(f, r#""{}""#, self.value),
-            KeyKind::LiteralString => write!(f, r#"'{}'"#, self.value),
+            KeyKind::BasicString => {
+                write!(f, "{}", tombi_toml_text::to_basic_string(&self.value))
+            }
+            KeyKind::LiteralString => {
+                write!(f, "{}", tombi_toml_text::to_literal_string(&self.value))
+            }
         }
     }
 }
 
+/// Whether the key can be written without the quotes.
+fn is_bare_key(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-'))
+}
+
 impl IntoDocument<Key> for tombi_document_tree::Key {
     fn into_document(self, toml_version: TomlVersion) -> Key {
         Key {
@@ -143,6 +158,7 @@ impl<'de> serde::de::Deserializer<'de> for &'de Key {
 mod test {
     use serde_json::json;
 
+    use super::*;
     use crate::test_deserialize;
 
     test_deserialize! {
@@ -196,4 +212,21 @@ mod test {
         #[test]
         fn float_and_bare_key(r#"3.14.abc = 'value'"#) -> Ok(json!({"3": {"14": {"abc": "value"}}}))
     }
+
+    #[test]
+    fn key_from_string_is_quoted_if_needed() {
+        let key = |value: &str| {
+            Key::from(crate::String::new(
+                crate::StringKind::BasicString,
+                value.to_string(),
+            ))
+            .to_string()
+        };
+
+        pretty_assertions::assert_eq!(key("bare_key-1"), "bare_key-1");
+        pretty_assertions::assert_eq!(key("quoted key"), r#""quoted key""#);
+        pretty_assertions::assert_eq!(key("a.b"), r#""a.b""#);
+        pretty_assertions::assert_eq!(key(r#"a"b"#), r#""a\"b""#);
+        pretty_assertions::assert_eq!(key(""), r#""""#);
+    }
 }
@@ -1,9 +1,14 @@
 pub mod de;
+mod diff;
+mod json;
 mod key;
 mod value;
+mod yaml;
 
 use std::ops::{Deref, DerefMut};
 
+pub use diff::{Difference, DifferenceKind};
+pub use json::JsonEncoding;
 pub use key::{Key, KeyKind};
 use serde::forward_to_deserialize_any;
 use tombi_toml_version::TomlVersion;
@@ -102,6 +107,21 @@ impl<'de> serde::Deserializer<'de> for &'de Document {
     }
 }
 
+/// Parse the TOML text into the document, asserting that it has no errors.
+#[cfg(test)]
+pub(crate) fn parse_document(source: &str) -> Document {
+    use tombi_ast::AstNode;
+    use tombi_document_tree::IntoDocumentTreeAndErrors;
+
+    let toml_version = TomlVersion::default();
+    let p = tombi_parser::parse(source, toml_version);
+    pretty_assertions::assert_eq!(p.errors, Vec::<tombi_parser::Error>::new());
+    let root = tombi_ast::Root::cast(p.into_syntax_node()).unwrap();
+    let (document_tree, errors) = root.into_document_tree_and_errors(toml_version).into();
+    pretty_assertions::assert_eq!(errors, vec![]);
+    document_tree.into_document(toml_version)
+}
+
 #[cfg(test)]
 #[macro_export]
 macro_rules! test_deserialize {
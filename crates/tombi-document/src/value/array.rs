@@ -114,14 +114,14 @@ impl<'de> serde::Deserialize<'de> for Array {
 
 #[cfg(feature = "serde")]
 struct ArrayDeserializer<'de> {
-    iter: std::slice::Iter<'de, Value>,
+    iter: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
 }
 
 #[cfg(feature = "serde")]
 impl<'de> ArrayDeserializer<'de> {
     fn new(array: &'de Array) -> Self {
         ArrayDeserializer {
-            iter: array.values().iter(),
+            iter: array.values().iter().enumerate(),
         }
     }
 }
@@ -135,7 +135,10 @@ impl<'de> serde::de::SeqAccess<'de> for ArrayDeserializer<'de> {
         T: serde::de::DeserializeSeed<'de>,
     {
         match self.iter.next() {
-            Some(value) => seed.deserialize(value).map(Some),
+            Some((index, value)) => seed
+                .deserialize(value)
+                .map(Some)
+                .map_err(|error| error.with_path_segment(crate::de::PathSegment::Index(index))),
             None => Ok(None),
         }
     }
@@ -50,6 +50,11 @@ impl Table {
         &self.key_values
     }
 
+    #[inline]
+    pub fn key_values_mut(&mut self) -> &mut IndexMap<Key, Value> {
+        &mut self.key_values
+    }
+
     pub fn entry(&mut self, key: Key) -> Entry<'_, Key, Value> {
         self.key_values.entry(key)
     }
@@ -104,6 +109,7 @@ impl<'de> serde::Deserialize<'de> for Table {
 #[cfg(feature = "serde")]
 struct TableDeserializer<'de> {
     iter: <&'de IndexMap<Key, Value> as IntoIterator>::IntoIter,
+    key: Option<&'de Key>,
     value: Option<&'de Value>,
 }
 
@@ -112,6 +118,7 @@ impl<'de> TableDeserializer<'de> {
     fn new(table: &'de Table) -> Self {
         Self {
             iter: table.key_values().iter(),
+            key: None,
             value: None,
         }
     }
@@ -127,8 +134,11 @@ impl<'de> serde::de::MapAccess<'de> for TableDeserializer<'de> {
     {
         match self.iter.next() {
             Some((key, value)) => {
+                self.key = Some(key);
                 self.value = Some(value);
-                seed.deserialize(key).map(Some)
+                seed.deserialize(key).map(Some).map_err(|error| {
+                    error.with_path_segment(crate::de::PathSegment::Key(key.value().to_string()))
+                })
             }
             None => Ok(None),
         }
@@ -139,7 +149,12 @@ impl<'de> serde::de::MapAccess<'de> for TableDeserializer<'de> {
         T: serde::de::DeserializeSeed<'de>,
     {
         match self.value.take() {
-            Some(value) => seed.deserialize(value),
+            Some(value) => seed.deserialize(value).map_err(|error| match self.key {
+                Some(key) => {
+                    error.with_path_segment(crate::de::PathSegment::Key(key.value().to_string()))
+                }
+                None => error,
+            }),
             None => Err(serde::de::Error::custom("value is missing")),
         }
     }
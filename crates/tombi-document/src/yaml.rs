@@ -0,0 +1,472 @@
+//! Conversion between the TOML document and YAML.
+
+use crate::de::{Error, PathSegment};
+use crate::{
+    Array, ArrayKind, Boolean, Document, Float, Integer, Key, LocalDate, LocalDateTime, LocalTime,
+    OffsetDateTime, String, StringKind, Table, TableKind, Value,
+};
+
+const INDENT: usize = 2;
+
+impl Document {
+    /// Convert the document into the YAML text.
+    ///
+    /// The date-times are written as the plain scalars,
+    /// and the strings that would be read back as the other types are quoted.
+    pub fn to_yaml_string(&self) -> std::string::String {
+        let mut output = std::string::String::new();
+        if self.key_values().is_empty() {
+            output.push_str("{}\n");
+        } else {
+            write_table(&mut output, self, 0, false);
+        }
+        output
+    }
+
+    /// Convert the YAML text into the document.
+    ///
+    /// Only the first document of the YAML stream is converted.
+    /// The plain scalars in the TOML date-time formats are converted into the date-times.
+    ///
+    /// Fails on `null` and on the root that is not a mapping.
+    /// The error has the key path to the value.
+    pub fn try_from_yaml_str(source: &str) -> Result<Self, Error> {
+        match tombi_yaml::parse(source).map_err(from_yaml_error)? {
+            Some(node) => match node_to_value(node)? {
+                Value::Table(table) => Ok(Document(table)),
+                _ => Err(Error::Custom(
+                    "the root of the TOML document must be a mapping".to_string(),
+                )),
+            },
+            None => Ok(Document::new()),
+        }
+    }
+}
+
+fn from_yaml_error(error: tombi_yaml::Error) -> Error {
+    error.path().iter().rev().fold(
+        Error::Custom(error.message().to_string()),
+        |error, segment| {
+            error.with_path_segment(match segment {
+                tombi_yaml::PathSegment::Key(key) => PathSegment::Key(key.clone()),
+                tombi_yaml::PathSegment::Index(index) => PathSegment::Index(*index),
+            })
+        },
+    )
+}
+
+fn node_to_value(node: tombi_yaml::Node) -> Result<Value, Error> {
+    match node {
+        tombi_yaml::Node::Scalar(scalar) => scalar_to_value(scalar),
+        tombi_yaml::Node::Sequence(sequence) => {
+            let values = sequence
+                .items
+                .into_iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    node_to_value(item)
+                        .map_err(|error| error.with_path_segment(PathSegment::Index(index)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut array = Array::new(
+                if !values.is_empty() && values.iter().all(|value| matches!(value, Value::Table(_)))
+                {
+                    ArrayKind::ArrayOfTable
+                } else {
+                    ArrayKind::Array
+                },
+            );
+            for value in values {
+                array.push(value);
+            }
+            Ok(Value::Array(array))
+        }
+        tombi_yaml::Node::Mapping(mapping) => {
+            // The keys of the TOML tables are the strings,
+            // so the scalar keys of the other types are used as they are written.
+            let mut table = Table::new(TableKind::Table);
+            for (key, value) in mapping.entries {
+                let value = node_to_value(value).map_err(|error| {
+                    error.with_path_segment(PathSegment::Key(key.value.clone()))
+                })?;
+                table.insert(
+                    Key::from(String::new(StringKind::BasicString, key.value)),
+                    value,
+                );
+            }
+            Ok(Value::Table(table))
+        }
+    }
+}
+
+fn scalar_to_value(scalar: tombi_yaml::ScalarNode) -> Result<Value, Error> {
+    if !scalar.plain {
+        return Ok(Value::String(String::new(
+            StringKind::BasicString,
+            scalar.value,
+        )));
+    }
+
+    if scalar.resolve() == Some(tombi_yaml::Scalar::Null) {
+        return Err(Error::Custom(
+            "null cannot be represented in TOML".to_string(),
+        ));
+    }
+
+    Ok(resolve_plain_scalar(&scalar.value)
+        .unwrap_or_else(|| Value::String(String::new(StringKind::BasicString, scalar.value))))
+}
+
+/// Resolve the plain scalar into the value that is not a string.
+fn resolve_plain_scalar(value: &str) -> Option<Value> {
+    match tombi_yaml::resolve_scalar(value) {
+        Some(tombi_yaml::Scalar::Boolean(boolean)) => Some(Value::Boolean(Boolean::new(boolean))),
+        Some(tombi_yaml::Scalar::Integer(integer)) => Some(Value::Integer(Integer::new(integer))),
+        Some(tombi_yaml::Scalar::Float(float)) => Some(Value::Float(Float::new(float))),
+        _ => {
+            if let Ok(date_time) = value.parse::<OffsetDateTime>() {
+                Some(Value::OffsetDateTime(date_time))
+            } else if let Ok(date_time) = value.parse::<LocalDateTime>() {
+                Some(Value::LocalDateTime(date_time))
+            } else if let Ok(date) = value.parse::<LocalDate>() {
+                Some(Value::LocalDate(date))
+            } else if let Ok(time) = value.parse::<LocalTime>() {
+                Some(Value::LocalTime(time))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Write the mapping entries.
+///
+/// In a sequence, the first entry follows the `- ` indicator of the item.
+fn write_table(output: &mut std::string::String, table: &Table, indent: usize, in_sequence: bool) {
+    for (index, (key, value)) in table.key_values().iter().enumerate() {
+        if !(in_sequence && index == 0) {
+            write_indent(output, indent);
+        }
+        write_string(output, key.value());
+        output.push(':');
+        write_nested_value(output, value, indent + INDENT);
+    }
+}
+
+/// Write the sequence items.
+///
+/// In a sequence, the first item follows the `- ` indicator of the item.
+fn write_array(output: &mut std::string::String, array: &Array, indent: usize, in_sequence: bool) {
+    for (index, value) in array.values().iter().enumerate() {
+        if !(in_sequence && index == 0) {
+            write_indent(output, indent);
+        }
+        output.push('-');
+        match value {
+            Value::Table(table) if !table.key_values().is_empty() => {
+                output.push(' ');
+                write_table(output, table, indent + INDENT, true);
+            }
+            Value::Array(array) if !array.is_empty() => {
+                output.push(' ');
+                write_array(output, array, indent + INDENT, true);
+            }
+            _ => write_nested_value(output, value, indent + INDENT),
+        }
+    }
+}
+
+/// Write the value after the `:` of the key or the `-` of the sequence item.
+fn write_nested_value(output: &mut std::string::String, value: &Value, indent: usize) {
+    match value {
+        Value::Table(table) if !table.key_values().is_empty() => {
+            output.push('\n');
+            write_table(output, table, indent, false);
+        }
+        Value::Array(array) if !array.is_empty() => {
+            output.push('\n');
+            write_array(output, array, indent, false);
+        }
+        value => {
+            output.push(' ');
+            write_scalar(output, value);
+            output.push('\n');
+        }
+    }
+}
+
+fn write_scalar(output: &mut std::string::String, value: &Value) {
+    match value {
+        Value::Boolean(boolean) => output.push_str(&boolean.value().to_string()),
+        Value::Integer(integer) => output.push_str(&integer.value().to_string()),
+        Value::Float(float) => {
+            let float = float.value();
+            if float.is_nan() {
+                output.push_str(".nan");
+            } else if float.is_infinite() {
+                output.push_str(if float.is_sign_positive() {
+                    ".inf"
+                } else {
+                    "-.inf"
+                });
+            } else {
+                output.push_str(&format!("{float:?}"));
+            }
+        }
+        Value::String(string) => write_string(output, string.value()),
+        Value::OffsetDateTime(date_time) => output.push_str(&date_time.to_string()),
+        Value::LocalDateTime(date_time) => output.push_str(&date_time.to_string()),
+        Value::LocalDate(date) => output.push_str(&date.to_string()),
+        Value::LocalTime(time) => output.push_str(&time.to_string()),
+        Value::Array(_) => output.push_str("[]"),
+        Value::Table(_) => output.push_str("{}"),
+    }
+}
+
+/// Write the string as the plain scalar if it is read back as the same string,
+/// otherwise as the double-quoted scalar.
+fn write_string(output: &mut std::string::String, value: &str) {
+    if is_plain_string(value) {
+        output.push_str(value);
+    } else {
+        // The escapes of the JSON strings are also valid in the YAML double-quoted scalars.
+        output.push_str(&serde_json::to_string(value).expect("string must be serializable"));
+    }
+}
+
+fn is_plain_string(value: &str) -> bool {
+    // The YAML 1.1 booleans are quoted, so that the older parsers read them as strings.
+    const YAML_1_1_BOOLEANS: &[&str] = &[
+        "y", "Y", "n", "N", "yes", "Yes", "YES", "no", "No", "NO", "on", "On", "ON", "off", "Off",
+        "OFF",
+    ];
+
+    value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '/')
+        && !value.ends_with(' ')
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.' | '/' | '+' | '@'))
+        && !YAML_1_1_BOOLEANS.contains(&value)
+        && tombi_yaml::resolve_scalar(value) != Some(tombi_yaml::Scalar::Null)
+        && resolve_plain_scalar(value).is_none()
+}
+
+fn write_indent(output: &mut std::string::String, indent: usize) {
+    output.extend(std::iter::repeat_n(' ', indent));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_document;
+
+    #[test]
+    fn to_yaml_string() {
+        let document = parse_document(
+            r#"
+name = "tombi"
+version = "1.0"
+count = 42
+infinity = -inf
+released = 1979-05-27T07:32:00Z
+date = 1979-05-27
+tags = ["toml", "yes", ""]
+empty = []
+matrix = [[1, 2], [{ a = 1 }]]
+"quoted key" = "a: b"
+[server]
+host = "localhost"
+[[items]]
+enabled = true
+name = "first"
+"#,
+        );
+
+        pretty_assertions::assert_eq!(
+            document.to_yaml_string(),
+            textwrap::dedent(
+                r#"
+                name: tombi
+                version: "1.0"
+                count: 42
+                infinity: -.inf
+                released: 1979-05-27T07:32:00Z
+                date: 1979-05-27
+                tags:
+                  - toml
+                  - "yes"
+                  - ""
+                empty: []
+                matrix:
+                  - - 1
+                    - 2
+                  - - a: 1
+                quoted key: "a: b"
+                server:
+                  host: localhost
+                items:
+                  - enabled: true
+                    name: first
+                "#
+            )
+            .trim_start()
+        );
+    }
+
+    #[test]
+    fn yaml_round_trip() {
+        let document = parse_document(
+            r#"
+"quoted key" = 1979-05-27
+time = 07:32:00
+date-string = "1979-05-27"
+nested = [[1, 2], [{ a = 0.5 }]]
+[[items]]
+enabled = true
+"#,
+        );
+
+        let converted = Document::try_from_yaml_str(&document.to_yaml_string()).unwrap();
+        pretty_assertions::assert_eq!(
+            converted.to_json_value(crate::JsonEncoding::Tagged),
+            document.to_json_value(crate::JsonEncoding::Tagged)
+        );
+    }
+
+    #[test]
+    fn try_from_yaml_str() {
+        let document = Document::try_from_yaml_str(&textwrap::dedent(
+            r#"
+            base: &base
+              port: 8080
+            server: *base
+            released: 1979-05-27T07:32:00Z
+            quoted: '1979-05-27'
+            tagged: !!str 42
+            "a.b": [x, 1, 1.5, true]
+            "#,
+        ))
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            serde_json::to_value(document.to_json_value(crate::JsonEncoding::Tagged)).unwrap(),
+            serde_json::json!({
+                "base": {"port": {"type": "integer", "value": "8080"}},
+                "server": {"port": {"type": "integer", "value": "8080"}},
+                "released": {"type": "datetime", "value": "1979-05-27T07:32:00Z"},
+                "quoted": {"type": "string", "value": "1979-05-27"},
+                "tagged": {"type": "string", "value": "42"},
+                "a.b": [
+                    {"type": "string", "value": "x"},
+                    {"type": "integer", "value": "1"},
+                    {"type": "float", "value": "1.5"},
+                    {"type": "bool", "value": "true"}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn floats() {
+        let document =
+            Document::try_from_yaml_str("integral: 1.0\nnan: .nan\ninf: -.inf\n").unwrap();
+
+        pretty_assertions::assert_eq!(
+            serde_json::to_value(document.to_json_value(crate::JsonEncoding::Tagged)).unwrap(),
+            serde_json::json!({
+                "integral": {"type": "float", "value": "1.0"},
+                "nan": {"type": "float", "value": "nan"},
+                "inf": {"type": "float", "value": "-inf"}
+            })
+        );
+    }
+
+    #[test]
+    fn merge_keys() {
+        let document = Document::try_from_yaml_str(&textwrap::dedent(
+            r#"
+            base: &base
+              host: localhost
+              port: 8080
+            extra: &extra
+              port: 9090
+              tls: true
+            server:
+              <<: [*base, *extra]
+              port: 80
+            quoted:
+              "<<": literal
+            "#,
+        ))
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            serde_json::to_value(document.to_json_value(crate::JsonEncoding::Plain)).unwrap(),
+            serde_json::json!({
+                "base": {"host": "localhost", "port": 8080},
+                "extra": {"port": 9090, "tls": true},
+                "server": {"port": 80, "host": "localhost", "tls": true},
+                "quoted": {"<<": "literal"}
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_merge_key() {
+        let error = Document::try_from_yaml_str("server:\n  <<: 1\n").unwrap_err();
+        pretty_assertions::assert_eq!(crate::de::join_path(error.path()), "server.<<");
+        pretty_assertions::assert_eq!(
+            error.message(),
+            "the value of the merge key must be a mapping or a sequence of mappings"
+        );
+    }
+
+    #[test]
+    fn duplicate_key_with_path() {
+        let error = Document::try_from_yaml_str(&textwrap::dedent(
+            r#"
+            server:
+              port: 80
+              port: 8080
+            "#,
+        ))
+        .unwrap_err();
+
+        pretty_assertions::assert_eq!(crate::de::join_path(error.path()), "server.port");
+        pretty_assertions::assert_eq!(error.message(), "duplicate key");
+    }
+
+    #[test]
+    fn null_with_path() {
+        let error = Document::try_from_yaml_str(&textwrap::dedent(
+            r#"
+            server:
+              - host: localhost
+                port: ~
+            "#,
+        ))
+        .unwrap_err();
+
+        pretty_assertions::assert_eq!(crate::de::join_path(error.path()), "server[0].port");
+        pretty_assertions::assert_eq!(error.message(), "null cannot be represented in TOML");
+    }
+
+    #[test]
+    fn root_must_be_mapping() {
+        let error = Document::try_from_yaml_str("- 1\n- 2\n").unwrap_err();
+        pretty_assertions::assert_eq!(
+            error.message(),
+            "the root of the TOML document must be a mapping"
+        );
+    }
+
+    #[test]
+    fn empty_stream() {
+        pretty_assertions::assert_eq!(Document::try_from_yaml_str("").unwrap(), Document::new());
+        pretty_assertions::assert_eq!(Document::new().to_yaml_string(), "{}\n");
+    }
+}
@@ -9,3 +9,19 @@ mod value;
 pub trait Format {
     fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error>;
 }
+
+/// Returns the number of the blank lines before the node in the source.
+pub(crate) fn blank_lines_before(node: &tombi_syntax::SyntaxNode) -> usize {
+    let mut line_break_count: usize = 0;
+    let mut token = node.first_token().and_then(|token| token.prev_token());
+    while let Some(prev) = token {
+        match prev.kind() {
+            tombi_syntax::SyntaxKind::LINE_BREAK => line_break_count += 1,
+            tombi_syntax::SyntaxKind::WHITESPACE => {}
+            _ => break,
+        }
+        token = prev.prev_token();
+    }
+
+    line_break_count.saturating_sub(1)
+}
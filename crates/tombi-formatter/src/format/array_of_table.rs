@@ -32,12 +32,7 @@ impl Format for tombi_ast::ArrayOfTable {
 
             self.key_values_begin_dangling_comments().format(f)?;
 
-            for (i, key_value) in key_values.into_iter().enumerate() {
-                if i != 0 {
-                    write!(f, "{}", f.line_ending())?;
-                }
-                key_value.format(f)?;
-            }
+            key_values.format(f)?;
 
             self.key_values_end_dangling_comments().format(f)?;
         }
@@ -1,8 +1,11 @@
 use std::fmt::Write;
 
+use itertools::Itertools;
 use tombi_ast::{
     BeginDanglingComment, DanglingComment, EndDanglingComment, LeadingComment, TailingComment,
 };
+use tombi_config::TomlVersion;
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::Format;
 
@@ -80,6 +83,30 @@ impl Format for Vec<Vec<EndDanglingComment>> {
 
 impl Format for Vec<LeadingComment> {
     fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        if f.comment_reflow() {
+            let lines = self
+                .iter()
+                .enumerate()
+                .map(|(i, comment)| {
+                    let comment = comment.as_ref();
+                    if is_directive_comment(comment) {
+                        comment.to_string().trim_ascii_end().to_string()
+                    } else {
+                        normalize_comment(comment, i == 0)
+                    }
+                })
+                .collect_vec();
+            let width = (f.line_width() as usize)
+                .saturating_sub(f.current_indent().graphemes(true).count());
+
+            for line in reflow_comment_lines(&lines, width, f.toml_version()) {
+                f.write_indent()?;
+                write!(f, "{line}{}", f.line_ending())?;
+            }
+
+            return Ok(());
+        }
+
         for (i, comment) in self.iter().enumerate() {
             f.write_indent()?;
             if i == 0 {
@@ -101,46 +128,207 @@ impl Format for TailingComment {
     }
 }
 
+/// Aligns the tailing comments of the lines.
+///
+/// Each line is a pair of the code and the formatted tailing comment.
+pub(crate) fn align_tailing_comments(lines: &mut [(String, Option<String>)]) {
+    let Some(max_width) = lines
+        .iter()
+        .filter(|(_, comment)| comment.is_some())
+        .map(|(code, _)| code.graphemes(true).count())
+        .max()
+    else {
+        return;
+    };
+
+    for (code, comment) in lines.iter_mut() {
+        if comment.is_some() {
+            let width = code.graphemes(true).count();
+            code.push_str(&" ".repeat(max_width - width));
+        }
+    }
+}
+
 fn format_comment(
     f: &mut crate::Formatter,
     comment: &tombi_ast::Comment,
     strip_leading_spaces: bool,
 ) -> Result<(), std::fmt::Error> {
+    write!(f, "{}", normalize_comment(comment, strip_leading_spaces))
+}
+
+/// Returns the comment with a space after `#`.
+fn normalize_comment(comment: &tombi_ast::Comment, strip_leading_spaces: bool) -> String {
     let comment = comment.to_string();
     {
         // For the purpose of reading the JSON Schema path defined in the file by taplo,
         // we format in a different style from the tombi comment style.
         if let Some(schema_url) = comment.strip_prefix("#:schema ") {
-            return write!(f, "#:schema {}", schema_url.trim());
+            return format!("#:schema {}", schema_url.trim());
         }
     }
 
+    let mut normalized = String::with_capacity(comment.len() + 1);
     let mut iter = comment.trim_ascii_end().chars();
 
     // write '#' character
-    write!(f, "{}", iter.next().unwrap())?;
+    normalized.push(iter.next().unwrap());
 
     if let Some(c) = iter.next() {
         if c != ' ' && c != '\t' {
-            write!(f, " ")?;
+            normalized.push(' ');
         }
-        write!(f, "{}", c)?;
+        normalized.push(c);
     }
     if strip_leading_spaces {
         for c in iter.by_ref() {
             if c != ' ' && c != '\t' {
-                write!(f, "{}", c)?;
+                normalized.push(c);
                 break;
             }
         }
     }
+    normalized.push_str(iter.as_str());
+
+    normalized
+}
+
+/// Whether the comment is a directive comment starting with `#:`, such as `#:schema`.
+#[inline]
+fn is_directive_comment(comment: &tombi_ast::Comment) -> bool {
+    comment.to_string().starts_with("#:")
+}
+
+/// Whether the comment text is a commented-out TOML line, such as `key = "value"` or `[table]`.
+fn is_commented_out_toml(text: &str, toml_version: TomlVersion) -> bool {
+    tombi_parser::parse(text, toml_version).errors.is_empty()
+}
+
+/// Wraps the paragraphs and the bullet list items of the comment lines that exceed the width.
+///
+/// Blank comment lines, directive comments, commented-out TOML lines and indented lines
+/// are kept as they are, and the paragraphs within the width are not joined,
+/// to respect the intended line breaks.
+fn reflow_comment_lines(lines: &[String], width: usize, toml_version: TomlVersion) -> Vec<String> {
+    let mut blocks: Vec<CommentBlock> = vec![];
+    for line in lines {
+        // NOTE: Directive comments have no space after `#`, so they are kept here.
+        let Some(text) = line.strip_prefix("# ") else {
+            blocks.push(CommentBlock::Verbatim(line));
+            continue;
+        };
+        if text.trim().is_empty() || is_commented_out_toml(text, toml_version) {
+            blocks.push(CommentBlock::Verbatim(line));
+            continue;
+        }
+
+        if let Some(marker) = bullet_marker(text) {
+            blocks.push(CommentBlock::Paragraph {
+                first_prefix: marker.to_string(),
+                prefix: " ".repeat(marker.len()),
+                lines: vec![line],
+                words: text[marker.len()..].split_whitespace().collect_vec(),
+            });
+            continue;
+        }
+
+        let indent = text.len() - text.trim_start().len();
+        match blocks.last_mut() {
+            Some(CommentBlock::Paragraph {
+                prefix,
+                lines,
+                words,
+                ..
+            }) if indent == prefix.len() => {
+                lines.push(line);
+                words.extend(text.split_whitespace());
+            }
+            _ if indent == 0 => blocks.push(CommentBlock::Paragraph {
+                first_prefix: String::new(),
+                prefix: String::new(),
+                lines: vec![line],
+                words: text.split_whitespace().collect_vec(),
+            }),
+            _ => blocks.push(CommentBlock::Verbatim(line)),
+        }
+    }
+
+    let mut reflowed = vec![];
+    for block in blocks {
+        match block {
+            CommentBlock::Verbatim(line) => reflowed.push(line.to_string()),
+            CommentBlock::Paragraph {
+                first_prefix,
+                prefix,
+                lines,
+                words,
+            } => {
+                if lines
+                    .iter()
+                    .all(|line| line.graphemes(true).count() <= width)
+                {
+                    reflowed.extend(lines.into_iter().map(ToString::to_string));
+                    continue;
+                }
 
-    write!(f, "{}", iter.as_str())
+                let mut current = format!("# {first_prefix}");
+                let mut has_word = false;
+                for word in words {
+                    if has_word
+                        && current.graphemes(true).count() + 1 + word.graphemes(true).count()
+                            > width
+                    {
+                        reflowed.push(std::mem::replace(&mut current, format!("# {prefix}")));
+                        has_word = false;
+                    }
+                    if has_word {
+                        current.push(' ');
+                    }
+                    current.push_str(word);
+                    has_word = true;
+                }
+                reflowed.push(current);
+            }
+        }
+    }
+
+    reflowed
+}
+
+enum CommentBlock<'a> {
+    Verbatim(&'a str),
+    Paragraph {
+        /// The bullet marker of the first line, such as `- ` or `1. `.
+        first_prefix: String,
+        /// The hanging indent of the continuation lines.
+        prefix: String,
+        lines: Vec<&'a str>,
+        words: Vec<&'a str>,
+    },
+}
+
+/// Returns the bullet marker with the following space, such as `- `, `* ` or `1. `.
+fn bullet_marker(text: &str) -> Option<&str> {
+    if ["- ", "* ", "+ "]
+        .iter()
+        .any(|marker| text.starts_with(marker))
+    {
+        return Some(&text[..2]);
+    }
+
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && text[digits..].starts_with(". ") {
+        return Some(&text[..digits + 2]);
+    }
+
+    None
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test_format;
+    use tombi_config::TomlVersion;
+
+    use crate::{test_format, FormatDefinitions, FormatOptions};
 
     test_format! {
         #[test]
@@ -243,4 +431,137 @@ mod tests {
             "#:schema ../../schemas/x-tombi-toml-v1.0.0.schema.json"
         );
     }
+
+    test_format! {
+        #[test]
+        fn directive_comment_with_comment_reflow(
+            r#"
+            #:tombi format.disabled = true
+            key = "value"
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                comment_reflow: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(source);
+    }
+
+    test_format! {
+        #[test]
+        fn comment_reflow(
+            r#"
+            #:schema ./schema.json
+
+            # This is a long comment paragraph that exceeds the line width.
+            #
+            # - This is a long bullet list item that exceeds the line width.
+            # - short item
+            #
+            # A short paragraph
+            # keeps its line breaks.
+            key = "value"
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                line_width: Some(40.try_into().unwrap()),
+                comment_reflow: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            #:schema ./schema.json
+
+            # This is a long comment paragraph that
+            # exceeds the line width.
+            #
+            # - This is a long bullet list item that
+            #   exceeds the line width.
+            # - short item
+            #
+            # A short paragraph
+            # keeps its line breaks.
+            key = "value"
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn comment_reflow_keeps_commented_out_toml(
+            r#"
+            # The dependencies below are disabled until the next release.
+            # a = 1
+            # b = 2
+            # [table]
+            key = "value"
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                line_width: Some(40.try_into().unwrap()),
+                comment_reflow: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            # The dependencies below are disabled
+            # until the next release.
+            # a = 1
+            # b = 2
+            # [table]
+            key = "value"
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn comment_reflow_in_table(
+            r#"
+            [table]
+            # 1. This is a long numbered list item exceeding the line width.
+            #    It continues here.
+            key = "value"
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                line_width: Some(40.try_into().unwrap()),
+                comment_reflow: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            [table]
+            # 1. This is a long numbered list item
+            #    exceeding the line width. It
+            #    continues here.
+            key = "value"
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn comment_reflow_with_format_options(
+            r#"
+            # This is a long comment paragraph that exceeds the line width.
+            key = "value"
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                line_width: Some(40.try_into().unwrap()),
+                ..Default::default()
+            },
+            &FormatOptions {
+                comment_reflow: Some(true),
+                ..FormatOptions::default()
+            }
+        ) -> Ok(
+            r#"
+            # This is a long comment paragraph that
+            # exceeds the line width.
+            key = "value"
+            "#
+        );
+    }
 }
@@ -1,7 +1,10 @@
 use std::fmt::Write;
 
+use itertools::Itertools;
 use tombi_ast::AstNode;
+use unicode_segmentation::UnicodeSegmentation;
 
+use super::{blank_lines_before, comment::align_tailing_comments};
 use crate::Format;
 
 impl Format for tombi_ast::KeyValue {
@@ -22,9 +25,144 @@ impl Format for tombi_ast::KeyValue {
     }
 }
 
+impl Format for Vec<tombi_ast::KeyValue> {
+    fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        if !f.key_value_equal_alignment() && !f.tailing_comment_alignment() {
+            for (i, key_value) in self.iter().enumerate() {
+                if i != 0 {
+                    write!(f, "{}", f.line_ending())?;
+                    write_blank_lines(key_value, f)?;
+                }
+                key_value.format(f)?;
+            }
+
+            return Ok(());
+        }
+
+        let mut groups: Vec<(&tombi_ast::KeyValue, Vec<KeyValueLine>)> = vec![];
+        for key_value in self {
+            let line = KeyValueLine::new(key_value, f)?;
+            match groups.last_mut() {
+                Some((_, group))
+                    if line.leading_comments.is_empty()
+                        && line.is_single_line
+                        && group.last().is_some_and(|last| last.is_single_line)
+                        && blank_lines(key_value, f) == 0 =>
+                {
+                    group.push(line)
+                }
+                _ => groups.push((key_value, vec![line])),
+            }
+        }
+
+        for (i, (first_key_value, group)) in groups.into_iter().enumerate() {
+            if i != 0 {
+                write!(f, "{}", f.line_ending())?;
+                write_blank_lines(first_key_value, f)?;
+            }
+
+            let max_keys_width = group
+                .iter()
+                .map(|line| line.keys.graphemes(true).count())
+                .max()
+                .unwrap_or_default();
+
+            let mut codes = group
+                .iter()
+                .map(|line| {
+                    let mut code = line.keys.clone();
+                    if f.key_value_equal_alignment() {
+                        code.push_str(
+                            &" ".repeat(max_keys_width - line.keys.graphemes(true).count()),
+                        );
+                    }
+                    code.push_str(&line.rest);
+                    (code, line.tailing_comment.clone())
+                })
+                .collect_vec();
+
+            if f.tailing_comment_alignment() {
+                align_tailing_comments(&mut codes);
+            }
+
+            for (j, (line, (code, tailing_comment))) in group.iter().zip(codes).enumerate() {
+                if j != 0 {
+                    write!(f, "{}", f.line_ending())?;
+                }
+                write!(
+                    f,
+                    "{}{code}{}",
+                    line.leading_comments,
+                    tailing_comment.unwrap_or_default()
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The blank lines before the key-value in the source, up to `max_blank_lines`.
+///
+/// The alignment groups are split by these blank lines, not by the blank lines of the source,
+/// so that the formatted text is formatted to itself.
+fn blank_lines(key_value: &tombi_ast::KeyValue, f: &crate::Formatter) -> usize {
+    blank_lines_before(key_value.syntax()).min(f.max_blank_lines() as usize)
+}
+
+fn write_blank_lines(
+    key_value: &tombi_ast::KeyValue,
+    f: &mut crate::Formatter,
+) -> Result<(), std::fmt::Error> {
+    for _ in 0..blank_lines(key_value, f) {
+        write!(f, "{}", f.line_ending())?;
+    }
+
+    Ok(())
+}
+
+/// A formatted key-value split into the parts to be aligned.
+struct KeyValueLine {
+    leading_comments: String,
+    /// The indent and the keys.
+    keys: String,
+    /// The rest of the key-value from ` = ` without the tailing comment.
+    rest: String,
+    tailing_comment: Option<String>,
+    is_single_line: bool,
+}
+
+impl KeyValueLine {
+    fn new(
+        key_value: &tombi_ast::KeyValue,
+        f: &mut crate::Formatter,
+    ) -> Result<Self, std::fmt::Error> {
+        let text = f.format_to_string(key_value)?;
+        let leading_comments = f.format_to_string(&key_value.leading_comments().collect_vec())?;
+        let keys = f.current_indent() + &f.format_to_string(&key_value.keys().unwrap())?;
+        let tailing_comment = match key_value.value().and_then(|value| value.tailing_comment()) {
+            Some(comment) => Some(f.format_to_string(&comment)?),
+            None => None,
+        };
+
+        let body = &text[leading_comments.len()..];
+        let rest = &body[keys.len()..body.len() - tailing_comment.as_ref().map_or(0, String::len)];
+
+        Ok(Self {
+            is_single_line: !body.contains('\n'),
+            rest: rest.to_string(),
+            leading_comments,
+            keys,
+            tailing_comment,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::test_format;
+    use tombi_config::TomlVersion;
+
+    use crate::{test_format, FormatDefinitions, FormatOptions};
 
     test_format! {
         #[test]
@@ -52,4 +190,168 @@ mod tests {
             "#
         ) -> Ok(source);
     }
+
+    test_format! {
+        #[test]
+        fn key_value_equal_alignment(
+            r#"
+            name = "tombi"
+            version = "0.1.0"  # version comment
+            edition.workspace = true
+
+            # authors comment
+            authors = ["tombi"]
+            license = "MIT"  # license comment
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                key_value_equal_alignment: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            name              = "tombi"
+            version           = "0.1.0"  # version comment
+            edition.workspace = true
+            # authors comment
+            authors = ["tombi"]
+            license = "MIT"  # license comment
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn key_value_tailing_comment_alignment(
+            r#"
+            name = "tombi"  # name comment
+            version = "0.1.0"  # version comment
+            edition.workspace = true
+
+            license = "MIT"  # license comment
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                tailing_comment_alignment: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            name = "tombi"     # name comment
+            version = "0.1.0"  # version comment
+            edition.workspace = true
+            license = "MIT"    # license comment
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn key_value_tailing_comment_alignment_with_blank_lines(
+            r#"
+            name = "tombi"  # name comment
+            version = "0.1.0"  # version comment
+
+            license = "MIT"  # license comment
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                tailing_comment_alignment: Some(true),
+                max_blank_lines: Some(1),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            name = "tombi"     # name comment
+            version = "0.1.0"  # version comment
+
+            license = "MIT"  # license comment
+            "#
+        );
+    }
+
+    #[tokio::test]
+    async fn key_value_alignment_is_idempotent() {
+        let source = textwrap::dedent(
+            r#"
+            name = "tombi"  # name comment
+            version = "0.1.0"  # version comment
+            edition.workspace = true
+
+
+            license = "MIT"  # license comment
+
+            # authors comment
+            authors = ["tombi"]
+            "#,
+        );
+
+        for max_blank_lines in [0, 1, 2] {
+            let format = |source: String| async move {
+                crate::Formatter::new(
+                    TomlVersion::default(),
+                    FormatDefinitions {
+                        key_value_equal_alignment: Some(true),
+                        tailing_comment_alignment: Some(true),
+                        max_blank_lines: Some(max_blank_lines),
+                        ..Default::default()
+                    },
+                    &crate::FormatOptions::default(),
+                    None,
+                    &tombi_schema_store::SchemaStore::new(),
+                )
+                .format(&source)
+                .await
+                .unwrap()
+            };
+
+            let formatted = format(source.clone()).await;
+            pretty_assertions::assert_eq!(format(formatted.clone()).await, formatted);
+        }
+    }
+
+    test_format! {
+        #[test]
+        fn key_value_equal_and_tailing_comment_alignment(
+            r#"
+            [package]
+            name = "tombi"  # name comment
+            version = "0.1.0"  # version comment
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                key_value_equal_alignment: Some(true),
+                tailing_comment_alignment: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            [package]
+            name    = "tombi"  # name comment
+            version = "0.1.0"  # version comment
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn key_value_alignment_with_format_options(
+            r#"
+            name = "tombi"  # name comment
+            version = "0.1.0"  # version comment
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions::default(),
+            &FormatOptions {
+                key_value_equal_alignment: Some(true),
+                tailing_comment_alignment: Some(true),
+                ..FormatOptions::default()
+            }
+        ) -> Ok(
+            r#"
+            name    = "tombi"  # name comment
+            version = "0.1.0"  # version comment
+            "#
+        );
+    }
 }
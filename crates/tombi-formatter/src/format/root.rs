@@ -1,8 +1,9 @@
 use std::fmt::Write;
 
 use itertools::Itertools;
+use tombi_ast::AstNode;
 
-use super::Format;
+use super::{blank_lines_before, Format};
 
 impl Format for tombi_ast::Root {
     fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
@@ -15,12 +16,7 @@ impl Format for tombi_ast::Root {
         if !key_values.is_empty() {
             self.key_values_begin_dangling_comments().format(f)?;
 
-            for (i, key_value) in key_values.iter().enumerate() {
-                if i != 0 {
-                    write!(f, "{}", f.line_ending())?;
-                }
-                key_value.format(f)?;
-            }
+            key_values.format(f)?;
 
             self.key_values_end_dangling_comments().format(f)?;
         } else {
@@ -31,91 +27,67 @@ impl Format for tombi_ast::Root {
             || key_values.is_empty() && key_values_dangling_comments.is_empty())
         {
             write!(f, "{}", f.line_ending())?;
-            write!(f, "{}", f.line_ending())?;
+            write_blank_lines(table_or_array_of_tables[0].syntax(), f)?;
         }
 
-        let mut header = Header::Root;
+        let mut pre_header: Option<Header> = None;
+        let mut group_header_keys = None;
         for (i, table_or_array_of_tables) in table_or_array_of_tables.iter().enumerate() {
             if i != 0 {
                 write!(f, "{}", f.line_ending())?;
             }
-            match table_or_array_of_tables {
-                tombi_ast::TableOrArrayOfTable::Table(table) => {
-                    let header_keys = table.header().unwrap().keys();
-                    let key_value_size = table.key_values().count();
-                    let has_dangling_comments = !table.key_values_dangling_comments().is_empty();
-
-                    match header {
-                        Header::Root => {}
-                        Header::Table {
-                            header_keys: pre_header_keys,
-                            key_value_size,
-                            has_dangling_comments,
-                        }
-                        | Header::ArrayOfTable {
-                            header_keys: pre_header_keys,
-                            key_value_size,
-                            has_dangling_comments,
-                        } => {
-                            if key_value_size > 0
-                                || !header_keys.starts_with(&pre_header_keys)
-                                || has_dangling_comments
-                            {
-                                write!(f, "{}", f.line_ending())?;
-                            }
-                        }
-                    };
-                    table.format(f)?;
-
-                    header = Header::Table {
-                        header_keys,
-                        key_value_size,
-                        has_dangling_comments,
-                    };
+
+            let header = match table_or_array_of_tables {
+                tombi_ast::TableOrArrayOfTable::Table(table) => Header {
+                    header_keys: table.header().unwrap().keys(),
+                    key_value_size: table.key_values().count(),
+                    has_dangling_comments: !table.key_values_dangling_comments().is_empty(),
+                    is_array_of_table: false,
+                },
+                tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => Header {
+                    header_keys: array_of_table.header().unwrap().keys(),
+                    key_value_size: array_of_table.key_values().count(),
+                    has_dangling_comments: !array_of_table
+                        .key_values_dangling_comments()
+                        .is_empty(),
+                    is_array_of_table: true,
+                },
+            };
+
+            match pre_header {
+                None => group_header_keys = Some(header.header_keys.clone()),
+                Some(pre_header) => {
+                    let is_same_array_of_tables = pre_header.is_array_of_table
+                        && header.is_array_of_table
+                        && pre_header.header_keys.same_as(&header.header_keys);
+                    let is_subtable = group_header_keys.as_ref().is_some_and(|group_header_keys| {
+                        header.header_keys.starts_with(group_header_keys)
+                            && !header.header_keys.same_as(group_header_keys)
+                    });
+
+                    let is_blank_line = (pre_header.key_value_size > 0
+                        || !header.header_keys.starts_with(&pre_header.header_keys)
+                        || is_same_array_of_tables
+                        || pre_header.has_dangling_comments
+                        || f.blank_line_before_table_header())
+                        && !(f.compact_subtables() && is_subtable)
+                        && !(f.compact_array_of_tables() && is_same_array_of_tables);
+
+                    if is_blank_line {
+                        write_blank_lines(table_or_array_of_tables.syntax(), f)?;
+                        group_header_keys = Some(header.header_keys.clone());
+                    }
                 }
+            }
+
+            match table_or_array_of_tables {
+                tombi_ast::TableOrArrayOfTable::Table(table) => table.format(f)?,
                 tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => {
-                    let header_keys = array_of_table.header().unwrap().keys();
-                    let key_value_size = array_of_table.key_values().count();
-                    let has_dangling_comments =
-                        !array_of_table.key_values_dangling_comments().is_empty();
-
-                    match header {
-                        Header::Root => {}
-                        Header::Table {
-                            header_keys: pre_header_keys,
-                            key_value_size,
-                            has_dangling_comments,
-                        } => {
-                            if key_value_size > 0
-                                || !header_keys.starts_with(&pre_header_keys)
-                                || has_dangling_comments
-                            {
-                                write!(f, "{}", f.line_ending())?;
-                            }
-                        }
-                        Header::ArrayOfTable {
-                            header_keys: pre_header_keys,
-                            key_value_size,
-                            has_dangling_comments,
-                        } => {
-                            if key_value_size > 0
-                                || !header_keys.starts_with(&pre_header_keys)
-                                || pre_header_keys.same_as(&header_keys)
-                                || has_dangling_comments
-                            {
-                                write!(f, "{}", f.line_ending())?;
-                            }
-                        }
-                    };
-                    array_of_table.format(f)?;
-
-                    header = Header::ArrayOfTable {
-                        header_keys,
-                        key_value_size,
-                        has_dangling_comments,
-                    };
+                    array_of_table.format(f)?
                 }
             }
+
+            pre_header = Some(header);
         }
 
         Ok(())
@@ -133,25 +105,35 @@ impl Format for tombi_ast::RootItem {
 }
 
 #[derive(Debug)]
-enum Header {
-    Root,
-
-    Table {
-        header_keys: tombi_ast::AstChildren<tombi_ast::Key>,
-        key_value_size: usize,
-        has_dangling_comments: bool,
-    },
-
-    ArrayOfTable {
-        header_keys: tombi_ast::AstChildren<tombi_ast::Key>,
-        key_value_size: usize,
-        has_dangling_comments: bool,
-    },
+struct Header {
+    header_keys: tombi_ast::AstChildren<tombi_ast::Key>,
+    key_value_size: usize,
+    has_dangling_comments: bool,
+    is_array_of_table: bool,
+}
+
+/// Writes the blank lines before the table.
+///
+/// At least one blank line is written, and the blank lines of the source are preserved up to `max_blank_lines`.
+fn write_blank_lines(
+    node: &tombi_syntax::SyntaxNode,
+    f: &mut crate::Formatter,
+) -> Result<(), std::fmt::Error> {
+    for _ in 0..blank_lines_before(node)
+        .min(f.max_blank_lines() as usize)
+        .max(1)
+    {
+        write!(f, "{}", f.line_ending())?;
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod test {
-    use crate::test_format;
+    use tombi_config::TomlVersion;
+
+    use crate::{test_format, FormatDefinitions};
 
     test_format! {
         #[test]
@@ -333,4 +315,136 @@ mod test {
             "#
         ) -> Ok(source);
     }
+
+    test_format! {
+        #[test]
+        fn max_blank_lines(
+            r#"
+            key1 = 1
+
+
+            key2 = 2
+            key3 = 3
+
+
+
+            [foo]
+            key = 1
+
+            [bar]
+            key = 2
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                max_blank_lines: Some(2),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            key1 = 1
+
+
+            key2 = 2
+            key3 = 3
+
+
+            [foo]
+            key = 1
+
+            [bar]
+            key = 2
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn blank_line_before_table_header(
+            r#"
+            [foo]
+            [foo.bar]
+            [[foo.baz]]
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                blank_line_before_table_header: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            [foo]
+
+            [foo.bar]
+
+            [[foo.baz]]
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn compact_subtables(
+            r#"
+            [foo]
+            key = 1
+
+            [foo.bar]
+            key = 2
+
+            [foo.baz]
+            key = 3
+
+            [qux]
+            key = 4
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                compact_subtables: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            [foo]
+            key = 1
+            [foo.bar]
+            key = 2
+            [foo.baz]
+            key = 3
+
+            [qux]
+            key = 4
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn compact_array_of_tables(
+            r#"
+            [[bin]]
+            name = "tombi"
+
+            [[bin]]
+            name = "tombi-lsp"
+
+            [[test]]
+            name = "test"
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                compact_array_of_tables: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            [[bin]]
+            name = "tombi"
+            [[bin]]
+            name = "tombi-lsp"
+
+            [[test]]
+            name = "test"
+            "#
+        );
+    }
 }
@@ -2,9 +2,10 @@ use std::fmt::Write;
 
 use itertools::Itertools;
 use tombi_ast::AstNode;
+use tombi_syntax::{SyntaxKind, T};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::Format;
+use crate::{format::comment::align_tailing_comments, Format};
 
 impl Format for tombi_ast::Array {
     fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
@@ -16,6 +17,45 @@ impl Format for tombi_ast::Array {
     }
 }
 
+/// A value of a multi-line array with its comma.
+struct ArrayElement {
+    value: tombi_ast::Value,
+    comma: Option<tombi_ast::Comma>,
+}
+
+impl Format for ArrayElement {
+    fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        self.value.format(f)?;
+
+        let (comma_leading_comments, comma_tailing_comment) = match &self.comma {
+            Some(comma) => (
+                comma.leading_comments().collect_vec(),
+                comma.tailing_comment(),
+            ),
+            None => (vec![], None),
+        };
+
+        if !comma_leading_comments.is_empty() {
+            write!(f, "{}", f.line_ending())?;
+            comma_leading_comments.format(f)?;
+            f.write_indent()?;
+            write!(f, ",")?;
+        } else if self.value.tailing_comment().is_some() {
+            write!(f, "{}", f.line_ending())?;
+            f.write_indent()?;
+            write!(f, ",")?;
+        } else {
+            write!(f, ",")?;
+        }
+
+        if let Some(comment) = comma_tailing_comment {
+            comment.format(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 pub(crate) fn exceeds_line_width(
     node: &tombi_ast::Array,
     f: &mut crate::Formatter,
@@ -78,43 +118,34 @@ fn format_multiline_array(
     if values_with_comma.is_empty() {
         array.inner_dangling_comments().format(f)?;
     } else {
-        array.inner_begin_dangling_comments().format(f)?;
-
-        for (i, (value, comma)) in values_with_comma.into_iter().enumerate() {
-            // value format
-            {
+        let mut begin_dangling_comments = array.inner_begin_dangling_comments();
+        // NOTE: The comments starting on the line of `[` are written
+        //       as the leading comments of the first value.
+        let bracket_comments = if has_comment_after_bracket_start(array) {
+            begin_dangling_comments.remove(0)
+        } else {
+            vec![]
+        };
+        begin_dangling_comments.format(f)?;
+        bracket_comments
+            .into_iter()
+            .map(|comment| tombi_ast::LeadingComment::from(tombi_ast::Comment::from(comment)))
+            .collect_vec()
+            .format(f)?;
+
+        let elements = values_with_comma
+            .into_iter()
+            .map(|(value, comma)| ArrayElement { value, comma })
+            .collect_vec();
+
+        if f.tailing_comment_alignment() {
+            format_aligned_array_elements(&elements, f)?;
+        } else {
+            for (i, element) in elements.iter().enumerate() {
                 if i > 0 {
                     write!(f, "{}", f.line_ending())?;
                 }
-                value.format(f)?;
-            }
-
-            // comma format
-            {
-                let (comma_leading_comments, comma_tailing_comment) = match comma {
-                    Some(comma) => (
-                        comma.leading_comments().collect_vec(),
-                        comma.tailing_comment(),
-                    ),
-                    None => (vec![], None),
-                };
-
-                if !comma_leading_comments.is_empty() {
-                    write!(f, "{}", f.line_ending())?;
-                    comma_leading_comments.format(f)?;
-                    f.write_indent()?;
-                    write!(f, ",")?;
-                } else if value.tailing_comment().is_some() {
-                    write!(f, "{}", f.line_ending())?;
-                    f.write_indent()?;
-                    write!(f, ",")?;
-                } else {
-                    write!(f, ",")?;
-                }
-
-                if let Some(comment) = comma_tailing_comment {
-                    comment.format(f)?;
-                }
+                element.format(f)?;
             }
         }
 
@@ -134,6 +165,17 @@ fn format_multiline_array(
     Ok(())
 }
 
+/// Whether a comment follows `[` on the same line.
+fn has_comment_after_bracket_start(array: &tombi_ast::Array) -> bool {
+    array
+        .syntax()
+        .children_with_tokens()
+        .skip_while(|node| node.kind() != T!('['))
+        .skip(1) // skip '['
+        .find(|node| node.kind() != SyntaxKind::WHITESPACE)
+        .is_some_and(|node| node.kind() == SyntaxKind::COMMENT)
+}
+
 fn format_singleline_array(
     array: &tombi_ast::Array,
     f: &mut crate::Formatter,
@@ -160,6 +202,68 @@ fn format_singleline_array(
     Ok(())
 }
 
+/// Formats the elements, aligning the tailing comments of consecutive single-line elements.
+fn format_aligned_array_elements(
+    elements: &[ArrayElement],
+    f: &mut crate::Formatter,
+) -> Result<(), std::fmt::Error> {
+    let mut groups: Vec<Vec<(String, String, Option<String>)>> = vec![];
+    for element in elements {
+        let text = f.format_to_string(element)?;
+        let leading_comments =
+            f.format_to_string(&element.value.leading_comments().collect_vec())?;
+        let tailing_comment = match element
+            .comma
+            .as_ref()
+            .and_then(|comma| comma.tailing_comment())
+        {
+            Some(comment) => Some(f.format_to_string(&comment)?),
+            None => None,
+        };
+        let code = text
+            [leading_comments.len()..text.len() - tailing_comment.as_ref().map_or(0, String::len)]
+            .to_string();
+
+        match groups.last_mut() {
+            Some(group)
+                if leading_comments.is_empty()
+                    && !code.contains('\n')
+                    && group
+                        .last()
+                        .is_some_and(|(_, code, _)| !code.contains('\n')) =>
+            {
+                group.push((leading_comments, code, tailing_comment))
+            }
+            _ => groups.push(vec![(leading_comments, code, tailing_comment)]),
+        }
+    }
+
+    for (i, group) in groups.into_iter().enumerate() {
+        let (leading_comments, mut lines): (Vec<_>, Vec<_>) = group
+            .into_iter()
+            .map(|(leading_comments, code, tailing_comment)| {
+                (leading_comments, (code, tailing_comment))
+            })
+            .unzip();
+        align_tailing_comments(&mut lines);
+
+        for (j, (leading_comments, (code, tailing_comment))) in
+            leading_comments.into_iter().zip(lines).enumerate()
+        {
+            if i > 0 || j > 0 {
+                write!(f, "{}", f.line_ending())?;
+            }
+            write!(
+                f,
+                "{leading_comments}{code}{}",
+                tailing_comment.unwrap_or_default()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -474,4 +578,81 @@ mod tests {
             "#
         );
     }
+
+    test_format! {
+        #[test]
+        fn multiline_array_tailing_comment_alignment(
+            r#"
+            array = [
+              1,  # one
+              22,  # two
+              333,
+              # leading comment
+              4444,  # four
+              55555,  # five
+            ]
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                tailing_comment_alignment: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            array = [
+              1,   # one
+              22,  # two
+              333,
+              # leading comment
+              4444,   # four
+              55555,  # five
+            ]
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn multiline_array_with_comment_after_bracket_and_blank_line(
+            r#"
+            array = [  # comment after bracket
+
+              # dangling comment
+
+              1,
+              2,
+            ]
+            "#
+        ) -> Ok(
+            r#"
+            array = [
+              # dangling comment
+
+              # comment after bracket
+              1,
+              2,
+            ]
+            "#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn multiline_array_with_comment_after_bracket(
+            r#"
+            array = [  # comment after bracket
+              1,
+              2,
+            ]
+            "#
+        ) -> Ok(
+            r#"
+            array = [
+              # comment after bracket
+              1,
+              2,
+            ]
+            "#
+        );
+    }
 }
@@ -1,14 +1,54 @@
-use super::LiteralNode;
+use std::fmt::Write;
 
-impl LiteralNode for tombi_ast::Float {
-    fn token(&self) -> Option<tombi_syntax::SyntaxToken> {
-        self.token()
+use tombi_ast::AstNode;
+
+use crate::format::Format;
+
+impl Format for tombi_ast::Float {
+    fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        self.leading_comments().collect::<Vec<_>>().format(f)?;
+
+        f.write_indent()?;
+        let text = self.token().unwrap().text().to_owned();
+        let text = if f.float_exponent_normalization() {
+            normalize_exponent(&text)
+        } else {
+            text
+        };
+        write!(f, "{text}")?;
+
+        if let Some(comment) = self.tailing_comment() {
+            comment.format(f)?;
+        }
+
+        Ok(())
     }
 }
 
+/// Writes the exponent with a lowercase `e`, without `+` and leading zeros.
+fn normalize_exponent(text: &str) -> String {
+    let Some(index) = text.find(['e', 'E']) else {
+        return text.to_owned();
+    };
+    let (mantissa, exponent) = (&text[..index], &text[index + 1..]);
+    let (sign, digits) = match exponent.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", exponent.strip_prefix('+').unwrap_or(exponent)),
+    };
+    let digits = digits.replace('_', "");
+    let digits = match digits.trim_start_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+
+    format!("{mantissa}e{sign}{digits}")
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::test_format;
+    use tombi_config::TomlVersion;
+
+    use crate::{test_format, FormatDefinitions};
 
     test_format! {
         #[test]
@@ -64,4 +104,40 @@ mod tests {
         #[test]
         fn invalid_key_value3("invalid_float_3 = 3.e+20") -> Err(_);
     }
+
+    test_format! {
+        #[test]
+        fn float_exponent_normalization1(
+            "key = 5E+022",
+            TomlVersion::default(),
+            FormatDefinitions {
+                float_exponent_normalization: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok("key = 5e22");
+    }
+
+    test_format! {
+        #[test]
+        fn float_exponent_normalization2(
+            "key = -2E-02",
+            TomlVersion::default(),
+            FormatDefinitions {
+                float_exponent_normalization: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok("key = -2e-2");
+    }
+
+    test_format! {
+        #[test]
+        fn float_exponent_normalization3(
+            "key = 1.5e00",
+            TomlVersion::default(),
+            FormatDefinitions {
+                float_exponent_normalization: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok("key = 1.5e0");
+    }
 }
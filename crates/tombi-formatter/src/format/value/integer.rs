@@ -1,32 +1,106 @@
-use super::LiteralNode;
+use std::fmt::Write;
 
-impl LiteralNode for tombi_ast::IntegerBin {
-    fn token(&self) -> Option<tombi_syntax::SyntaxToken> {
-        self.token()
+use tombi_ast::AstNode;
+use tombi_config::HexCase;
+
+use crate::format::Format;
+
+impl Format for tombi_ast::IntegerBin {
+    fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        let text = self.token().unwrap().text().to_owned();
+        format_prefixed_integer(self, &text, HexCase::Preserve, f)
+    }
+}
+
+impl Format for tombi_ast::IntegerHex {
+    fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        let text = self.token().unwrap().text().to_owned();
+        format_prefixed_integer(self, &text, f.hex_case(), f)
     }
 }
 
-impl LiteralNode for tombi_ast::IntegerHex {
-    fn token(&self) -> Option<tombi_syntax::SyntaxToken> {
-        self.token()
+impl Format for tombi_ast::IntegerDec {
+    fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        self.leading_comments().collect::<Vec<_>>().format(f)?;
+
+        f.write_indent()?;
+        let text = self.token().unwrap().text().to_owned();
+        let text = if f.integer_digit_grouping() {
+            group_digits(&text)
+        } else {
+            text
+        };
+        write!(f, "{text}")?;
+
+        if let Some(comment) = self.tailing_comment() {
+            comment.format(f)?;
+        }
+
+        Ok(())
     }
 }
 
-impl LiteralNode for tombi_ast::IntegerDec {
-    fn token(&self) -> Option<tombi_syntax::SyntaxToken> {
-        self.token()
+impl Format for tombi_ast::IntegerOct {
+    fn format(&self, f: &mut crate::Formatter) -> Result<(), std::fmt::Error> {
+        let text = self.token().unwrap().text().to_owned();
+        format_prefixed_integer(self, &text, HexCase::Preserve, f)
     }
 }
 
-impl LiteralNode for tombi_ast::IntegerOct {
-    fn token(&self) -> Option<tombi_syntax::SyntaxToken> {
-        self.token()
+/// Writes the integer with the `0x`, `0o` or `0b` prefix.
+///
+/// The prefix is always written in lowercase, as TOML requires,
+/// and the case of the digits follows `digits_case`.
+fn format_prefixed_integer(
+    node: &impl AstNode,
+    text: &str,
+    digits_case: HexCase,
+    f: &mut crate::Formatter,
+) -> Result<(), std::fmt::Error> {
+    node.leading_comments().collect::<Vec<_>>().format(f)?;
+
+    f.write_indent()?;
+    let (prefix, digits) = text.split_at(2);
+    let digits = match digits_case {
+        HexCase::Upper => digits.to_uppercase(),
+        HexCase::Lower => digits.to_lowercase(),
+        HexCase::Preserve => digits.to_owned(),
+    };
+    write!(f, "{}{digits}", prefix.to_ascii_lowercase())?;
+
+    if let Some(comment) = node.tailing_comment() {
+        comment.format(f)?;
     }
+
+    Ok(())
+}
+
+/// Groups the digits of a decimal integer by three digits.
+///
+/// Integers with less than five digits are written without `_`.
+fn group_digits(text: &str) -> String {
+    let (sign, digits) = text.split_at(if text.starts_with(['+', '-']) { 1 } else { 0 });
+    let digits = digits.replace('_', "");
+    if digits.len() < 5 {
+        return format!("{sign}{digits}");
+    }
+
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index != 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push('_');
+        }
+        grouped.push(digit);
+    }
+
+    format!("{sign}{grouped}")
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::test_format;
+    use tombi_config::{HexCase, TomlVersion};
+
+    use crate::{test_format, FormatDefinitions, FormatOptions};
 
     test_format! {
         #[test]
@@ -102,4 +176,95 @@ mod tests {
         #[test]
         fn integer_dec_key_value8("int8 = 1_2_3_4_5") -> Ok(source);
     }
+
+    test_format! {
+        #[test]
+        fn integer_dec_digit_grouping1(
+            "int = 1000000",
+            TomlVersion::default(),
+            FormatDefinitions {
+                integer_digit_grouping: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok("int = 1_000_000");
+    }
+
+    test_format! {
+        #[test]
+        fn integer_dec_digit_grouping2(
+            "int = -53_49_221",
+            TomlVersion::default(),
+            FormatDefinitions {
+                integer_digit_grouping: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok("int = -5_349_221");
+    }
+
+    test_format! {
+        #[test]
+        fn integer_dec_digit_grouping3(
+            "int = 1_0_0",
+            TomlVersion::default(),
+            FormatDefinitions {
+                integer_digit_grouping: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok("int = 100");
+    }
+
+    test_format! {
+        #[test]
+        fn integer_hex_case_upper(
+            "hex = 0xdead_beef",
+            TomlVersion::default(),
+            FormatDefinitions {
+                hex_case: Some(HexCase::Upper),
+                ..Default::default()
+            }
+        ) -> Ok("hex = 0xDEAD_BEEF");
+    }
+
+    test_format! {
+        #[test]
+        fn integer_hex_case_lower(
+            "hex = 0xDEADBEEF",
+            TomlVersion::default(),
+            FormatDefinitions {
+                hex_case: Some(HexCase::Lower),
+                ..Default::default()
+            }
+        ) -> Ok("hex = 0xdeadbeef");
+    }
+
+    test_format! {
+        #[test]
+        fn integer_oct_bin_hex_case_upper(
+            r#"
+            oct = 0o755
+            bin = 0b1010
+            "#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                hex_case: Some(HexCase::Upper),
+                ..Default::default()
+            }
+        ) -> Ok(source);
+    }
+
+    test_format! {
+        #[test]
+        fn integer_hex_case_options_take_precedence(
+            "hex = 0xDEADBEEF",
+            TomlVersion::default(),
+            FormatDefinitions {
+                hex_case: Some(HexCase::Upper),
+                ..Default::default()
+            },
+            &FormatOptions {
+                hex_case: Some(HexCase::Lower),
+                ..FormatOptions::default()
+            }
+        ) -> Ok("hex = 0xdeadbeef");
+    }
 }
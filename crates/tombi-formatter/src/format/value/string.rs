@@ -2,6 +2,8 @@ use std::fmt::Write;
 
 use tombi_ast::AstNode;
 use tombi_config::QuoteStyle;
+use tombi_syntax::SyntaxKind;
+use unicode_segmentation::UnicodeSegmentation;
 
 use super::LiteralNode;
 use crate::format::Format;
@@ -23,6 +25,42 @@ impl Format for tombi_ast::BasicString {
                 }
             }
         };
+
+        // NOTE: The leading whitespace of the content would be trimmed
+        //       after the line ending backslash, so the string is not wrapped.
+        if text.starts_with('"')
+            && !text[1..].starts_with([' ', '\t'])
+            && f.string_line_wrap()
+            && is_table_key_value(self.syntax())
+            && f.current_line_width() + text.graphemes(true).count() > f.line_width() as usize
+        {
+            f.inc_indent();
+            let indent = f.current_indent();
+            let lines = wrap_lines(
+                &text[1..text.len() - 1],
+                (f.line_width() as usize).saturating_sub(indent.graphemes(true).count() + 1),
+            );
+            if lines.len() > 1 {
+                write!(f, "\"\"\"\\")?;
+                for (index, line) in lines.iter().enumerate() {
+                    write!(f, "{}", f.line_ending())?;
+                    f.write_indent()?;
+                    if index + 1 == lines.len() {
+                        write!(f, "{line}\"\"\"")?;
+                    } else {
+                        write!(f, "{line}\\")?;
+                    }
+                }
+                f.dec_indent();
+
+                if let Some(comment) = self.tailing_comment() {
+                    comment.format(f)?;
+                }
+
+                return Ok(());
+            }
+            f.dec_indent();
+        }
         write!(f, "{text}")?;
 
         if let Some(comment) = self.tailing_comment() {
@@ -59,6 +97,53 @@ impl Format for tombi_ast::LiteralString {
         Ok(())
     }
 }
+
+/// Whether the string is the value of a key-value in a table, not in an inline table or an array.
+fn is_table_key_value(node: &tombi_syntax::SyntaxNode) -> bool {
+    node.parent().is_some_and(|parent| {
+        parent.kind() == SyntaxKind::KEY_VALUE
+            && parent
+                .parent()
+                .is_some_and(|grand_parent| grand_parent.kind() != SyntaxKind::INLINE_TABLE)
+    })
+}
+
+/// Splits the content of a basic string into lines within the width.
+///
+/// Lines are split only after spaces, and the spaces are kept at the end of the line,
+/// because the whitespace after a line ending backslash is trimmed.
+fn wrap_lines(content: &str, width: usize) -> Vec<&str> {
+    let mut lines = vec![];
+    let mut line_start = 0;
+    let mut line_width = 0;
+    let mut word_start = 0;
+    let mut word_width = 0;
+    let mut prev_is_space = false;
+
+    for (index, grapheme) in content.grapheme_indices(true) {
+        let is_space = grapheme == " ";
+        if prev_is_space && !is_space {
+            if line_width + word_width > width && line_start < word_start {
+                lines.push(&content[line_start..word_start]);
+                line_start = word_start;
+                line_width = 0;
+            }
+            line_width += word_width;
+            word_start = index;
+            word_width = 0;
+        }
+        word_width += 1;
+        prev_is_space = is_space;
+    }
+    if line_width + word_width > width && line_start < word_start {
+        lines.push(&content[line_start..word_start]);
+        line_start = word_start;
+    }
+    lines.push(&content[line_start..]);
+
+    lines
+}
+
 impl LiteralNode for tombi_ast::MultiLineBasicString {
     fn token(&self) -> Option<tombi_syntax::SyntaxToken> {
         self.token()
@@ -110,4 +195,49 @@ mod tests {
             }
         ) -> Ok(source);
     }
+
+    test_format! {
+        #[test]
+        fn basic_string_line_wrap(
+            r#"key = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor.""#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                line_width: Some(40.try_into().unwrap()),
+                string_line_wrap: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(
+            r#"
+            key = """\
+              Lorem ipsum dolor sit amet, \
+              consectetur adipiscing elit, sed do \
+              eiusmod tempor.""""#
+        );
+    }
+
+    test_format! {
+        #[test]
+        fn basic_string_line_wrap_with_leading_whitespace(
+            r#"key = "   Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor.""#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                line_width: Some(40.try_into().unwrap()),
+                string_line_wrap: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(source);
+    }
+
+    test_format! {
+        #[test]
+        fn basic_string_line_wrap_in_inline_table(
+            r#"key = { value = "Lorem ipsum dolor sit amet, consectetur adipiscing elit" }"#,
+            TomlVersion::default(),
+            FormatDefinitions {
+                line_width: Some(40.try_into().unwrap()),
+                string_line_wrap: Some(true),
+                ..Default::default()
+            }
+        ) -> Ok(source);
+    }
 }
@@ -15,7 +15,6 @@ pub struct Formatter<'a> {
     indent_depth: u8,
     skip_indent: bool,
     definitions: crate::FormatDefinitions,
-    #[allow(dead_code)]
     options: &'a crate::FormatOptions,
     source_url_or_path: Option<Either<&'a Url, &'a std::path::Path>>,
     schema_store: &'a tombi_schema_store::SchemaStore,
@@ -44,7 +43,7 @@ impl<'a> Formatter<'a> {
     }
 
     /// Format a TOML document and return the result as a string
-    pub async fn format(mut self, source: &str) -> Result<String, Vec<Diagnostic>> {
+    pub async fn format(self, source: &str) -> Result<String, Vec<Diagnostic>> {
         let source_schema = if let Some(parsed) =
             tombi_parser::parse_document_header_comments(source).cast::<tombi_ast::Root>()
         {
@@ -57,8 +56,17 @@ impl<'a> Formatter<'a> {
             None
         };
 
+        self.format_with_source_schema(source, source_schema.as_ref())
+            .await
+    }
+
+    /// Format a TOML document with the given schema instead of the one associated with the source.
+    pub async fn format_with_source_schema(
+        mut self,
+        source: &str,
+        source_schema: Option<&tombi_schema_store::SourceSchema>,
+    ) -> Result<String, Vec<Diagnostic>> {
         self.toml_version = source_schema
-            .as_ref()
             .and_then(|schema| {
                 schema
                     .root_schema
@@ -78,16 +86,18 @@ impl<'a> Formatter<'a> {
                 diagnostics
             })?;
 
+        let order_overrides = self
+            .schema_store
+            .resolve_order_overrides(self.source_url_or_path)
+            .await;
+
         let root = tombi_ast_editor::Editor::new(
             root,
             &tombi_schema_store::SchemaContext {
                 toml_version: self.toml_version,
-                root_schema: source_schema
-                    .as_ref()
-                    .and_then(|schema| schema.root_schema.as_ref()),
-                sub_schema_url_map: source_schema
-                    .as_ref()
-                    .map(|schema| &schema.sub_schema_url_map),
+                root_schema: source_schema.and_then(|schema| schema.root_schema.as_ref()),
+                sub_schema_url_map: source_schema.map(|schema| &schema.sub_schema_url_map),
+                order_overrides: Some(&order_overrides),
                 store: self.schema_store,
             },
         )
@@ -105,10 +115,7 @@ impl<'a> Formatter<'a> {
     }
 
     /// Format a node and return the result as a string
-    pub(crate) fn format_to_string<T: Format>(
-        &mut self,
-        node: &T,
-    ) -> Result<String, std::fmt::Error> {
+    pub fn format_to_string<T: Format>(&mut self, node: &T) -> Result<String, std::fmt::Error> {
         let old_buf = std::mem::take(&mut self.buf);
         let old_indent = self.indent_depth;
         let old_skip = self.skip_indent;
@@ -155,6 +162,94 @@ impl<'a> Formatter<'a> {
         self.definitions.quote_style.unwrap_or_default()
     }
 
+    #[inline]
+    pub(crate) fn integer_digit_grouping(&self) -> bool {
+        self.options
+            .integer_digit_grouping
+            .or(self.definitions.integer_digit_grouping)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn hex_case(&self) -> tombi_config::HexCase {
+        self.options
+            .hex_case
+            .or(self.definitions.hex_case)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn float_exponent_normalization(&self) -> bool {
+        self.options
+            .float_exponent_normalization
+            .or(self.definitions.float_exponent_normalization)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn string_line_wrap(&self) -> bool {
+        self.options
+            .string_line_wrap
+            .or(self.definitions.string_line_wrap)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn key_value_equal_alignment(&self) -> bool {
+        self.options
+            .key_value_equal_alignment
+            .or(self.definitions.key_value_equal_alignment)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn tailing_comment_alignment(&self) -> bool {
+        self.options
+            .tailing_comment_alignment
+            .or(self.definitions.tailing_comment_alignment)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn comment_reflow(&self) -> bool {
+        self.options
+            .comment_reflow
+            .or(self.definitions.comment_reflow)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn max_blank_lines(&self) -> u8 {
+        self.options
+            .max_blank_lines
+            .or(self.definitions.max_blank_lines)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn blank_line_before_table_header(&self) -> bool {
+        self.options
+            .blank_line_before_table_header
+            .or(self.definitions.blank_line_before_table_header)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn compact_subtables(&self) -> bool {
+        self.options
+            .compact_subtables
+            .or(self.definitions.compact_subtables)
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub(crate) fn compact_array_of_tables(&self) -> bool {
+        self.options
+            .compact_array_of_tables
+            .or(self.definitions.compact_array_of_tables)
+            .unwrap_or_default()
+    }
+
     #[inline]
     pub(crate) const fn tailing_comment_space(&self) -> &'static str {
         self.definitions.tailing_comment_space()
@@ -190,6 +285,11 @@ impl<'a> Formatter<'a> {
         }
     }
 
+    #[inline]
+    pub(crate) fn current_indent(&self) -> String {
+        self.ident(self.indent_depth)
+    }
+
     #[inline]
     pub(crate) fn reset(&mut self) {
         self.reset_indent();
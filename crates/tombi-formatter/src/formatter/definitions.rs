@@ -1,5 +1,5 @@
 use tombi_config::{
-    DateTimeDelimiter, IndentStyle, IndentWidth, LineEnding, LineWidth, QuoteStyle,
+    DateTimeDelimiter, HexCase, IndentStyle, IndentWidth, LineEnding, LineWidth, QuoteStyle,
 };
 
 /// FormatDefinitions provides the definition of the format that does not have the freedom set by [`FormatOptions`][crate::FormatOptions].
@@ -51,6 +51,62 @@ pub struct FormatDefinitions {
 
     /// # The preferred quote character for strings.
     pub quote_style: Option<QuoteStyle>,
+
+    /// # Group the digits of decimal integers.
+    ///
+    /// [`FormatOptions::integer_digit_grouping`][crate::FormatOptions::integer_digit_grouping] takes precedence.
+    pub integer_digit_grouping: Option<bool>,
+
+    /// # The letter case of hexadecimal digits.
+    ///
+    /// [`FormatOptions::hex_case`][crate::FormatOptions::hex_case] takes precedence.
+    #[cfg_attr(feature = "jsonschema", schemars(default = "HexCase::default"))]
+    pub hex_case: Option<HexCase>,
+
+    /// # Normalize the exponent of floats.
+    ///
+    /// [`FormatOptions::float_exponent_normalization`][crate::FormatOptions::float_exponent_normalization] takes precedence.
+    pub float_exponent_normalization: Option<bool>,
+
+    /// # Wrap long strings.
+    ///
+    /// [`FormatOptions::string_line_wrap`][crate::FormatOptions::string_line_wrap] takes precedence.
+    pub string_line_wrap: Option<bool>,
+
+    /// # Align the equal signs of key-values.
+    ///
+    /// [`FormatOptions::key_value_equal_alignment`][crate::FormatOptions::key_value_equal_alignment] takes precedence.
+    pub key_value_equal_alignment: Option<bool>,
+
+    /// # Align the tailing comments.
+    ///
+    /// [`FormatOptions::tailing_comment_alignment`][crate::FormatOptions::tailing_comment_alignment] takes precedence.
+    pub tailing_comment_alignment: Option<bool>,
+
+    /// # Reflow the leading comments.
+    ///
+    /// [`FormatOptions::comment_reflow`][crate::FormatOptions::comment_reflow] takes precedence.
+    pub comment_reflow: Option<bool>,
+
+    /// # The maximum number of consecutive blank lines to preserve.
+    ///
+    /// [`FormatOptions::max_blank_lines`][crate::FormatOptions::max_blank_lines] takes precedence.
+    pub max_blank_lines: Option<u8>,
+
+    /// # Insert a blank line before each table header.
+    ///
+    /// [`FormatOptions::blank_line_before_table_header`][crate::FormatOptions::blank_line_before_table_header] takes precedence.
+    pub blank_line_before_table_header: Option<bool>,
+
+    /// # Remove the blank line before subtables.
+    ///
+    /// [`FormatOptions::compact_subtables`][crate::FormatOptions::compact_subtables] takes precedence.
+    pub compact_subtables: Option<bool>,
+
+    /// # Remove the blank lines between array of tables.
+    ///
+    /// [`FormatOptions::compact_array_of_tables`][crate::FormatOptions::compact_array_of_tables] takes precedence.
+    pub compact_array_of_tables: Option<bool>,
 }
 
 impl FormatDefinitions {
@@ -62,6 +118,17 @@ impl FormatDefinitions {
             line_ending: None,
             date_time_delimiter: None,
             quote_style: None,
+            integer_digit_grouping: None,
+            hex_case: None,
+            float_exponent_normalization: None,
+            string_line_wrap: None,
+            key_value_equal_alignment: None,
+            tailing_comment_alignment: None,
+            comment_reflow: None,
+            max_blank_lines: None,
+            blank_line_before_table_header: None,
+            compact_subtables: None,
+            compact_array_of_tables: None,
         }
     }
 
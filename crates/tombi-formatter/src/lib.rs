@@ -1,7 +1,7 @@
 mod format;
 pub mod formatter;
 
-use format::Format;
+pub use format::Format;
 use formatter::definitions::FormatDefinitions;
 pub use formatter::Formatter;
 pub use tombi_config::FormatOptions;
@@ -33,6 +33,42 @@ mod table_keys_order {
             )
         }
 
+        test_format! {
+            #[tokio::test]
+            async fn test_root_tables(
+                r#"
+                # Lint settings
+                [tool.ruff]
+                line-length = 88
+
+                [build-system]
+                requires = ["maturin>=1.5,<2.0"]
+
+                [project]
+                name = "tombi"
+
+                [project.urls]
+                homepage = "https://tombi-toml.github.io/tombi"
+                "#,
+                pyproject_schema_path(),
+            ) -> Ok(
+                r#"
+                [project]
+                name = "tombi"
+
+                [project.urls]
+                homepage = "https://tombi-toml.github.io/tombi"
+
+                [build-system]
+                requires = ["maturin>=1.5,<2.0"]
+
+                # Lint settings
+                [tool.ruff]
+                line-length = 88
+                "#
+            )
+        }
+
         test_format! {
             #[tokio::test]
             async fn test_project_dependencies_single_line(
@@ -265,6 +301,188 @@ mod table_keys_order {
         }
     }
 
+    mod overrides {
+        use super::test_format;
+        use tombi_config::OverrideItem;
+        use tombi_test_lib::{pyproject_schema_path, type_test_schema_path};
+        use tombi_x_keyword::{ArrayValuesOrder, TableKeysOrder};
+
+        test_format! {
+            #[tokio::test]
+            async fn test_override_table_keys_order(
+                r#"
+                [project]
+                name = "tombi"
+                description = "TOML Toolkit"
+                "#,
+                pyproject_schema_path(),
+                overrides = vec![OverrideItem {
+                    include: vec!["*.toml".to_string()],
+                    keys: vec!["project".to_string()],
+                    table_keys_order: Some(TableKeysOrder::Ascending),
+                    array_values_order: None,
+                }],
+            ) -> Ok(
+                r#"
+                [project]
+                description = "TOML Toolkit"
+                name = "tombi"
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_override_array_values_order(
+                r#"
+                [project]
+                name = "tombi"
+                keywords = ["toml", "format", "lint"]
+                "#,
+                pyproject_schema_path(),
+                overrides = vec![OverrideItem {
+                    include: vec!["*.toml".to_string()],
+                    keys: vec!["project.keywords".to_string()],
+                    table_keys_order: None,
+                    array_values_order: Some(ArrayValuesOrder::Descending),
+                }],
+            ) -> Ok(
+                r#"
+                [project]
+                name = "tombi"
+                keywords = ["toml", "lint", "format"]
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_override_root_table_keys_order(
+                r#"
+                integer = 1
+                boolean = true
+                "#,
+                type_test_schema_path(),
+                overrides = vec![OverrideItem {
+                    include: vec!["*.toml".to_string()],
+                    keys: vec!["".to_string()],
+                    table_keys_order: Some(TableKeysOrder::Schema),
+                    array_values_order: None,
+                }],
+            ) -> Ok(
+                r#"
+                boolean = true
+                integer = 1
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_override_root_tables_order(
+                r#"
+                [project]
+                name = "tombi"
+
+                [project.urls]
+                homepage = "https://tombi-toml.github.io/tombi"
+
+                [tool.ruff]
+                line-length = 88
+
+                [build-system]
+                requires = ["maturin>=1.5,<2.0"]
+                "#,
+                pyproject_schema_path(),
+                overrides = vec![OverrideItem {
+                    include: vec!["*.toml".to_string()],
+                    keys: vec!["".to_string()],
+                    table_keys_order: Some(TableKeysOrder::Ascending),
+                    array_values_order: None,
+                }],
+            ) -> Ok(
+                r#"
+                [build-system]
+                requires = ["maturin>=1.5,<2.0"]
+
+                [project]
+                name = "tombi"
+
+                [project.urls]
+                homepage = "https://tombi-toml.github.io/tombi"
+
+                [tool.ruff]
+                line-length = 88
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_override_not_included(
+                r#"
+                [project]
+                name = "tombi"
+                keywords = ["toml", "format", "lint"]
+                "#,
+                pyproject_schema_path(),
+                overrides = vec![OverrideItem {
+                    include: vec!["other.toml".to_string()],
+                    keys: vec!["project.keywords".to_string()],
+                    table_keys_order: None,
+                    array_values_order: Some(ArrayValuesOrder::Descending),
+                }],
+            ) -> Ok(
+                r#"
+                [project]
+                name = "tombi"
+                keywords = ["format", "lint", "toml"]
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_override_without_schema(
+                r#"
+                [project]
+                name = "tombi"
+                urls = { repository = "https://github.com/tombi-toml/tombi", homepage = "https://tombi-toml.github.io/tombi" }
+                description = "TOML Toolkit"
+                keywords = ["toml", "format", "lint"]
+                "#,
+                overrides = vec![
+                    OverrideItem {
+                        include: vec!["*.toml".to_string()],
+                        keys: vec!["project".to_string()],
+                        table_keys_order: Some(TableKeysOrder::Ascending),
+                        array_values_order: None,
+                    },
+                    OverrideItem {
+                        include: vec!["*.toml".to_string()],
+                        keys: vec!["project.keywords".to_string()],
+                        table_keys_order: None,
+                        array_values_order: Some(ArrayValuesOrder::Ascending),
+                    },
+                    OverrideItem {
+                        include: vec!["*.toml".to_string()],
+                        keys: vec!["project.urls".to_string()],
+                        table_keys_order: Some(TableKeysOrder::Ascending),
+                        array_values_order: None,
+                    },
+                ],
+            ) -> Ok(
+                r#"
+                [project]
+                description = "TOML Toolkit"
+                keywords = ["format", "lint", "toml"]
+                name = "tombi"
+                urls = { homepage = "https://tombi-toml.github.io/tombi", repository = "https://github.com/tombi-toml/tombi" }
+                "#
+            )
+        }
+    }
+
     mod cargo {
         use tombi_test_lib::cargo_schema_path;
 
@@ -432,6 +650,66 @@ mod table_keys_order {
                 "#
             )
         }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_cargo_root_tables(
+                r#"
+                # Targets
+                [[bin]]
+                name = "a"
+
+                [dependencies]
+                serde = "1.0"
+
+                # Platform specific
+                [target.'cfg(unix)'.dependencies]
+                libc = "0.2"
+
+                [[bin]]
+                name = "b"
+
+                # The package
+                [package]
+                name = "demo"
+                version = "0.1.0"
+
+                [package.metadata.docs]
+                all-features = true
+
+                [dev-dependencies]
+                tokio = "1"
+                "#,
+                cargo_schema_path(),
+            ) -> Ok(
+                r#"
+                # The package
+                [package]
+                name = "demo"
+                version = "0.1.0"
+
+                [package.metadata.docs]
+                all-features = true
+
+                # Targets
+                [[bin]]
+                name = "a"
+
+                [[bin]]
+                name = "b"
+
+                [dependencies]
+                serde = "1.0"
+
+                [dev-dependencies]
+                tokio = "1"
+
+                # Platform specific
+                [target.'cfg(unix)'.dependencies]
+                libc = "0.2"
+                "#
+            )
+        }
     }
 
     mod tombi {
@@ -880,8 +1158,304 @@ mod table_keys_order {
         }
     }
 
+    mod array_values_order_by {
+        use super::test_format;
+        use tombi_test_lib::x_tombi_array_values_order_by_schema_path;
+
+        test_format! {
+            #[tokio::test]
+            async fn test_inline_tables_order_by_keys(
+                r#"
+                authors = [
+                  # carol leading comment
+                  { name = "carol", email = "c@example.com" },  # carol trailing comment
+                  { name = "alice", email = "b@example.com" },
+                  { name = "alice", email = "a@example.com" },
+                ]
+                "#,
+                x_tombi_array_values_order_by_schema_path(),
+            ) -> Ok(
+                r#"
+                authors = [
+                  { name = "alice", email = "a@example.com" },
+                  { name = "alice", email = "b@example.com" },
+                  # carol leading comment
+                  { name = "carol", email = "c@example.com" },  # carol trailing comment
+                ]
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_inline_tables_order_by_version_sort(
+                r#"
+                releases = [{ version = "1.10.0" }, { version = "1.2.0" }, { version = "1.9.1" }]
+                "#,
+                x_tombi_array_values_order_by_schema_path(),
+            ) -> Ok(
+                r#"
+                releases = [
+                  { version = "1.2.0" },
+                  { version = "1.9.1" },
+                  { version = "1.10.0" },
+                ]
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_array_of_tables_order_by_key(
+                r#"
+                # zeta leading comment
+                [[bin]]  # zeta header trailing comment
+                name = "zeta"
+                path = "src/zeta.rs"  # zeta path trailing comment
+
+                [bin.metadata]
+                key = "zeta"
+
+                # alpha leading comment
+                [[bin]]
+                name = "alpha"
+                path = "src/alpha.rs"
+
+                [[bin]]
+                path = "src/nameless.rs"
+
+                [[bin]]
+                name = "beta"
+                "#,
+                x_tombi_array_values_order_by_schema_path(),
+            ) -> Ok(
+                r#"
+                # alpha leading comment
+                [[bin]]
+                name = "alpha"
+                path = "src/alpha.rs"
+
+                [[bin]]
+                name = "beta"
+
+                # zeta leading comment
+                [[bin]]  # zeta header trailing comment
+                name = "zeta"
+                path = "src/zeta.rs"  # zeta path trailing comment
+
+                [bin.metadata]
+                key = "zeta"
+
+                [[bin]]
+                path = "src/nameless.rs"
+                "#
+            )
+        }
+    }
+
+    mod table_style {
+        use super::test_format;
+        use tombi_test_lib::x_tombi_table_style_schema_path;
+
+        test_format! {
+            #[tokio::test]
+            async fn test_dotted_keys_to_inline_table(
+                r#"
+                [dependencies]
+                # toml leading comment
+                toml.version = "0.8"
+                toml.features = ["parse"]  # toml trailing comment
+                serde = "1"
+                "#,
+                x_tombi_table_style_schema_path(),
+            ) -> Ok(
+                r#"
+                [dependencies]
+                # toml leading comment
+                toml = { version = "0.8", features = ["parse"] }  # toml trailing comment
+                serde = "1"
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_section_to_inline_table(
+                r#"
+                [dependencies]
+                serde = "1"
+
+                # tokio leading comment
+                [dependencies.tokio]  # tokio trailing comment
+                version = "1"
+                features = ["full"]
+                "#,
+                x_tombi_table_style_schema_path(),
+            ) -> Ok(
+                r#"
+                [dependencies]
+                serde = "1"
+                # tokio leading comment
+                tokio = { version = "1", features = ["full"] }  # tokio trailing comment
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_section_with_inner_comments_is_kept(
+                r#"
+                [dependencies]
+                serde = "1"
+
+                [dependencies.tokio]
+                # version leading comment
+                version = "1"
+                "#,
+                x_tombi_table_style_schema_path(),
+            ) -> Ok(
+                r#"
+                [dependencies]
+                serde = "1"
+
+                [dependencies.tokio]
+                # version leading comment
+                version = "1"
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_inline_table_and_dotted_keys_to_section(
+                r#"
+                [profile]
+                release = { opt-level = 3 }  # release trailing comment
+                dev.debug = true  # debug trailing comment
+                "#,
+                x_tombi_table_style_schema_path(),
+            ) -> Ok(
+                r#"
+                [profile]
+                [profile.release]  # release trailing comment
+                opt-level = 3
+
+                [profile.dev]
+                debug = true  # debug trailing comment
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_inline_table_and_section_to_dotted_keys(
+                r#"
+                [package]
+                name = "foo"
+
+                [package.metadata]
+                a = 1  # a trailing comment
+                b = "x"
+                "#,
+                x_tombi_table_style_schema_path(),
+            ) -> Ok(
+                r#"
+                [package]
+                name = "foo"
+                metadata.a = 1  # a trailing comment
+                metadata.b = "x"
+                "#
+            )
+        }
+    }
+
+    mod array_values_unique {
+        use super::test_format;
+        use tombi_test_lib::x_tombi_array_values_unique_schema_path;
+
+        test_format! {
+            #[tokio::test]
+            async fn test_unique_items(
+                r#"
+                keywords = ["toml", "json", 'toml', "yaml", "json"]
+                "#,
+                x_tombi_array_values_unique_schema_path(),
+            ) -> Ok(
+                r#"
+                keywords = ["toml", "json", "yaml"]
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_x_tombi_array_values_unique_with_comments(
+                r#"
+                ports = [
+                  # http leading comment
+                  80,  # http trailing comment
+                  # duplicate leading comment
+                  0x50,  # duplicate trailing comment
+                  443,
+                  80
+                ]
+                "#,
+                x_tombi_array_values_unique_schema_path(),
+            ) -> Ok(
+                r#"
+                ports = [
+                  # http leading comment
+                  80,  # http trailing comment
+                  443,
+                ]
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_unique_inline_tables(
+                r#"
+                authors = [{ name = "a", email = "x" }, { email = "x", name = "a" }, { name = "b" }]
+                "#,
+                x_tombi_array_values_unique_schema_path(),
+            ) -> Ok(
+                r#"
+                authors = [{ name = "a", email = "x" }, { name = "b" }]
+                "#
+            )
+        }
+
+        test_format! {
+            #[tokio::test]
+            async fn test_x_tombi_array_values_unique_false(
+                r#"
+                tags = ["a", "a"]
+                "#,
+                x_tombi_array_values_unique_schema_path(),
+            ) -> Ok(
+                r#"
+                tags = ["a", "a"]
+                "#
+            )
+        }
+    }
+
     #[macro_export]
     macro_rules! test_format {
+        (
+            #[tokio::test]
+            async fn $name:ident(
+                $source:expr,
+                overrides = $overrides:expr$(,)?
+            ) -> Ok($expected:expr$(,)?)
+        ) => {
+            test_format! {
+                #[tokio::test]
+                async fn _$name($source, Option::<&std::path::Path>::None, $overrides) -> Ok($expected)
+            }
+        };
+
         (
             #[tokio::test]
             async fn $name:ident(
@@ -891,7 +1465,21 @@ mod table_keys_order {
         ) => {
             test_format! {
                 #[tokio::test]
-                async fn _$name($source, Some($schema_path)) -> Ok($expected)
+                async fn _$name($source, Some($schema_path), Vec::new()) -> Ok($expected)
+            }
+        };
+
+        (
+            #[tokio::test]
+            async fn $name:ident(
+                $source:expr,
+                $schema_path:expr,
+                overrides = $overrides:expr$(,)?
+            ) -> Ok($expected:expr$(,)?)
+        ) => {
+            test_format! {
+                #[tokio::test]
+                async fn _$name($source, Some($schema_path), $overrides) -> Ok($expected)
             }
         };
 
@@ -903,7 +1491,7 @@ mod table_keys_order {
         ) => {
             test_format! {
                 #[tokio::test]
-                async fn _$name($source, Option::<&std::path::Path>::None) -> Ok($expected)
+                async fn _$name($source, Option::<&std::path::Path>::None, Vec::new()) -> Ok($expected)
             }
         };
 
@@ -911,7 +1499,8 @@ mod table_keys_order {
             #[tokio::test]
             async fn _$name:ident(
                 $source:expr,
-                $schema_path:expr$(,)?
+                $schema_path:expr,
+                $overrides:expr$(,)?
             ) -> Ok($expected:expr$(,)?)
         ) => {
             #[tokio::test]
@@ -940,6 +1529,10 @@ mod table_keys_order {
                         .await;
                 }
 
+                // Load overrides
+                let overrides: Vec<tombi_config::OverrideItem> = $overrides;
+                schema_store.load_overrides(&overrides).await;
+
                 // Initialize formatter
                 let format_options = FormatOptions::default();
                 let source_path = tombi_test_lib::project_root_path().join("test.toml");
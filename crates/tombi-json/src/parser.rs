@@ -373,6 +373,16 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// The range of the current token.
+    ///
+    /// Used to locate the error returned by [`Parser::parse`].
+    pub fn current_range(&self) -> Range {
+        self.peek()
+            .or_else(|| self.lexed.tokens.last())
+            .map(|token| token.range())
+            .unwrap_or_default()
+    }
+
     fn peek(&self) -> Option<&Token> {
         self.lexed.tokens.get(self.position)
     }
@@ -1,5 +1,39 @@
 #[derive(thiserror::Error, Debug)]
-pub enum ErrorKind {}
+pub enum ErrorKind {
+    #[error("{0}")]
+    JsonParseFailed(String),
+
+    #[error("JSON Schema must be an object")]
+    SchemaMustBeObject,
+
+    #[error("schema must be an object or a boolean")]
+    InvalidSchema,
+
+    #[error("\"{keyword}\" must be {expected}")]
+    InvalidKeywordValue { keyword: String, expected: String },
+
+    #[error("\"{keyword}\" must not be empty")]
+    EmptyKeywordValue { keyword: String },
+
+    #[error("\"{keyword}\" has duplicate value \"{value}\"")]
+    DuplicateKeywordValue { keyword: String, value: String },
+
+    #[error("invalid regular expression \"{pattern}\": {reason}")]
+    InvalidPattern { pattern: String, reason: String },
+
+    #[error("invalid \"{keyword}\" value \"{value}\", expected one of {expected}")]
+    InvalidEnumValue {
+        keyword: String,
+        value: String,
+        expected: String,
+    },
+
+    #[error("unresolved $ref \"{reference}\"")]
+    UnresolvedReference { reference: String },
+
+    #[error("unresolved $ref \"{reference}\": {reason}")]
+    ReferenceFetchFailed { reference: String, reason: String },
+}
 
 #[derive(Debug)]
 pub struct Error {
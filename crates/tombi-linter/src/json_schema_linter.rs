@@ -0,0 +1,913 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use itertools::{Either, Itertools};
+use tombi_diagnostic::{Diagnostic, SetDiagnostics};
+use tombi_json::{ObjectNode, StringNode, ValueNode};
+use url::Url;
+
+/// The meta-schema of the JSON Schema files, including the `x-tombi-*` keywords.
+static META_SCHEMA: LazyLock<ValueNode> = LazyLock::new(|| {
+    tombi_json::parse(include_str!("../../../schemas/json-schema.schema.json"))
+        .expect("the meta-schema must be a valid JSON")
+});
+
+/// Linter for JSON Schema files.
+///
+/// Validates the keywords against the JSON Schema meta-schema,
+/// and reports unresolved `$ref`s and invalid `x-tombi-*` keywords.
+pub struct JsonSchemaLinter<'a> {
+    source_url_or_path: Option<Either<&'a Url, &'a std::path::Path>>,
+    schema_store: &'a tombi_schema_store::SchemaStore,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> JsonSchemaLinter<'a> {
+    pub fn new(
+        source_url_or_path: Option<Either<&'a Url, &'a std::path::Path>>,
+        schema_store: &'a tombi_schema_store::SchemaStore,
+    ) -> Self {
+        Self {
+            source_url_or_path,
+            schema_store,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub async fn lint(mut self, source: &str) -> Result<(), Vec<Diagnostic>> {
+        let mut parser = tombi_json::Parser::new(source);
+        let root = match parser.parse() {
+            Ok(root) => root,
+            Err(err) => {
+                crate::Error {
+                    kind: crate::ErrorKind::JsonParseFailed(err.to_string()),
+                    range: parser.current_range(),
+                }
+                .set_diagnostics(&mut self.diagnostics);
+                return Err(self.diagnostics);
+            }
+        };
+
+        let ValueNode::Object(object) = &root else {
+            crate::Error {
+                kind: crate::ErrorKind::SchemaMustBeObject,
+                range: root.range(),
+            }
+            .set_diagnostics(&mut self.diagnostics);
+            return Err(self.diagnostics);
+        };
+
+        self.diagnostics
+            .extend(tombi_schema_store::lint_schema_dialect(object));
+
+        let mut references = Vec::new();
+        lint_schema_object(object, &mut self.diagnostics, &mut references);
+        self.lint_references(&root, references).await;
+
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            self.diagnostics
+                .sort_by_key(|diagnostic| diagnostic.range());
+            Err(self.diagnostics)
+        }
+    }
+
+    async fn lint_references(&mut self, root: &ValueNode, references: Vec<&StringNode>) {
+        let base_url = match self.source_url_or_path {
+            Some(Either::Left(url)) => Some(url.clone()),
+            Some(Either::Right(path)) => std::path::absolute(path)
+                .ok()
+                .and_then(|path| tombi_url::url_from_file_path(&path).ok()),
+            None => None,
+        };
+        let mut documents = HashMap::<Url, Result<Option<ValueNode>, String>>::new();
+
+        for reference in references {
+            let (document, fragment) = match reference.value.split_once('#') {
+                Some((document, fragment)) => (document, fragment),
+                None => (reference.value.as_str(), ""),
+            };
+
+            if document.is_empty() {
+                if !is_resolvable_fragment(root, fragment) {
+                    crate::Error {
+                        kind: crate::ErrorKind::UnresolvedReference {
+                            reference: reference.value.clone(),
+                        },
+                        range: reference.range,
+                    }
+                    .set_diagnostics(&mut self.diagnostics);
+                }
+                continue;
+            }
+
+            let document_url = match &base_url {
+                Some(base_url) => base_url.join(document),
+                None => Url::parse(document),
+            };
+            let Ok(document_url) = document_url else {
+                // Relative references can not be resolved without the source url.
+                if base_url.is_some() {
+                    crate::Error {
+                        kind: crate::ErrorKind::UnresolvedReference {
+                            reference: reference.value.clone(),
+                        },
+                        range: reference.range,
+                    }
+                    .set_diagnostics(&mut self.diagnostics);
+                }
+                continue;
+            };
+
+            if !documents.contains_key(&document_url) {
+                let document = self
+                    .schema_store
+                    .try_get_schema_value(&tombi_schema_store::SchemaUrl::new(document_url.clone()))
+                    .await
+                    .map_err(|err| err.to_string());
+                documents.insert(document_url.clone(), document);
+            }
+
+            match &documents[&document_url] {
+                Ok(Some(document)) => {
+                    if !is_resolvable_fragment(document, fragment) {
+                        crate::Error {
+                            kind: crate::ErrorKind::UnresolvedReference {
+                                reference: reference.value.clone(),
+                            },
+                            range: reference.range,
+                        }
+                        .set_diagnostics(&mut self.diagnostics);
+                    }
+                }
+                // Offline, and the schema is not cached.
+                Ok(None) => {}
+                Err(reason) => {
+                    crate::Error {
+                        kind: crate::ErrorKind::ReferenceFetchFailed {
+                            reference: reference.value.clone(),
+                            reason: reason.clone(),
+                        },
+                        range: reference.range,
+                    }
+                    .set_diagnostics(&mut self.diagnostics);
+                }
+            }
+        }
+    }
+}
+
+/// Whether the JSON file is linted as a JSON Schema.
+///
+/// Like the editor extensions, `*.schema.json` files are JSON Schemas,
+/// and the other JSON files only if they have the `$schema` keyword.
+pub fn is_json_schema_file(path: &std::path::Path, source: &str) -> bool {
+    if path
+        .file_name()
+        .is_some_and(|file_name| file_name.to_string_lossy().ends_with(".schema.json"))
+    {
+        return true;
+    }
+
+    path.extension()
+        .is_some_and(|extension| extension == "json")
+        && tombi_json::parse(source).is_ok_and(|value| {
+            value
+                .as_object()
+                .is_some_and(|object| object.get("$schema").is_some())
+        })
+}
+
+fn lint_schema<'a>(
+    value: &'a ValueNode,
+    diagnostics: &mut Vec<Diagnostic>,
+    references: &mut Vec<&'a StringNode>,
+) {
+    match value {
+        ValueNode::Object(object) => lint_schema_object(object, diagnostics, references),
+        ValueNode::Bool(_) => {}
+        _ => crate::Error {
+            kind: crate::ErrorKind::InvalidSchema,
+            range: value.range(),
+        }
+        .set_diagnostics(diagnostics),
+    }
+}
+
+fn lint_schema_object<'a>(
+    object: &'a ObjectNode,
+    diagnostics: &mut Vec<Diagnostic>,
+    references: &mut Vec<&'a StringNode>,
+) {
+    let keywords = META_SCHEMA
+        .as_object()
+        .and_then(|meta_schema| meta_schema.get("properties"))
+        .and_then(ValueNode::as_object);
+
+    for (key, value) in object.properties.iter() {
+        let keyword = key.value.as_str();
+        if let ("$ref", ValueNode::String(reference)) = (keyword, value) {
+            references.push(reference);
+        }
+
+        match keywords.and_then(|keywords| keywords.get(keyword)) {
+            Some(meta_schema) => lint_keyword_value(
+                keyword,
+                value,
+                meta_schema,
+                meta_schema,
+                diagnostics,
+                references,
+            ),
+            None if keyword.starts_with("x-tombi-") => crate::Warning {
+                kind: crate::WarningKind::UnknownXTombiKeyword(keyword.to_string()),
+                range: key.range,
+            }
+            .set_diagnostics(diagnostics),
+            None => {}
+        }
+    }
+}
+
+/// Validate the value of the keyword against the sub-schema of the meta-schema.
+///
+/// `expected_meta_schema` is the sub-schema described in the error message,
+/// so that an invalid item is reported as an invalid value of the whole array.
+fn lint_keyword_value<'a>(
+    keyword: &str,
+    value: &'a ValueNode,
+    meta_schema: &ValueNode,
+    expected_meta_schema: &ValueNode,
+    diagnostics: &mut Vec<Diagnostic>,
+    references: &mut Vec<&'a StringNode>,
+) {
+    let ValueNode::Object(meta_object) = meta_schema else {
+        return;
+    };
+
+    if let Some(reference) = meta_object.get("$ref").and_then(ValueNode::as_str) {
+        match resolve_meta_schema_reference(reference) {
+            Some(meta_schema) => lint_keyword_value(
+                keyword,
+                value,
+                meta_schema,
+                expected_meta_schema,
+                diagnostics,
+                references,
+            ),
+            // The value is a schema itself.
+            None => lint_schema(value, diagnostics, references),
+        }
+        return;
+    }
+
+    if let Some(branches) = meta_object.get("anyOf").and_then(ValueNode::as_array) {
+        match branches
+            .items
+            .iter()
+            .find(|branch| matches_type(value, branch))
+        {
+            Some(branch) => lint_keyword_value(
+                keyword,
+                value,
+                branch,
+                expected_meta_schema,
+                diagnostics,
+                references,
+            ),
+            None => invalid_keyword_value(keyword, expected_meta_schema, value, diagnostics),
+        }
+        return;
+    }
+
+    if !matches_type(value, meta_schema) {
+        invalid_keyword_value(keyword, expected_meta_schema, value, diagnostics);
+        return;
+    }
+
+    if let Some(values) = meta_object.get("enum").and_then(ValueNode::as_array) {
+        let value_string = value.to_string();
+        if !values
+            .items
+            .iter()
+            .any(|enum_value| enum_value.to_string() == value_string)
+        {
+            crate::Error {
+                kind: crate::ErrorKind::InvalidEnumValue {
+                    keyword: keyword.to_string(),
+                    value: match value {
+                        ValueNode::String(string) => string.value.clone(),
+                        _ => value_string,
+                    },
+                    expected: values.items.iter().join(", "),
+                },
+                range: value.range(),
+            }
+            .set_diagnostics(diagnostics);
+        }
+        return;
+    }
+
+    if let Some(number) = value.as_f64() {
+        let below_minimum = meta_object
+            .get("minimum")
+            .and_then(ValueNode::as_f64)
+            .is_some_and(|minimum| number < minimum);
+        let below_exclusive_minimum = meta_object
+            .get("exclusiveMinimum")
+            .and_then(ValueNode::as_f64)
+            .is_some_and(|minimum| number <= minimum);
+        if below_minimum || below_exclusive_minimum {
+            invalid_keyword_value(keyword, expected_meta_schema, value, diagnostics);
+        }
+    }
+
+    match value {
+        ValueNode::String(string) => lint_format(meta_object, string, diagnostics),
+        ValueNode::Array(array) => {
+            if meta_object
+                .get("minItems")
+                .and_then(ValueNode::as_u64)
+                .is_some_and(|min_items| (array.len() as u64) < min_items)
+            {
+                if array.is_empty() {
+                    crate::Error {
+                        kind: crate::ErrorKind::EmptyKeywordValue {
+                            keyword: keyword.to_string(),
+                        },
+                        range: value.range(),
+                    }
+                    .set_diagnostics(diagnostics);
+                } else {
+                    invalid_keyword_value(keyword, expected_meta_schema, value, diagnostics);
+                }
+            }
+
+            if let Some(items) = meta_object.get("items") {
+                for item in array.items.iter() {
+                    lint_keyword_value(keyword, item, items, meta_schema, diagnostics, references);
+                }
+            }
+
+            if meta_object.get("uniqueItems").and_then(ValueNode::as_bool) == Some(true) {
+                for (index, item) in array.items.iter().enumerate() {
+                    let item_string = item.to_string();
+                    if array.items[..index]
+                        .iter()
+                        .any(|other| other.to_string() == item_string)
+                    {
+                        crate::Error {
+                            kind: crate::ErrorKind::DuplicateKeywordValue {
+                                keyword: keyword.to_string(),
+                                value: match item {
+                                    ValueNode::String(string) => string.value.clone(),
+                                    _ => item_string,
+                                },
+                            },
+                            range: item.range(),
+                        }
+                        .set_diagnostics(diagnostics);
+                    }
+                }
+            }
+        }
+        ValueNode::Object(object) => {
+            let property_names = meta_object
+                .get("propertyNames")
+                .and_then(ValueNode::as_object);
+            let additional_properties = meta_object.get("additionalProperties");
+            for (key, property) in object.properties.iter() {
+                if let Some(property_names) = property_names {
+                    lint_format(property_names, key, diagnostics);
+                }
+                if let Some(additional_properties) = additional_properties {
+                    lint_keyword_value(
+                        keyword,
+                        property,
+                        additional_properties,
+                        additional_properties,
+                        diagnostics,
+                        references,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn invalid_keyword_value(
+    keyword: &str,
+    expected_meta_schema: &ValueNode,
+    value: &ValueNode,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    crate::Error {
+        kind: crate::ErrorKind::InvalidKeywordValue {
+            keyword: keyword.to_string(),
+            expected: expected_value(expected_meta_schema),
+        },
+        range: value.range(),
+    }
+    .set_diagnostics(diagnostics);
+}
+
+/// Resolve the `$ref` in the meta-schema.
+///
+/// Returns `None` for the root `#`, that is, the value must be a schema.
+fn resolve_meta_schema_reference(reference: &str) -> Option<&'static ValueNode> {
+    match reference.strip_prefix('#') {
+        Some("") | None => None,
+        Some(fragment) => resolve_fragment(&META_SCHEMA, fragment),
+    }
+}
+
+/// Check the JSON type of the value against the sub-schema of the meta-schema.
+///
+/// `enum` without `type` accepts the types of its values.
+fn matches_type(value: &ValueNode, meta_schema: &ValueNode) -> bool {
+    let ValueNode::Object(meta_object) = meta_schema else {
+        return true;
+    };
+
+    if let Some(reference) = meta_object.get("$ref").and_then(ValueNode::as_str) {
+        return match resolve_meta_schema_reference(reference) {
+            Some(meta_schema) => matches_type(value, meta_schema),
+            None => value.is_object() || value.is_bool(),
+        };
+    }
+
+    if let Some(branches) = meta_object.get("anyOf").and_then(ValueNode::as_array) {
+        return branches
+            .items
+            .iter()
+            .any(|branch| matches_type(value, branch));
+    }
+
+    match meta_object.get("type") {
+        Some(ValueNode::String(type_name)) => is_type(value, &type_name.value),
+        Some(ValueNode::Array(type_names)) => type_names
+            .items
+            .iter()
+            .filter_map(ValueNode::as_str)
+            .any(|type_name| is_type(value, type_name)),
+        _ => match meta_object.get("enum").and_then(ValueNode::as_array) {
+            Some(values) => values
+                .items
+                .iter()
+                .any(|enum_value| json_type(enum_value) == json_type(value)),
+            None => true,
+        },
+    }
+}
+
+fn is_type(value: &ValueNode, type_name: &str) -> bool {
+    match type_name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        _ => json_type(value) == type_name,
+    }
+}
+
+fn json_type(value: &ValueNode) -> &'static str {
+    match value {
+        ValueNode::Null(_) => "null",
+        ValueNode::Bool(_) => "boolean",
+        ValueNode::Number(_) => "number",
+        ValueNode::String(_) => "string",
+        ValueNode::Array(_) => "array",
+        ValueNode::Object(_) => "object",
+    }
+}
+
+/// Describe the values accepted by the sub-schema of the meta-schema,
+/// e.g. `a non-negative integer`.
+fn expected_value(meta_schema: &ValueNode) -> String {
+    let ValueNode::Object(meta_object) = meta_schema else {
+        return "any value".to_string();
+    };
+
+    if let Some(reference) = meta_object.get("$ref").and_then(ValueNode::as_str) {
+        return match resolve_meta_schema_reference(reference) {
+            Some(meta_schema) => expected_value(meta_schema),
+            None => "a schema".to_string(),
+        };
+    }
+
+    if let Some(branches) = meta_object.get("anyOf").and_then(ValueNode::as_array) {
+        return branches
+            .items
+            .iter()
+            .map(expected_value)
+            .unique()
+            .join(" or ");
+    }
+
+    let type_names = match meta_object.get("type") {
+        Some(ValueNode::String(type_name)) => vec![type_name.value.as_str()],
+        Some(ValueNode::Array(type_names)) => type_names
+            .items
+            .iter()
+            .filter_map(ValueNode::as_str)
+            .collect(),
+        _ => match meta_object.get("enum").and_then(ValueNode::as_array) {
+            Some(values) => values.items.iter().map(json_type).unique().collect(),
+            None => return "any value".to_string(),
+        },
+    };
+
+    type_names
+        .into_iter()
+        .map(|type_name| match type_name {
+            "integer" => match meta_object.get("minimum").and_then(ValueNode::as_i64) {
+                Some(0) => "a non-negative integer".to_string(),
+                Some(minimum) => format!("an integer greater than or equal to {minimum}"),
+                None => "an integer".to_string(),
+            },
+            "number" => match meta_object.get("exclusiveMinimum") {
+                Some(ValueNode::Number(minimum)) => format!("a number greater than {minimum}"),
+                _ => "a number".to_string(),
+            },
+            "array" => match meta_object.get("items") {
+                Some(items) => format!(
+                    "an array of {}",
+                    expected_value(items)
+                        .split(" or ")
+                        .map(|expected| {
+                            let expected = expected
+                                .strip_prefix("an ")
+                                .or_else(|| expected.strip_prefix("a "))
+                                .unwrap_or(expected);
+                            format!("{expected}s")
+                        })
+                        .join(" or ")
+                ),
+                None => "an array".to_string(),
+            },
+            "object" => "an object".to_string(),
+            "null" => "null".to_string(),
+            type_name => format!("a {type_name}"),
+        })
+        .join(" or ")
+}
+
+fn lint_format(meta_object: &ObjectNode, value: &StringNode, diagnostics: &mut Vec<Diagnostic>) {
+    if meta_object.get("format").and_then(ValueNode::as_str) == Some("regex") {
+        lint_pattern(value, diagnostics);
+    }
+}
+
+fn lint_pattern(pattern: &StringNode, diagnostics: &mut Vec<Diagnostic>) {
+    // JSON Schema uses ECMA-262 regular expressions,
+    // so look-around and backreferences are valid even if `regex` does not support them.
+    if let Err(err) = regex_syntax::ast::parse::Parser::new().parse(&pattern.value) {
+        if matches!(
+            err.kind(),
+            regex_syntax::ast::ErrorKind::UnsupportedLookAround
+                | regex_syntax::ast::ErrorKind::UnsupportedBackreference
+        ) {
+            return;
+        }
+    }
+
+    if let Err(err) = regex::Regex::new(&pattern.value) {
+        crate::Error {
+            kind: crate::ErrorKind::InvalidPattern {
+                pattern: pattern.value.clone(),
+                reason: err.to_string(),
+            },
+            range: pattern.range,
+        }
+        .set_diagnostics(diagnostics);
+    }
+}
+
+/// Check that the fragment of `$ref` points to a value in the document.
+///
+/// The fragment is a JSON pointer (`/definitions/foo`) or a plain name anchor (`foo`).
+fn is_resolvable_fragment(document: &ValueNode, fragment: &str) -> bool {
+    if fragment.is_empty() {
+        return true;
+    }
+
+    match fragment.strip_prefix('/') {
+        Some(_) => resolve_fragment(document, fragment).is_some(),
+        None => has_anchor(document, fragment),
+    }
+}
+
+/// Resolve the JSON pointer fragment (`/definitions/foo`) in the document.
+fn resolve_fragment<'a>(document: &'a ValueNode, fragment: &str) -> Option<&'a ValueNode> {
+    let mut value = document;
+    for token in fragment.strip_prefix('/')?.split('/') {
+        let token = percent_decode(token).replace("~1", "/").replace("~0", "~");
+        value = match value {
+            ValueNode::Object(object) => object.get(&token),
+            ValueNode::Array(array) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| array.items.get(index)),
+            _ => None,
+        }?;
+    }
+
+    Some(value)
+}
+
+fn has_anchor(value: &ValueNode, anchor: &str) -> bool {
+    match value {
+        ValueNode::Object(object) => {
+            object.get("$anchor").and_then(|value| value.as_str()) == Some(anchor)
+                || object
+                    .get("$id")
+                    .and_then(|value| value.as_str())
+                    .and_then(|id| id.strip_prefix('#'))
+                    == Some(anchor)
+                || object
+                    .properties
+                    .values()
+                    .any(|value| has_anchor(value, anchor))
+        }
+        ValueNode::Array(array) => array.items.iter().any(|value| has_anchor(value, anchor)),
+        _ => false,
+    }
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' && index + 2 < bytes.len() {
+            if let Some(byte) = std::str::from_utf8(&bytes[index + 1..index + 3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[index]);
+        index += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn lint(source: &str) -> Vec<String> {
+        let schema_store = tombi_schema_store::SchemaStore::new();
+        match JsonSchemaLinter::new(None, &schema_store)
+            .lint(source)
+            .await
+        {
+            Ok(()) => Vec::with_capacity(0),
+            Err(diagnostics) => diagnostics
+                .into_iter()
+                .map(|diagnostic| diagnostic.message().to_string())
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_schema() {
+        pretty_assertions::assert_eq!(
+            lint(
+                r##"{
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "type": "object",
+                    "x-tombi-table-keys-order": "schema",
+                    "properties": {
+                        "name": { "type": ["string", "null"] },
+                        "items": { "type": "array", "items": { "$ref": "#/definitions/item" } }
+                    },
+                    "required": ["name"],
+                    "definitions": {
+                        "item": { "type": "integer", "minimum": 0 }
+                    }
+                }"##
+            )
+            .await,
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_keywords() {
+        pretty_assertions::assert_eq!(
+            lint(
+                r##"{
+                    "type": "object",
+                    "properties": {
+                        "a": { "type": "text" },
+                        "b": { "type": "array", "minItems": -1 },
+                        "c": { "type": "string", "pattern": "(" }
+                    },
+                    "required": ["a", "a"]
+                }"##
+            )
+            .await,
+            vec![
+                "invalid \"type\" value \"text\", expected one of \"array\", \"boolean\", \"integer\", \"null\", \"number\", \"object\", \"string\"".to_string(),
+                "\"minItems\" must be a non-negative integer".to_string(),
+                "invalid regular expression \"(\": regex parse error:\n    (\n    ^\nerror: unclosed group".to_string(),
+                "\"required\" has duplicate value \"a\"".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn ecma_regex_patterns() {
+        pretty_assertions::assert_eq!(
+            lint(
+                r##"{
+                    "type": "object",
+                    "properties": {
+                        "a": { "type": "string", "pattern": "^(?!_).*$" },
+                        "b": { "type": "string", "pattern": "^(a)\\1$" }
+                    },
+                    "patternProperties": {
+                        "^(?=x)": { "type": "string" }
+                    }
+                }"##
+            )
+            .await,
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_dialect_keywords() {
+        pretty_assertions::assert_eq!(
+            lint(
+                r##"{
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "type": "object",
+                    "$defs": {}
+                }"##
+            )
+            .await,
+            vec!["\"$defs\" is not supported in JSON Schema draft-07".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn unresolved_references() {
+        pretty_assertions::assert_eq!(
+            lint(
+                r##"{
+                    "type": "object",
+                    "properties": {
+                        "a": { "$ref": "#/definitions/a" },
+                        "b": { "$ref": "#/definitions/b" },
+                        "c": { "$ref": "#c" }
+                    },
+                    "definitions": {
+                        "a": { "type": "string", "$anchor": "c" }
+                    }
+                }"##
+            )
+            .await,
+            vec!["unresolved $ref \"#/definitions/b\"".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn x_tombi_keywords() {
+        pretty_assertions::assert_eq!(
+            lint(
+                r##"{
+                    "type": "object",
+                    "x-tombi-toml-version": "v2.0.0",
+                    "x-tombi-table-keys-order": "random",
+                    "x-tombi-unknown": true
+                }"##
+            )
+            .await,
+            vec![
+                "invalid \"x-tombi-toml-version\" value \"v2.0.0\", expected one of \"v1.0.0\", \"v1.1.0-preview\"".to_string(),
+                "invalid \"x-tombi-table-keys-order\" value \"random\", expected one of \"ascending\", \"descending\", \"schema\", \"version-sort\"".to_string(),
+                "unknown keyword \"x-tombi-unknown\"".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_keyword_types() {
+        pretty_assertions::assert_eq!(
+            lint(
+                r##"{
+                    "type": ["string", 1],
+                    "items": 1,
+                    "multipleOf": 0,
+                    "anyOf": [],
+                    "x-tombi-array-values-order-by": ["name", true]
+                }"##
+            )
+            .await,
+            vec![
+                "\"type\" must be an array of strings".to_string(),
+                "\"items\" must be a schema or an array of schemas".to_string(),
+                "\"multipleOf\" must be a number greater than 0".to_string(),
+                "\"anyOf\" must not be empty".to_string(),
+                "\"x-tombi-array-values-order-by\" must be an array of strings".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_schema_files() {
+        use std::path::Path;
+
+        assert!(is_json_schema_file(
+            Path::new("my-config.schema.json"),
+            "{}"
+        ));
+        assert!(is_json_schema_file(
+            Path::new("my-config.json"),
+            r#"{"$schema": "http://json-schema.org/draft-07/schema#"}"#
+        ));
+        assert!(!is_json_schema_file(
+            Path::new("package.json"),
+            r#"{"name": "tombi"}"#
+        ));
+        assert!(!is_json_schema_file(
+            Path::new("pyproject.toml"),
+            r#"{"$schema": "http://json-schema.org/draft-07/schema#"}"#
+        ));
+    }
+
+    #[test]
+    fn meta_schema_x_tombi_values() {
+        let keywords = META_SCHEMA
+            .as_object()
+            .and_then(|meta_schema| meta_schema.get("properties"))
+            .and_then(ValueNode::as_object)
+            .unwrap();
+        let enum_values = |keyword: &str| -> Vec<String> {
+            keywords
+                .get(keyword)
+                .and_then(ValueNode::as_object)
+                .and_then(|meta_schema| meta_schema.get("enum"))
+                .and_then(ValueNode::as_array)
+                .unwrap()
+                .items
+                .iter()
+                .filter_map(|value| value.as_str().map(ToString::to_string))
+                .collect()
+        };
+
+        pretty_assertions::assert_eq!(
+            enum_values(tombi_x_keyword::X_TOMBI_TOML_VERSION),
+            [
+                tombi_config::TomlVersion::V1_0_0,
+                tombi_config::TomlVersion::V1_1_0_Preview
+            ]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+        );
+        for value in enum_values(tombi_x_keyword::X_TOMBI_TABLE_KEYS_ORDER) {
+            assert!(tombi_x_keyword::TableKeysOrder::try_from(value.as_str()).is_ok());
+        }
+        for value in enum_values(tombi_x_keyword::X_TOMBI_TABLE_STYLE) {
+            assert!(tombi_x_keyword::TableStyle::try_from(value.as_str()).is_ok());
+        }
+        for value in enum_values(tombi_x_keyword::X_TOMBI_ARRAY_VALUES_ORDER) {
+            assert!(tombi_x_keyword::ArrayValuesOrder::try_from(value.as_str()).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn repository_schemas() {
+        let schema_store =
+            tombi_schema_store::SchemaStore::new_with_options(tombi_schema_store::Options {
+                offline: Some(true),
+                ..Default::default()
+            });
+        let schemas_dir = tombi_test_lib::project_root_path().join("schemas");
+
+        for entry in std::fs::read_dir(&schemas_dir).unwrap() {
+            let path = entry.unwrap().path();
+            if !path.to_string_lossy().ends_with(".schema.json") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).unwrap();
+            let errors = match JsonSchemaLinter::new(Some(Either::Right(&path)), &schema_store)
+                .lint(&source)
+                .await
+            {
+                Ok(()) => Vec::with_capacity(0),
+                Err(diagnostics) => diagnostics
+                    .into_iter()
+                    .filter(|diagnostic| diagnostic.level() == tombi_diagnostic::Level::ERROR)
+                    .map(|diagnostic| diagnostic.message().to_string())
+                    .collect(),
+            };
+            pretty_assertions::assert_eq!(errors, Vec::<String>::new(), "{}", path.display());
+        }
+    }
+}
@@ -1,16 +1,20 @@
 mod error;
+mod json_schema_linter;
 mod lint;
 mod linter;
 mod rule;
 mod severity;
+mod warning;
 
 pub use error::{Error, ErrorKind};
+pub use json_schema_linter::{is_json_schema_file, JsonSchemaLinter};
 use lint::Lint;
 pub use linter::Linter;
 use rule::Rule;
 pub use severity::{Severity, SeverityKind};
 pub use tombi_config::LintOptions;
 use tombi_diagnostic::Diagnostic;
+pub use warning::{Warning, WarningKind};
 
 #[cfg(test)]
 #[macro_export]
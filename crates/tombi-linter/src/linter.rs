@@ -80,6 +80,7 @@ impl<'a> Linter<'a> {
                     toml_version,
                     root_schema: source_schema.root_schema.as_ref(),
                     sub_schema_url_map: Some(&source_schema.sub_schema_url_map),
+                    order_overrides: None,
                     store: self.schema_store,
                 };
                 if let Err(schema_diagnostics) =
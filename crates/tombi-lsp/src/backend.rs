@@ -68,6 +68,7 @@ impl Backend {
         let options = tombi_schema_store::Options {
             offline: options.offline,
             strict: config.schema.as_ref().and_then(|schema| schema.strict()),
+            cache: tombi_schema_store::CacheOptions::from_schema_options(config.schema.as_ref()),
         };
 
         Self {
@@ -92,6 +93,9 @@ impl Backend {
                 return None;
             }
         };
+        if document_source.is_json() {
+            return None;
+        }
 
         let source_schema = if let Some(parsed) =
             tombi_parser::parse_document_header_comments(&document_source.text)
@@ -1,5 +1,14 @@
+use itertools::Itertools;
+use tombi_ast::AstNode;
+use tombi_ast_editor::{
+    apply_changes, array_values_unique,
+    text::{key_value_text, keys_text, leading_comments_text, tailing_comment_text},
+};
+use tombi_config::TomlVersion;
 use tombi_document_tree::TableKind;
-use tombi_schema_store::{dig_accessors, Accessor, AccessorContext, AccessorKeyKind};
+use tombi_schema_store::{
+    dig_accessors, Accessor, AccessorContext, AccessorKeyKind, ArraySchema, SchemaContext,
+};
 use tower_lsp::lsp_types::{
     CodeAction, CodeActionKind, DocumentChanges, OneOf, OptionalVersionedTextDocumentIdentifier,
     TextDocumentEdit, TextDocumentIdentifier, TextEdit, WorkspaceEdit,
@@ -8,6 +17,8 @@ use tower_lsp::lsp_types::{
 pub enum CodeActionRefactorRewriteName {
     DottedKeysToInlineTable,
     InlineTableToDottedKeys,
+    TableToInlineTable,
+    InlineTableToTable,
 }
 
 impl std::fmt::Display for CodeActionRefactorRewriteName {
@@ -19,6 +30,26 @@ impl std::fmt::Display for CodeActionRefactorRewriteName {
             CodeActionRefactorRewriteName::InlineTableToDottedKeys => {
                 write!(f, "Convert Inline Table to Dotted Keys")
             }
+            CodeActionRefactorRewriteName::TableToInlineTable => {
+                write!(f, "Convert Table to Inline Table")
+            }
+            CodeActionRefactorRewriteName::InlineTableToTable => {
+                write!(f, "Convert Inline Table to Table")
+            }
+        }
+    }
+}
+
+pub enum CodeActionQuickFixName {
+    RemoveDuplicateValues,
+}
+
+impl std::fmt::Display for CodeActionQuickFixName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodeActionQuickFixName::RemoveDuplicateValues => {
+                write!(f, "Remove Duplicate Values")
+            }
         }
     }
 }
@@ -163,3 +194,380 @@ pub fn inline_table_to_dot_keys_code_action(
         _ => None,
     }
 }
+
+/// Converts `[parent.key]` at the cursor into `key = { ... }` of the parent table.
+pub fn table_to_inline_table_code_action(
+    text_document: &TextDocumentIdentifier,
+    root: &tombi_ast::Root,
+    position: tombi_text::Position,
+    toml_version: TomlVersion,
+) -> Option<CodeAction> {
+    let items = header_keys_and_items(root, toml_version);
+    let (index, keys, table) =
+        items
+            .iter()
+            .enumerate()
+            .find_map(|(index, (keys, item))| match item {
+                tombi_ast::TableOrArrayOfTable::Table(table) if table.contains_header(position) => {
+                    Some((index, keys, table))
+                }
+                _ => None,
+            })?;
+
+    // NOTE: Subtables cannot be written in the inline table.
+    if items
+        .iter()
+        .any(|(other_keys, _)| other_keys.len() > keys.len() && other_keys.starts_with(keys))
+    {
+        return None;
+    }
+    if !table.key_values_begin_dangling_comments().is_empty()
+        || !table.key_values_dangling_comments().is_empty()
+        || !table.key_values_end_dangling_comments().is_empty()
+        || table.key_values().any(|key_value| {
+            key_value.leading_comments().next().is_some() || key_value.tailing_comment().is_some()
+        })
+    {
+        return None;
+    }
+
+    let key_text = table.header()?.keys().last()?.syntax().to_string();
+    let inner = table
+        .key_values()
+        .map(|key_value| key_value_text(&key_value))
+        .collect::<Option<Vec<_>>>()?;
+    let key_value_text = format!(
+        "{}{key_text} = {}{}",
+        leading_comments_text(table.header_leading_comments()),
+        if inner.is_empty() {
+            "{}".to_string()
+        } else {
+            format!("{{ {} }}", inner.join(", "))
+        },
+        tailing_comment_text(table.header_tailing_comment()),
+    );
+
+    let prev_end = table
+        .syntax()
+        .prev_sibling()
+        .map(|prev| significant_end(&prev));
+    let delete_range = tombi_text::Range::new(
+        prev_end.unwrap_or(table.range().start),
+        significant_end(table.syntax()),
+    );
+
+    let parent_keys = &keys[..keys.len() - 1];
+    let edits = if parent_keys.is_empty() {
+        match (root.key_values().last(), prev_end) {
+            (Some(key_value), _) => insert_and_delete_edits(
+                significant_end(key_value.syntax()),
+                format!("\n{key_value_text}"),
+                delete_range,
+            ),
+            (None, Some(_)) => insert_and_delete_edits(
+                items.first()?.1.range().start,
+                format!("{key_value_text}\n\n"),
+                delete_range,
+            ),
+            (None, None) => vec![TextEdit {
+                range: delete_range.into(),
+                new_text: key_value_text,
+            }],
+        }
+    } else {
+        let (_, parent) = items[..index]
+            .iter()
+            .rev()
+            .find(|(other_keys, _)| other_keys == parent_keys)
+            .or_else(|| {
+                items[index + 1..].iter().find(|(other_keys, other)| {
+                    other_keys == parent_keys
+                        && matches!(other, tombi_ast::TableOrArrayOfTable::Table(_))
+                })
+            })?;
+        insert_and_delete_edits(
+            body_end(parent)?,
+            format!("\n{key_value_text}"),
+            delete_range,
+        )
+    };
+
+    Some(refactor_rewrite_code_action(
+        text_document,
+        CodeActionRefactorRewriteName::TableToInlineTable,
+        edits,
+    ))
+}
+
+/// Converts `key = { ... }` at the cursor into `[parent.key]`.
+pub fn inline_table_to_table_code_action(
+    text_document: &TextDocumentIdentifier,
+    root: &tombi_ast::Root,
+    position: tombi_text::Position,
+    toml_version: TomlVersion,
+) -> Option<CodeAction> {
+    let items = header_keys_and_items(root, toml_version);
+    let (parent, key_value) = root
+        .key_values()
+        .find(|key_value| key_value.range().contains(position))
+        .map(|key_value| (None, key_value))
+        .or_else(|| {
+            items.iter().find_map(|(_, item)| {
+                let key_values = match item {
+                    tombi_ast::TableOrArrayOfTable::Table(table) => table.key_values(),
+                    tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => {
+                        array_of_table.key_values()
+                    }
+                };
+                key_values
+                    .into_iter()
+                    .find(|key_value| key_value.range().contains(position))
+                    .map(|key_value| (Some(item), key_value))
+            })
+        })?;
+
+    let Some(tombi_ast::Value::InlineTable(inline_table)) = key_value.value() else {
+        return None;
+    };
+    if inline_table.has_inner_comments() {
+        return None;
+    }
+
+    let keys = key_value.keys()?;
+    let header = match parent {
+        Some(parent) => format!("{}.{}", keys_text(&parent.header()?), keys_text(&keys)),
+        None => keys_text(&keys),
+    };
+    let lines = inline_table
+        .key_values()
+        .map(|key_value| key_value_text(&key_value))
+        .collect::<Option<Vec<_>>>()?;
+    let table_text = format!(
+        "{}[{header}]{}{}",
+        leading_comments_text(key_value.leading_comments()),
+        tailing_comment_text(key_value.tailing_comment()),
+        lines
+            .iter()
+            .map(|line| format!("\n{line}"))
+            .collect::<String>(),
+    );
+
+    let prev_end = key_value
+        .syntax()
+        .prev_sibling()
+        .map(|prev| significant_end(&prev))
+        .or_else(|| parent.and_then(header_end));
+    let delete_range = match prev_end {
+        Some(prev_end) => tombi_text::Range::new(prev_end, significant_end(key_value.syntax())),
+        None => tombi_text::Range::new(
+            key_value.range().start,
+            key_value
+                .syntax()
+                .next_sibling()
+                .map(|next| next.range().start)
+                .unwrap_or_else(|| significant_end(key_value.syntax())),
+        ),
+    };
+
+    let edits = match (parent, items.first()) {
+        (Some(parent), _) => insert_and_delete_edits(
+            significant_end(parent.syntax()),
+            format!("\n\n{table_text}"),
+            delete_range,
+        ),
+        (None, Some((_, first_item))) => insert_and_delete_edits(
+            first_item.range().start,
+            format!("{table_text}\n\n"),
+            delete_range,
+        ),
+        (None, None) => insert_and_delete_edits(
+            significant_end(root.syntax()),
+            if prev_end.is_some() {
+                format!("\n\n{table_text}")
+            } else {
+                table_text
+            },
+            delete_range,
+        ),
+    };
+
+    Some(refactor_rewrite_code_action(
+        text_document,
+        CodeActionRefactorRewriteName::InlineTableToTable,
+        edits,
+    ))
+}
+
+/// Returns the innermost array containing the cursor.
+pub fn array_at_position(
+    root: &tombi_ast::Root,
+    position: tombi_text::Position,
+) -> Option<tombi_ast::Array> {
+    root.syntax()
+        .descendants()
+        .filter_map(tombi_ast::Array::cast)
+        .filter(|array| array.range().contains(position))
+        .last()
+}
+
+/// Removes the duplicate values of the array, keeping the first occurrence and its comments.
+pub fn remove_duplicate_values_code_action(
+    text_document: &TextDocumentIdentifier,
+    root: &tombi_ast::Root,
+    array: &tombi_ast::Array,
+    array_schema: &ArraySchema,
+    schema_context: &SchemaContext<'_>,
+) -> Option<CodeAction> {
+    let new_root = root.clone_for_update();
+    let new_array = new_root
+        .syntax()
+        .descendants()
+        .filter_map(tombi_ast::Array::cast)
+        .find(|new_array| new_array.syntax().span() == array.syntax().span())?;
+
+    let (_, changes) = array_values_unique(
+        new_array.values_with_comma().collect_vec(),
+        array_schema,
+        schema_context,
+    );
+    if changes.is_empty() {
+        return None;
+    }
+    apply_changes(&new_root, changes);
+
+    let edits = vec![TextEdit {
+        range: array.range().into(),
+        new_text: new_array.syntax().to_string(),
+    }];
+
+    Some(CodeAction {
+        title: CodeActionQuickFixName::RemoveDuplicateValues.to_string(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: None,
+            document_changes: Some(DocumentChanges::Edits(vec![TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier {
+                    uri: text_document.uri.clone(),
+                    version: None,
+                },
+                edits: edits.into_iter().map(OneOf::Left).collect_vec(),
+            }])),
+            change_annotations: None,
+        }),
+        ..Default::default()
+    })
+}
+
+fn refactor_rewrite_code_action(
+    text_document: &TextDocumentIdentifier,
+    name: CodeActionRefactorRewriteName,
+    edits: Vec<TextEdit>,
+) -> CodeAction {
+    CodeAction {
+        title: name.to_string(),
+        kind: Some(CodeActionKind::REFACTOR_REWRITE),
+        edit: Some(WorkspaceEdit {
+            changes: None,
+            document_changes: Some(DocumentChanges::Edits(vec![TextDocumentEdit {
+                text_document: OptionalVersionedTextDocumentIdentifier {
+                    uri: text_document.uri.clone(),
+                    version: None,
+                },
+                edits: edits.into_iter().map(OneOf::Left).collect_vec(),
+            }])),
+            change_annotations: None,
+        }),
+        ..Default::default()
+    }
+}
+
+/// Returns the edits to insert the text and delete the range.
+///
+/// If the insertion touches the deleted range, they are merged into a single edit,
+/// because the order of the touching edits is not defined in LSP.
+fn insert_and_delete_edits(
+    position: tombi_text::Position,
+    new_text: String,
+    delete_range: tombi_text::Range,
+) -> Vec<TextEdit> {
+    if position == delete_range.start || position == delete_range.end {
+        vec![TextEdit {
+            range: delete_range.into(),
+            new_text,
+        }]
+    } else {
+        vec![
+            TextEdit {
+                range: tombi_text::Range::at(position).into(),
+                new_text,
+            },
+            TextEdit {
+                range: delete_range.into(),
+                new_text: String::new(),
+            },
+        ]
+    }
+}
+
+fn header_keys_and_items(
+    root: &tombi_ast::Root,
+    toml_version: TomlVersion,
+) -> Vec<(Vec<String>, tombi_ast::TableOrArrayOfTable)> {
+    root.table_or_array_of_tables()
+        .filter_map(|item| {
+            let keys = item
+                .header()?
+                .keys()
+                .map(|key| key.try_to_raw_text(toml_version).ok())
+                .collect::<Option<Vec<_>>>()?;
+            Some((keys, item))
+        })
+        .collect_vec()
+}
+
+/// Returns the end of the header, including its tailing comment.
+fn header_end(item: &tombi_ast::TableOrArrayOfTable) -> Option<tombi_text::Position> {
+    match item {
+        tombi_ast::TableOrArrayOfTable::Table(table) => table
+            .header_tailing_comment()
+            .map(|comment| comment.syntax().range().end)
+            .or_else(|| table.bracket_end().map(|token| token.range().end)),
+        tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => array_of_table
+            .header_tailing_comment()
+            .map(|comment| comment.syntax().range().end)
+            .or_else(|| {
+                array_of_table
+                    .double_bracket_end()
+                    .map(|token| token.range().end)
+            }),
+    }
+}
+
+/// Returns the end of the last key-value, or the end of the header.
+fn body_end(item: &tombi_ast::TableOrArrayOfTable) -> Option<tombi_text::Position> {
+    let last_key_value = match item {
+        tombi_ast::TableOrArrayOfTable::Table(table) => table.key_values().last(),
+        tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => {
+            array_of_table.key_values().last()
+        }
+    };
+    match last_key_value {
+        Some(key_value) => Some(significant_end(key_value.syntax())),
+        None => header_end(item),
+    }
+}
+
+/// Returns the end of the node, ignoring the tailing whitespaces and line breaks.
+fn significant_end(node: &tombi_syntax::SyntaxNode) -> tombi_text::Position {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            !matches!(
+                token.kind(),
+                tombi_syntax::SyntaxKind::WHITESPACE | tombi_syntax::SyntaxKind::LINE_BREAK
+            )
+        })
+        .last()
+        .map(|token| token.range().end)
+        .unwrap_or_else(|| node.range().end)
+}
@@ -66,7 +66,7 @@ impl FindCompletionContents for tombi_document_tree::Array {
                             }
                             if value.range().contains(position) {
                                 let accessor = Accessor::Index(index);
-                                if let Some(items) = &array_schema.items {
+                                if let Some(items) = array_schema.item_schema(index) {
                                     if let Ok(Some(current_schema)) = items
                                         .write()
                                         .await
@@ -95,7 +95,7 @@ impl FindCompletionContents for tombi_document_tree::Array {
                                 }
                             }
                         }
-                        if let Some(items) = &array_schema.items {
+                        if let Some(items) = array_schema.item_schema(new_item_index) {
                             if let Ok(Some(current_schema)) = items
                                 .write()
                                 .await
@@ -2,13 +2,21 @@
 pub struct DocumentSource {
     pub text: String,
     pub version: i32,
+    pub language_id: String,
 }
 
 impl DocumentSource {
-    pub fn new(text: impl Into<String>, version: i32) -> Self {
+    pub fn new(text: impl Into<String>, version: i32, language_id: impl Into<String>) -> Self {
         Self {
             text: text.into(),
             version,
+            language_id: language_id.into(),
         }
     }
+
+    /// JSON documents are linted as JSON Schema, and only support diagnostics.
+    #[inline]
+    pub fn is_json(&self) -> bool {
+        self.language_id.eq_ignore_ascii_case("json")
+    }
 }
@@ -57,7 +57,7 @@ impl GetTypeDefinition for tombi_document_tree::Array {
                             if value.range().contains(position) {
                                 let accessor = Accessor::Index(index);
 
-                                if let Some(items) = &array_schema.items {
+                                if let Some(items) = array_schema.item_schema(index) {
                                     let mut referable_schema = items.write().await;
                                     if let Ok(Some(current_schema)) = referable_schema
                                         .resolve(
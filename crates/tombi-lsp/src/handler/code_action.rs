@@ -1,11 +1,19 @@
 use crate::{
-    code_action::{dot_keys_to_inline_table_code_action, inline_table_to_dot_keys_code_action},
+    code_action::{
+        array_at_position, dot_keys_to_inline_table_code_action,
+        inline_table_to_dot_keys_code_action, inline_table_to_table_code_action,
+        remove_duplicate_values_code_action, table_to_inline_table_code_action,
+    },
     Backend,
 };
+use std::borrow::Cow;
+
 use itertools::Either;
+use tombi_ast_editor::get_value_schema;
 use tombi_document_tree::TryIntoDocumentTree;
 use tombi_schema_store::{
-    build_accessor_contexts, get_accessors, get_completion_keys_with_context,
+    build_accessor_contexts, dig_accessors, get_accessors, get_completion_keys_with_context,
+    ArraySchema, CurrentSchema, SchemaContext, ValueSchema,
 };
 use tower_lsp::lsp_types::{CodeActionOrCommand, CodeActionParams};
 
@@ -55,7 +63,7 @@ pub async fn handle_code_action(
         return Ok(None);
     };
 
-    let Ok(document_tree) = root.try_into_document_tree(toml_version) else {
+    let Ok(document_tree) = root.clone().try_into_document_tree(toml_version) else {
         return Ok(None);
     };
 
@@ -82,6 +90,40 @@ pub async fn handle_code_action(
         code_actions.push(code_action.into());
     }
 
+    if let Some(code_action) =
+        table_to_inline_table_code_action(&text_document, &root, position, toml_version)
+    {
+        code_actions.push(code_action.into());
+    }
+    if let Some(code_action) =
+        inline_table_to_table_code_action(&text_document, &root, position, toml_version)
+    {
+        code_actions.push(code_action.into());
+    }
+
+    if let Some(array) = array_at_position(&root, position) {
+        let schema_context = SchemaContext {
+            toml_version,
+            root_schema: source_schema.as_ref().and_then(|s| s.root_schema.as_ref()),
+            sub_schema_url_map: source_schema.as_ref().map(|s| &s.sub_schema_url_map),
+            order_overrides: None,
+            store: &backend.schema_store,
+        };
+        if let Some(array_schema) =
+            get_array_schema(&root, &document_tree, &array, &schema_context).await
+        {
+            if let Some(code_action) = remove_duplicate_values_code_action(
+                &text_document,
+                &root,
+                &array,
+                &array_schema,
+                &schema_context,
+            ) {
+                code_actions.push(code_action.into());
+            }
+        }
+    }
+
     if let Some(extension_code_actions) = tombi_extension_cargo::code_action(
         &text_document,
         &document_tree,
@@ -99,6 +141,54 @@ pub async fn handle_code_action(
     Ok(Some(code_actions))
 }
 
+/// The schema of the array, looked up by the accessors of the array.
+async fn get_array_schema(
+    root: &tombi_ast::Root,
+    document_tree: &tombi_document_tree::DocumentTree,
+    array: &tombi_ast::Array,
+    schema_context: &SchemaContext<'_>,
+) -> Option<ArraySchema> {
+    let position = array.bracket_start()?.range().start;
+    let (keys, _) =
+        get_completion_keys_with_context(root, position, schema_context.toml_version).await?;
+    let accessors = get_accessors(document_tree, &keys, position);
+
+    // NOTE: The sub schema of the longest root accessors takes precedence over the root schema.
+    let mut sub_schema = None;
+    for index in (1..=accessors.len()).rev() {
+        if let Some(Ok(document_schema)) = schema_context
+            .get_subschema(&accessors[..index], None)
+            .await
+        {
+            sub_schema = Some((index, document_schema));
+            break;
+        }
+    }
+    let (value, accessors, document_schema) = match &sub_schema {
+        Some((index, document_schema)) => (
+            dig_accessors(document_tree, &accessors[..*index])?.1,
+            &accessors[*index..],
+            document_schema,
+        ),
+        None => (
+            document_tree.into(),
+            &accessors[..],
+            schema_context.root_schema?,
+        ),
+    };
+
+    let current_schema = CurrentSchema {
+        value_schema: Cow::Borrowed(document_schema.value_schema.as_ref()?),
+        schema_url: Cow::Borrowed(&document_schema.schema_url),
+        definitions: Cow::Borrowed(&document_schema.definitions),
+    };
+
+    match get_value_schema(value, accessors, &current_schema, schema_context).await {
+        Some(ValueSchema::Array(array_schema)) => Some(array_schema),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,6 +95,7 @@ pub async fn handle_completion(
         sub_schema_url_map: source_schema
             .as_ref()
             .map(|schema| &schema.sub_schema_url_map),
+        order_overrides: None,
         store: &backend.schema_store,
     };
 
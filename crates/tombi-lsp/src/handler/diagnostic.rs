@@ -32,6 +32,35 @@ pub async fn handle_diagnostic(
         ));
     }
 
+    if let Some(document) = backend
+        .document_sources
+        .read()
+        .await
+        .get(&text_document.uri)
+        .filter(|document| document.is_json())
+    {
+        let diagnostics = tombi_linter::JsonSchemaLinter::new(
+            Some(Either::Left(&text_document.uri)),
+            &backend.schema_store,
+        )
+        .lint(&document.text)
+        .await
+        .map_or_else(
+            |diagnostics| diagnostics.into_iter().unique().map(Into::into).collect(),
+            |_| vec![],
+        );
+
+        return Ok(DocumentDiagnosticReportResult::Report(
+            DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    items: diagnostics,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+        ));
+    }
+
     let Some(root) = backend.get_incomplete_ast(&text_document.uri).await else {
         return Ok(DocumentDiagnosticReportResult::Report(
             DocumentDiagnosticReport::Full(Default::default()),
@@ -12,6 +12,10 @@ pub async fn handle_did_open(backend: &Backend, params: DidOpenTextDocumentParam
     let mut document_sources = backend.document_sources.write().await;
     document_sources.insert(
         text_document.uri,
-        DocumentSource::new(text_document.text, text_document.version),
+        DocumentSource::new(
+            text_document.text,
+            text_document.version,
+            text_document.language_id,
+        ),
     );
 }
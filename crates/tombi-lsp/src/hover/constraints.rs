@@ -1,4 +1,4 @@
-use tombi_x_keyword::{ArrayValuesOrder, TableKeysOrder};
+use tombi_x_keyword::{ArrayValuesOrder, TableKeysOrder, TableStyle};
 
 use super::display_value::DisplayValue;
 
@@ -25,6 +25,8 @@ pub struct ValueConstraints {
     pub max_items: Option<usize>,
     pub unique_items: Option<bool>,
     pub values_order: Option<ArrayValuesOrder>,
+    pub values_order_by: Option<Vec<String>>,
+    pub values_unique: Option<bool>,
 
     // Table
     pub required_keys: Option<Vec<String>>,
@@ -33,6 +35,7 @@ pub struct ValueConstraints {
     pub key_patterns: Option<Vec<String>>,
     pub additional_keys: Option<bool>,
     pub keys_order: Option<TableKeysOrder>,
+    pub table_style: Option<TableStyle>,
 }
 
 impl std::fmt::Display for ValueConstraints {
@@ -105,6 +108,17 @@ impl std::fmt::Display for ValueConstraints {
             write!(f, "Values Order: `{}`\n\n", values_order)?;
         }
 
+        if let Some(values_order_by) = &self.values_order_by {
+            write!(f, "Values Order By:\n\n")?;
+            for key in values_order_by.iter() {
+                write!(f, "- `{}`\n\n", key)?;
+            }
+        }
+
+        if let Some(values_unique) = self.values_unique {
+            write!(f, "Values Unique: `{}`\n\n", values_unique)?;
+        }
+
         if let Some(required_keys) = &self.required_keys {
             write!(f, "Required Keys:\n\n")?;
             for key in required_keys.iter() {
@@ -135,6 +149,10 @@ impl std::fmt::Display for ValueConstraints {
             write!(f, "Keys Order: `{}`\n\n", keys_order)?;
         }
 
+        if let Some(table_style) = &self.table_style {
+            write!(f, "Table Style: `{}`\n\n", table_style)?;
+        }
+
         Ok(())
     }
 }
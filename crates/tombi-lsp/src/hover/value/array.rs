@@ -59,7 +59,7 @@ impl GetHoverContent for tombi_document_tree::Array {
                             if value.range().contains(position) {
                                 let accessor = Accessor::Index(index);
 
-                                if let Some(items) = &array_schema.items {
+                                if let Some(items) = array_schema.item_schema(index) {
                                     let mut referable_schema = items.write().await;
                                     if let Ok(Some(current_schema)) = referable_schema
                                         .resolve(
@@ -94,6 +94,8 @@ impl GetHoverContent for tombi_document_tree::Array {
                                                 constraints.max_items = array_schema.max_items;
                                                 constraints.unique_items =
                                                     array_schema.unique_items;
+                                                constraints.values_unique =
+                                                    array_schema.values_unique;
                                             }
                                         }
 
@@ -252,6 +254,8 @@ impl GetHoverContent for ArraySchema {
                     max_items: self.max_items,
                     unique_items: self.unique_items,
                     values_order: self.values_order.clone(),
+                    values_order_by: self.values_order_by.clone(),
+                    values_unique: self.values_unique,
                     ..Default::default()
                 }),
                 schema_url: current_schema.map(|cs| cs.schema_url.as_ref().clone()),
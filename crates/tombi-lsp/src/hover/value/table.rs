@@ -441,6 +441,7 @@ impl GetHoverContent for TableSchema {
                         self.allows_any_additional_properties(schema_context.strict()),
                     ),
                     keys_order: self.keys_order,
+                    table_style: self.style,
                     ..Default::default()
                 }),
                 schema_url: current_schema.map(|schema| schema.schema_url.as_ref().clone()),
@@ -400,6 +400,107 @@ mod refactor_rewrite {
             "#,
             ) -> Ok(None);
         }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn table_to_inline_table(
+                r#"
+            [foo]
+            a = 1
+
+            [foo.bar█]
+            b = 2
+            c = "x"
+            "#,
+                Select(CodeActionRefactorRewriteName::TableToInlineTable),
+            ) -> Ok(Some(
+                r#"
+            [foo]
+            a = 1
+            bar = { b = 2, c = "x" }
+            "#
+            ));
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn table_to_inline_table_in_root_with_comment(
+                r#"
+            a = 1
+
+            [foo█] # comment
+            b = 2
+            "#,
+                Select(CodeActionRefactorRewriteName::TableToInlineTable),
+            ) -> Ok(Some(
+                r#"
+            a = 1
+            foo = { b = 2 } # comment
+            "#
+            ));
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn table_with_subtables_to_inline_table(
+                r#"
+            [foo█]
+            a = 1
+
+            [foo.bar]
+            b = 2
+            "#,
+                Select(CodeActionRefactorRewriteName::TableToInlineTable),
+            ) -> Ok(None);
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn inline_table_to_table(
+                r#"
+            [foo]
+            a = 1
+            bar█ = { b = 2, c = "x" } # comment
+
+            [baz]
+            d = 3
+            "#,
+                Select(CodeActionRefactorRewriteName::InlineTableToTable),
+            ) -> Ok(Some(
+                r#"
+            [foo]
+            a = 1
+
+            [foo.bar] # comment
+            b = 2
+            c = "x"
+
+            [baz]
+            d = 3
+            "#
+            ));
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn inline_table_to_table_in_root(
+                r#"
+            foo█ = { a = 1 }
+
+            [bar]
+            b = 2
+            "#,
+                Select(CodeActionRefactorRewriteName::InlineTableToTable),
+            ) -> Ok(Some(
+                r#"
+            [foo]
+            a = 1
+
+            [bar]
+            b = 2
+            "#
+            ));
+        }
     }
 
     mod cargo_toml {
@@ -581,3 +682,102 @@ mod refactor_rewrite {
         }
     }
 }
+
+mod quick_fix {
+    mod array_values_unique {
+        use tombi_lsp::code_action::CodeActionQuickFixName;
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn remove_duplicate_values(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            keywords = ["toml", "json"█, 'toml', "yaml", "json"]
+            "#,
+                Select(CodeActionQuickFixName::RemoveDuplicateValues),
+            ) -> Ok(Some(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            keywords = ["toml", "json", "yaml"]
+            "#
+            ));
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn remove_duplicate_values_keeps_first_comments(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            ports = [
+              # http
+              80,  # http comment
+              0x50,
+              443,█
+              80
+            ]
+            "#,
+                Select(CodeActionQuickFixName::RemoveDuplicateValues),
+            ) -> Ok(Some(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            ports = [
+              # http
+              80,  # http comment
+              443
+            ]
+            "#
+            ));
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn remove_duplicate_inline_tables(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            authors = [{ name = "a", email = "x" }, █{ email = "x", name = "a" }, { name = "b" }]
+            "#,
+                Select(CodeActionQuickFixName::RemoveDuplicateValues),
+            ) -> Ok(Some(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            authors = [{ name = "a", email = "x" }, { name = "b" }]
+            "#
+            ));
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn remove_duplicate_values_in_array_of_tables(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            [[packages]]
+            keywords = ["a", "b"]
+
+            [[packages]]
+            keywords = ["b"█, "c", "b"]
+            "#,
+                Select(CodeActionQuickFixName::RemoveDuplicateValues),
+            ) -> Ok(Some(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            [[packages]]
+            keywords = ["a", "b"]
+
+            [[packages]]
+            keywords = ["b", "c"]
+            "#
+            ));
+        }
+
+        test_code_action_refactor_rewrite! {
+            #[tokio::test]
+            async fn remove_duplicate_values_disabled(
+                r#"
+            #:schema ../../schemas/x-tombi-array-values-unique.schema.json
+            tags = ["a"█, "a"]
+            "#,
+                Select(CodeActionQuickFixName::RemoveDuplicateValues),
+            ) -> Ok(None);
+        }
+    }
+}
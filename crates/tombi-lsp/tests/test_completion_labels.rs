@@ -23,6 +23,7 @@ mod completion_labels {
                 "include",
                 "lint",
                 "lsp",
+                "overrides",
                 "schema",
                 "schemas",
                 "server",
@@ -76,6 +77,7 @@ mod completion_labels {
                 "include",
                 "lint",
                 "lsp",
+                "overrides",
                 "schema",
                 "schemas",
                 "server",
@@ -99,6 +101,7 @@ mod completion_labels {
                 "include",
                 "lint",
                 "lsp",
+                "overrides",
                 "schema",
                 "schemas",
                 "server",
@@ -115,6 +118,7 @@ mod completion_labels {
                 "format",
                 "lint",
                 "lsp",
+                "overrides",
                 "schema",
                 "schemas",
                 "server",
@@ -134,6 +138,7 @@ mod completion_labels {
                 "format",
                 "lint",
                 "lsp",
+                "overrides",
                 "schema",
                 "schemas",
                 "server",
@@ -155,6 +160,7 @@ mod completion_labels {
                 "format",
                 "lint",
                 "lsp",
+                "overrides",
                 "schema",
                 "schemas",
                 "server",
@@ -170,6 +176,7 @@ mod completion_labels {
                 "format",
                 "lint",
                 "lsp",
+                "overrides",
                 "schema",
                 "schemas",
                 "server",
@@ -184,6 +191,7 @@ mod completion_labels {
                 "#,
                 Schema(tombi_schema_path()),
             ) -> Ok([
+                "cache",
                 "catalog",
             ]);
         }
@@ -297,6 +305,7 @@ mod completion_labels {
                 "#,
                 Schema(tombi_schema_path()),
             ) -> Ok([
+                "code-action",
                 "completion",
                 "diagnostics",
                 "document-link",
@@ -319,6 +328,7 @@ mod completion_labels {
                 "#,
                 Schema(tombi_schema_path()),
             ) -> Ok([
+                "code-action",
                 "completion",
                 "diagnostics",
                 "document-link",
@@ -131,6 +131,7 @@ mod goto_definition_tests {
             ) -> Ok([
                 project_root_path().join("crates/tombi-ast-editor/Cargo.toml"),
                 project_root_path().join("crates/tombi-formatter/Cargo.toml"),
+                project_root_path().join("crates/tombi-lsp/Cargo.toml"),
             ]);
         );
 
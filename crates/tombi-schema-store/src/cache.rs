@@ -0,0 +1,331 @@
+//! On-disk cache of remote schemas and catalogs.
+//!
+//! Each cached URL is stored as a pair of files in the cache directory:
+//! the response body and a `.meta.json` file holding the URL, the response validators
+//! (`ETag`/`Last-Modified`), the `Content-Type` and the time it was fetched.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+use itertools::Itertools;
+
+use crate::CacheValidators;
+
+/// Environment variable to override the cache directory.
+pub const CACHE_DIR_ENV: &str = "TOMBI_CACHE_DIR";
+
+/// Default time to live of the cached files.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(tombi_config::DEFAULT_SCHEMA_CACHE_TTL);
+
+const REMOTE_CACHE_DIRNAME: &str = "remote";
+const METADATA_FILE_SUFFIX: &str = ".meta.json";
+
+#[derive(Debug, Clone, Default)]
+pub struct CacheOptions {
+    /// The cache directory. If `None`, [`get_cache_dir_path`] is used.
+    pub cache_dir_path: Option<PathBuf>,
+
+    /// The time to live of the cached files. If `None`, [`DEFAULT_CACHE_TTL`] is used.
+    pub cache_ttl: Option<Duration>,
+}
+
+impl CacheOptions {
+    /// Build the cache options from the `[schema]` section of the config.
+    ///
+    /// Returns `None` if the cache is disabled.
+    pub fn from_schema_options(
+        schema_options: Option<&tombi_config::SchemaOptions>,
+    ) -> Option<Self> {
+        let schema_options = match schema_options {
+            Some(schema_options) => schema_options,
+            None => &tombi_config::SchemaOptions::default(),
+        };
+        if !schema_options.cache_enabled() {
+            return None;
+        }
+
+        Some(Self {
+            cache_dir_path: None,
+            cache_ttl: schema_options.cache_ttl().map(Duration::from_secs),
+        })
+    }
+
+    #[inline]
+    pub fn cache_dir_path(&self) -> Option<PathBuf> {
+        self.cache_dir_path.clone().or_else(get_cache_dir_path)
+    }
+
+    #[inline]
+    pub fn cache_ttl(&self) -> Duration {
+        self.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL)
+    }
+}
+
+/// Get the default cache directory.
+///
+/// `$TOMBI_CACHE_DIR` if set, otherwise `tombi` under the platform cache directory
+/// (`$XDG_CACHE_HOME` or `~/.cache` on Linux).
+pub fn get_cache_dir_path() -> Option<PathBuf> {
+    if let Some(cache_dir_path) = std::env::var_os(CACHE_DIR_ENV) {
+        if !cache_dir_path.is_empty() {
+            return Some(PathBuf::from(cache_dir_path));
+        }
+    }
+    dirs::cache_dir().map(|cache_dir| cache_dir.join("tombi"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheMetadata {
+    pub url: String,
+
+    #[serde(flatten)]
+    pub validators: CacheValidators,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    /// Unix time in seconds when the file was fetched or revalidated.
+    pub fetched_at: u64,
+}
+
+impl CacheMetadata {
+    fn new(url: &url::Url, validators: CacheValidators, content_type: Option<String>) -> Self {
+        Self {
+            url: url.to_string(),
+            validators,
+            content_type,
+            fetched_at: now_unix_secs(),
+        }
+    }
+
+    /// Elapsed time since the file was fetched or revalidated.
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(now_unix_secs().saturating_sub(self.fetched_at))
+    }
+
+    #[inline]
+    pub fn is_expired(&self, ttl: Duration) -> bool {
+        self.age() >= ttl
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub metadata: CacheMetadata,
+    pub body_path: PathBuf,
+}
+
+impl CacheEntry {
+    /// Size of the cached body in bytes.
+    pub fn size(&self) -> u64 {
+        std::fs::metadata(&self.body_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or_default()
+    }
+}
+
+pub(crate) fn read_cache(cache_dir_path: &Path, url: &url::Url) -> Option<(CacheMetadata, Bytes)> {
+    let body_path = get_body_path(cache_dir_path, url);
+    let metadata = read_metadata(&get_metadata_path(&body_path))?;
+    if metadata.url != url.as_str() {
+        return None;
+    }
+
+    match std::fs::read(&body_path) {
+        Ok(bytes) => Some((metadata, Bytes::from(bytes))),
+        Err(_) => None,
+    }
+}
+
+pub(crate) fn save_cache(
+    cache_dir_path: &Path,
+    url: &url::Url,
+    bytes: &[u8],
+    validators: CacheValidators,
+    content_type: Option<String>,
+) -> std::io::Result<()> {
+    let body_path = get_body_path(cache_dir_path, url);
+    if let Some(parent) = body_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&body_path, bytes)?;
+    write_metadata(
+        &body_path,
+        &CacheMetadata::new(url, validators, content_type),
+    )
+}
+
+/// Mark the cached file as fresh again after the remote server answered `304 Not Modified`.
+pub(crate) fn refresh_cache(
+    cache_dir_path: &Path,
+    url: &url::Url,
+    metadata: CacheMetadata,
+) -> std::io::Result<()> {
+    write_metadata(
+        &get_body_path(cache_dir_path, url),
+        &CacheMetadata::new(url, metadata.validators, metadata.content_type),
+    )
+}
+
+/// List the cached files, sorted by URL.
+pub fn list_cache(cache_dir_path: &Path) -> std::io::Result<Vec<CacheEntry>> {
+    let remote_dir_path = cache_dir_path.join(REMOTE_CACHE_DIRNAME);
+    if !remote_dir_path.exists() {
+        return Ok(Vec::with_capacity(0));
+    }
+
+    let mut entries = Vec::new();
+    for dir_entry in std::fs::read_dir(&remote_dir_path)? {
+        let metadata_path = dir_entry?.path();
+        let Some(file_name) = metadata_path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(body_file_name) = file_name.strip_suffix(METADATA_FILE_SUFFIX) else {
+            continue;
+        };
+        let body_path = remote_dir_path.join(body_file_name);
+        if !body_path.exists() {
+            continue;
+        }
+        if let Some(metadata) = read_metadata(&metadata_path) {
+            entries.push(CacheEntry {
+                metadata,
+                body_path,
+            });
+        }
+    }
+
+    Ok(entries
+        .into_iter()
+        .sorted_by(|a, b| a.metadata.url.cmp(&b.metadata.url))
+        .collect())
+}
+
+/// Remove all the cached files, and return the number of removed entries.
+pub fn clear_cache(cache_dir_path: &Path) -> std::io::Result<usize> {
+    let remote_dir_path = cache_dir_path.join(REMOTE_CACHE_DIRNAME);
+    if !remote_dir_path.exists() {
+        return Ok(0);
+    }
+
+    let count = list_cache(cache_dir_path)?.len();
+    std::fs::remove_dir_all(&remote_dir_path)?;
+
+    Ok(count)
+}
+
+fn get_body_path(cache_dir_path: &Path, url: &url::Url) -> PathBuf {
+    let extension = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|file_name| Path::new(file_name).extension())
+        .and_then(|extension| extension.to_str())
+        .filter(|extension| extension.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or("bin");
+
+    cache_dir_path
+        .join(REMOTE_CACHE_DIRNAME)
+        .join(format!("{:016x}.{extension}", fnv1a_hash(url.as_str())))
+}
+
+fn get_metadata_path(body_path: &Path) -> PathBuf {
+    let mut metadata_path = body_path.as_os_str().to_owned();
+    metadata_path.push(METADATA_FILE_SUFFIX);
+    PathBuf::from(metadata_path)
+}
+
+fn read_metadata(metadata_path: &Path) -> Option<CacheMetadata> {
+    let content = std::fs::read(metadata_path).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+fn write_metadata(body_path: &Path, metadata: &CacheMetadata) -> std::io::Result<()> {
+    let content = serde_json::to_vec_pretty(metadata).map_err(std::io::Error::other)?;
+    std::fs::write(get_metadata_path(body_path), content)
+}
+
+/// Stable hash of the URL, used as the cache file name.
+///
+/// [`std::hash::Hash`] is not guaranteed to be stable across releases,
+/// so we use 64-bit FNV-1a.
+fn fnv1a_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_read_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let url = url::Url::parse("https://json.schemastore.org/api/json/catalog.json").unwrap();
+        let validators = CacheValidators {
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+        };
+
+        assert!(read_cache(cache_dir.path(), &url).is_none());
+
+        save_cache(
+            cache_dir.path(),
+            &url,
+            b"{}",
+            validators.clone(),
+            Some("application/json".to_string()),
+        )
+        .unwrap();
+
+        let (metadata, bytes) = read_cache(cache_dir.path(), &url).unwrap();
+        pretty_assertions::assert_eq!(metadata.url, url.as_str());
+        pretty_assertions::assert_eq!(metadata.validators, validators);
+        pretty_assertions::assert_eq!(metadata.content_type.as_deref(), Some("application/json"));
+        pretty_assertions::assert_eq!(bytes.as_ref(), b"{}");
+        assert!(!metadata.is_expired(DEFAULT_CACHE_TTL));
+        assert!(metadata.is_expired(Duration::ZERO));
+    }
+
+    #[test]
+    fn list_and_clear_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let urls = [
+            url::Url::parse("https://example.com/b.json").unwrap(),
+            url::Url::parse("https://example.com/a.json").unwrap(),
+        ];
+        for url in &urls {
+            save_cache(
+                cache_dir.path(),
+                url,
+                b"{}",
+                CacheValidators::default(),
+                None,
+            )
+            .unwrap();
+        }
+
+        let entries = list_cache(cache_dir.path()).unwrap();
+        pretty_assertions::assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.metadata.url.as_str())
+                .collect_vec(),
+            vec!["https://example.com/a.json", "https://example.com/b.json"]
+        );
+
+        pretty_assertions::assert_eq!(clear_cache(cache_dir.path()).unwrap(), 2);
+        assert!(list_cache(cache_dir.path()).unwrap().is_empty());
+    }
+}
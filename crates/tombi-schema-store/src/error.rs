@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::{json::CatalogUrl, SchemaUrl};
+use crate::{json::CatalogUrl, SchemaFormat, SchemaUrl};
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum Error {
@@ -31,6 +31,13 @@ pub enum Error {
     #[error("failed to read catalog: {catalog_path}")]
     CatalogFileReadFailed { catalog_path: PathBuf },
 
+    #[error("invalid {format} format catalog: {catalog_url}, reason: {reason}")]
+    InvalidCatalogFormat {
+        catalog_url: CatalogUrl,
+        format: SchemaFormat,
+        reason: String,
+    },
+
     #[error("unsupported schema url: {schema_url}")]
     UnsupportedSchemaUrl { schema_url: SchemaUrl },
 
@@ -84,4 +91,7 @@ pub enum Error {
 
     #[error("schema must be an object: {schema_url}")]
     SchemaMustBeObject { schema_url: SchemaUrl },
+
+    #[error("failed to write vendored file: {path}, reason: {reason}")]
+    VendorFileWriteFailed { path: PathBuf, reason: String },
 }
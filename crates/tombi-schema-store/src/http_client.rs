@@ -1,5 +1,8 @@
 mod error;
 
+use bytes::Bytes;
+pub use error::FetchError;
+
 #[cfg(feature = "reqwest01")]
 mod reqwest_client;
 #[cfg(feature = "reqwest01")]
@@ -14,3 +17,32 @@ pub use gloo_net_client::HttpClient;
 mod surf_client;
 #[cfg(feature = "surf2")]
 pub use surf_client::HttpClient;
+
+/// Validators of a previous response, used to make a conditional request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheValidators {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Response of a conditional request.
+#[derive(Debug, Clone)]
+pub enum ConditionalResponse {
+    Modified {
+        bytes: Bytes,
+        validators: CacheValidators,
+        /// The `Content-Type` header, used to detect the format of the content.
+        content_type: Option<String>,
+    },
+    NotModified,
+}
@@ -1,4 +1,4 @@
-use crate::http_client::error::FetchError;
+use crate::http_client::{error::FetchError, CacheValidators, ConditionalResponse};
 use bytes::Bytes;
 
 #[derive(Debug, Clone)]
@@ -33,4 +33,55 @@ impl HttpClient {
 
         Ok(Bytes::from(binary))
     }
+
+    pub async fn get_bytes_if_modified(
+        &self,
+        url: &str,
+        validators: &CacheValidators,
+    ) -> Result<ConditionalResponse, FetchError> {
+        let mut request = gloo_net::http::Request::get(url);
+        if let Some(etag) = &validators.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| FetchError::FetchFailed {
+                reason: err.to_string(),
+            })?;
+
+        if response.status() == 304 {
+            return Ok(ConditionalResponse::NotModified);
+        }
+
+        let is_success = 200 <= response.status() && response.status() < 300;
+        if !is_success {
+            return Err(FetchError::StatusNotOk {
+                status: response.status(),
+            });
+        }
+
+        let validators = CacheValidators {
+            etag: response.headers().get("ETag"),
+            last_modified: response.headers().get("Last-Modified"),
+        };
+        let content_type = response.headers().get("Content-Type");
+
+        let binary = response
+            .binary()
+            .await
+            .map_err(|e| FetchError::BodyReadFailed {
+                reason: e.to_string(),
+            })?;
+
+        Ok(ConditionalResponse::Modified {
+            bytes: Bytes::from(binary),
+            validators,
+            content_type,
+        })
+    }
 }
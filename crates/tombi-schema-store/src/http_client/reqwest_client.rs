@@ -1,4 +1,4 @@
-use crate::http_client::error::FetchError;
+use crate::http_client::{error::FetchError, CacheValidators, ConditionalResponse};
 use bytes::Bytes;
 
 #[derive(Debug, Clone)]
@@ -37,4 +37,61 @@ impl HttpClient {
                 reason: err.to_string(),
             })
     }
+
+    pub async fn get_bytes_if_modified(
+        &self,
+        url: &str,
+        validators: &CacheValidators,
+    ) -> Result<ConditionalResponse, FetchError> {
+        let mut request = self.0.get(url);
+        if let Some(etag) = &validators.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| FetchError::FetchFailed {
+                reason: err.to_string(),
+            })?;
+
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(ConditionalResponse::NotModified);
+        }
+
+        if !response.status().is_success() {
+            return Err(FetchError::StatusNotOk {
+                status: response.status().as_u16(),
+            });
+        }
+
+        let get_header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToString::to_string)
+        };
+        let validators = CacheValidators {
+            etag: get_header(reqwest::header::ETAG),
+            last_modified: get_header(reqwest::header::LAST_MODIFIED),
+        };
+        let content_type = get_header(reqwest::header::CONTENT_TYPE);
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|err| FetchError::BodyReadFailed {
+                reason: err.to_string(),
+            })?;
+
+        Ok(ConditionalResponse::Modified {
+            bytes,
+            validators,
+            content_type,
+        })
+    }
 }
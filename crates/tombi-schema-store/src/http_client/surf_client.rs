@@ -1,4 +1,4 @@
-use crate::http_client::error::FetchError;
+use crate::http_client::{error::FetchError, CacheValidators, ConditionalResponse};
 use bytes::Bytes;
 
 #[derive(Debug, Clone)]
@@ -34,4 +34,60 @@ impl HttpClient {
 
         Ok(Bytes::from(binary))
     }
+
+    pub async fn get_bytes_if_modified(
+        &self,
+        url: &str,
+        validators: &CacheValidators,
+    ) -> Result<ConditionalResponse, FetchError> {
+        let mut request = self.0.get(url);
+        if let Some(etag) = &validators.etag {
+            request = request.header("If-None-Match", etag.as_str());
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header("If-Modified-Since", last_modified.as_str());
+        }
+
+        let mut response = request
+            .send()
+            .await
+            .map_err(|err| FetchError::FetchFailed {
+                reason: err.to_string(),
+            })?;
+
+        if response.status() == surf::StatusCode::NotModified {
+            return Ok(ConditionalResponse::NotModified);
+        }
+
+        if !response.status().is_success() {
+            return Err(FetchError::StatusNotOk {
+                status: response.status().into(),
+            });
+        }
+
+        let validators = CacheValidators {
+            etag: response
+                .header("ETag")
+                .map(|values| values.last().as_str().to_string()),
+            last_modified: response
+                .header("Last-Modified")
+                .map(|values| values.last().as_str().to_string()),
+        };
+        let content_type = response
+            .header("Content-Type")
+            .map(|values| values.last().as_str().to_string());
+
+        let binary = response
+            .body_bytes()
+            .await
+            .map_err(|e| FetchError::BodyReadFailed {
+                reason: e.to_string(),
+            })?;
+
+        Ok(ConditionalResponse::Modified {
+            bytes: Bytes::from(binary),
+            validators,
+            content_type,
+        })
+    }
 }
@@ -1,24 +1,30 @@
 mod accessor;
+pub mod cache;
 mod error;
 mod http_client;
 pub mod json;
 pub mod macros;
 mod options;
 mod schema;
+mod schema_format;
 mod store;
 mod value_type;
+mod vendor;
 mod x_taplo;
 
 pub use accessor::{Accessor, Accessors};
+pub use cache::CacheOptions;
 pub use error::Error;
 pub use http_client::*;
 use itertools::Itertools;
 pub use options::Options;
 pub use schema::*;
+pub use schema_format::SchemaFormat;
 pub use store::SchemaStore;
 use tombi_ast::{algo::ancestors_at_position, AstNode};
 use tombi_document_tree::TryIntoDocumentTree;
 pub use value_type::ValueType;
+pub use vendor::{VendorResult, VendoredSchema, VENDORED_CATALOG_FILENAME};
 
 pub use crate::accessor::{AccessorContext, AccessorKeyKind, KeyContext};
 
@@ -2,4 +2,5 @@
 pub struct Options {
     pub strict: Option<bool>,
     pub offline: Option<bool>,
+    pub cache: Option<crate::CacheOptions>,
 }
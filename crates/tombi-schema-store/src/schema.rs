@@ -13,6 +13,7 @@ mod one_of_schema;
 mod referable_schema;
 mod schema_accessor;
 mod schema_context;
+mod schema_dialect;
 mod schema_url;
 mod source_schema;
 mod string_schema;
@@ -38,6 +39,7 @@ pub use one_of_schema::OneOfSchema;
 pub use referable_schema::{is_online_url, CurrentSchema, Referable};
 pub use schema_accessor::{GetHeaderSchemarAccessors, SchemaAccessor, SchemaAccessors};
 pub use schema_context::SchemaContext;
+pub use schema_dialect::{lint_schema_dialect, SchemaDialect};
 pub use schema_url::SchemaUrl;
 pub use source_schema::{SourceSchema, SubSchemaUrlMap};
 pub use string_schema::StringSchema;
@@ -67,6 +69,17 @@ pub struct Schema {
     pub sub_root_keys: Option<Vec<SchemaAccessor>>,
 }
 
+/// The ordering of the keys and values that overrides the schema.
+#[derive(Debug, Clone)]
+pub struct OrderOverride {
+    pub include: Vec<String>,
+    pub accessors: Vec<SchemaAccessor>,
+    pub table_keys_order: Option<tombi_x_keyword::TableKeysOrder>,
+    pub array_values_order: Option<tombi_x_keyword::ArrayValuesOrder>,
+}
+
+pub type OrderOverrides = Vec<OrderOverride>;
+
 pub trait FindSchemaCandidates {
     fn find_schema_candidates<'a: 'b, 'b>(
         &'a self,
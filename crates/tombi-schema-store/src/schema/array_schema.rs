@@ -1,7 +1,10 @@
 use std::{borrow::Cow, sync::Arc};
 
 use tombi_future::{BoxFuture, Boxable};
-use tombi_x_keyword::{ArrayValuesOrder, X_TOMBI_ARRAY_VALUES_ORDER};
+use tombi_x_keyword::{
+    ArrayValuesOrder, X_TOMBI_ARRAY_VALUES_ORDER, X_TOMBI_ARRAY_VALUES_ORDER_BY,
+    X_TOMBI_ARRAY_VALUES_UNIQUE,
+};
 
 use super::{
     CurrentSchema, FindSchemaCandidates, Referable, SchemaDefinitions, SchemaItem, SchemaUrl,
@@ -14,6 +17,7 @@ pub struct ArraySchema {
     pub title: Option<String>,
     pub description: Option<String>,
     pub range: tombi_text::Range,
+    pub prefix_items: Option<Vec<Option<SchemaItem>>>,
     pub items: Option<SchemaItem>,
     pub min_items: Option<usize>,
     pub max_items: Option<usize>,
@@ -22,6 +26,12 @@ pub struct ArraySchema {
     pub default: Option<tombi_json::Value>,
     pub examples: Option<Vec<tombi_json::Value>>,
     pub values_order: Option<ArrayValuesOrder>,
+    /// The keys of the table values to sort the array by, in priority order.
+    pub values_order_by: Option<Vec<String>>,
+    /// Whether the formatter removes the duplicate values.
+    ///
+    /// If not specified, it follows `uniqueItems`.
+    pub values_unique: Option<bool>,
     pub deprecated: Option<bool>,
 }
 
@@ -34,6 +44,20 @@ impl ArraySchema {
             description: object
                 .get("description")
                 .and_then(|v| v.as_str().map(|s| s.to_string())),
+            prefix_items: object.get("prefixItems").and_then(|value| {
+                value.as_array().map(|array| {
+                    array
+                        .items
+                        .iter()
+                        .map(|value| {
+                            value
+                                .as_object()
+                                .and_then(Referable::<ValueSchema>::new)
+                                .map(|schema| Arc::new(tokio::sync::RwLock::new(schema)))
+                        })
+                        .collect()
+                })
+            }),
             items: object.get("items").and_then(|value| {
                 value
                     .as_object()
@@ -82,6 +106,39 @@ impl ArraySchema {
                         None
                     }
                 }),
+            values_order_by: object
+                .get(X_TOMBI_ARRAY_VALUES_ORDER_BY)
+                .and_then(|order_by| {
+                    let keys = match order_by {
+                        tombi_json::ValueNode::String(string) => Some(vec![string.value.clone()]),
+                        tombi_json::ValueNode::Array(array) => array
+                            .items
+                            .iter()
+                            .map(|key| key.as_str().map(ToString::to_string))
+                            .collect::<Option<Vec<_>>>()
+                            .filter(|keys| !keys.is_empty()),
+                        _ => None,
+                    };
+                    if keys.is_none() {
+                        tracing::error!(
+                            "invalid {X_TOMBI_ARRAY_VALUES_ORDER_BY}: {}",
+                            order_by.to_string()
+                        );
+                    }
+                    keys
+                }),
+            values_unique: object.get(X_TOMBI_ARRAY_VALUES_UNIQUE).and_then(
+                |unique| match unique {
+                    tombi_json::ValueNode::Bool(unique) => Some(unique.value),
+                    _ => {
+                        tracing::error!(
+                            "invalid {X_TOMBI_ARRAY_VALUES_UNIQUE}: {}",
+                            unique.to_string()
+                        );
+                        None
+                    }
+                },
+            ),
             deprecated: object.get("deprecated").and_then(|v| v.as_bool()),
             range: object.range,
         }
@@ -90,6 +147,23 @@ impl ArraySchema {
     pub fn value_type(&self) -> crate::ValueType {
         crate::ValueType::Array
     }
+
+    /// Whether the duplicate values should be removed.
+    ///
+    /// `x-tombi-array-values-unique` takes precedence over `uniqueItems`.
+    pub fn is_values_unique(&self) -> bool {
+        self.values_unique.or(self.unique_items).unwrap_or_default()
+    }
+
+    /// The schema of the item at the index.
+    ///
+    /// `prefixItems` takes precedence over `items`.
+    pub fn item_schema(&self, index: usize) -> Option<&SchemaItem> {
+        match &self.prefix_items {
+            Some(prefix_items) if index < prefix_items.len() => prefix_items[index].as_ref(),
+            _ => self.items.as_ref(),
+        }
+    }
 }
 
 impl FindSchemaCandidates for ArraySchema {
@@ -104,7 +178,10 @@ impl FindSchemaCandidates for ArraySchema {
             let mut errors = Vec::new();
             let mut candidates = Vec::new();
 
-            let Some(ref items) = self.items else {
+            let Some(items) = (match accessors.first() {
+                Some(Accessor::Index(index)) => self.item_schema(*index),
+                _ => self.items.as_ref(),
+            }) else {
                 return (candidates, errors);
             };
 
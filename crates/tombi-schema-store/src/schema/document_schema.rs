@@ -3,7 +3,8 @@ use tombi_config::TomlVersion;
 use tombi_future::{BoxFuture, Boxable};
 
 use super::{
-    referable_schema::Referable, FindSchemaCandidates, SchemaDefinitions, SchemaUrl, ValueSchema,
+    referable_schema::Referable, schema_dialect::normalize_schema, FindSchemaCandidates,
+    SchemaDefinitions, SchemaDialect, SchemaUrl, ValueSchema,
 };
 use crate::{Accessor, SchemaStore};
 
@@ -14,10 +15,26 @@ pub struct DocumentSchema {
     pub(crate) toml_version: Option<TomlVersion>,
     pub value_schema: Option<ValueSchema>,
     pub definitions: SchemaDefinitions,
+    pub dialect: Option<SchemaDialect>,
+
+    /// Warnings about the schema document itself, such as keywords not supported by the dialect.
+    ///
+    /// The ranges are located in the schema document of [`schema_url`](Self::schema_url).
+    pub diagnostics: Vec<tombi_diagnostic::Diagnostic>,
 }
 
 impl DocumentSchema {
-    pub fn new(object: tombi_json::ObjectNode, schema_url: SchemaUrl) -> Self {
+    pub fn new(mut object: tombi_json::ObjectNode, schema_url: SchemaUrl) -> Self {
+        let mut diagnostics = Vec::new();
+        let dialect = SchemaDialect::detect(&object, &mut diagnostics);
+        normalize_schema(&mut object, dialect, &mut diagnostics);
+        if let Ok(schema_path) = tombi_url::url_to_file_path(&schema_url) {
+            diagnostics = diagnostics
+                .into_iter()
+                .map(|diagnostic| diagnostic.with_source_file(&schema_path))
+                .collect();
+        }
+
         let toml_version = object
             .get("x-tombi-toml-version")
             .and_then(|obj| match obj {
@@ -60,6 +77,8 @@ impl DocumentSchema {
             toml_version,
             value_schema,
             definitions: SchemaDefinitions::new(definitions.into()),
+            dialect,
+            diagnostics,
         }
     }
 
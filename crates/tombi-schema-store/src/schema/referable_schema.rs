@@ -121,41 +121,71 @@ impl Referable<ValueSchema> {
                         }
 
                         *self = referable_schema;
-                    } else if is_online_url(reference) {
-                        let schema_url = SchemaUrl::parse(reference)?;
-
-                        if let Some(mut document_schema) =
+                    } else if !reference.starts_with('#') {
+                        // NOTE: Relative references are resolved against the current schema url,
+                        //       which is needed to use the vendored schemas.
+                        let Ok(mut reference_url) = schema_url.join(reference) else {
+                            return Err(crate::Error::InvalidJsonSchemaReference {
+                                reference: reference.to_owned(),
+                            });
+                        };
+                        let fragment = reference_url
+                            .fragment()
+                            .filter(|fragment| !fragment.is_empty())
+                            .map(|fragment| format!("#{fragment}"));
+                        reference_url.set_fragment(None);
+                        let schema_url = SchemaUrl::new(reference_url);
+
+                        let Some(document_schema) =
                             schema_store.try_get_document_schema(&schema_url).await?
-                        {
-                            if let Some(value_schema) = &mut document_schema.value_schema {
-                                if title.is_some() || description.is_some() {
-                                    value_schema.set_title(title.to_owned());
-                                    value_schema.set_description(description.to_owned());
-                                }
-                                if let Some(deprecated) = deprecated {
-                                    value_schema.set_deprecated(*deprecated);
+                        else {
+                            return Ok(None);
+                        };
+
+                        let mut referable_schema = match &fragment {
+                            Some(fragment) => document_schema
+                                .definitions
+                                .read()
+                                .await
+                                .get(fragment)
+                                .cloned(),
+                            None => document_schema.value_schema.clone().map(|value_schema| {
+                                Referable::Resolved {
+                                    schema_url: None,
+                                    value: value_schema,
                                 }
+                            }),
+                        }
+                        .ok_or_else(|| {
+                            crate::Error::InvalidJsonSchemaReference {
+                                reference: reference.to_owned(),
+                            }
+                        })?;
 
-                                *self = Referable::Resolved {
-                                    schema_url: Some(document_schema.schema_url.clone()),
-                                    value: value_schema.clone(),
-                                };
-
-                                return self
-                                    .resolve(
-                                        Cow::Owned(document_schema.schema_url),
-                                        Cow::Owned(document_schema.definitions),
-                                        schema_store,
-                                    )
-                                    .await;
-                            } else {
-                                return Err(crate::Error::InvalidJsonSchemaReference {
-                                    reference: reference.to_owned(),
-                                });
+                        if let Referable::Resolved {
+                            schema_url: reference_url,
+                            value: ref mut value_schema,
+                        } = &mut referable_schema
+                        {
+                            if title.is_some() || description.is_some() {
+                                value_schema.set_title(title.to_owned());
+                                value_schema.set_description(description.to_owned());
                             }
-                        } else {
-                            return Ok(None);
+                            if let Some(deprecated) = deprecated {
+                                value_schema.set_deprecated(*deprecated);
+                            }
+                            *reference_url = Some(document_schema.schema_url.clone());
                         }
+
+                        *self = referable_schema;
+
+                        return self
+                            .resolve(
+                                Cow::Owned(document_schema.schema_url),
+                                Cow::Owned(document_schema.definitions),
+                                schema_store,
+                            )
+                            .await;
                     } else {
                         return Err(crate::Error::UnsupportedReference {
                             reference: reference.to_owned(),
@@ -4,6 +4,7 @@ pub struct SchemaContext<'a> {
     pub toml_version: tombi_config::TomlVersion,
     pub root_schema: Option<&'a crate::DocumentSchema>,
     pub sub_schema_url_map: Option<&'a crate::SubSchemaUrlMap>,
+    pub order_overrides: Option<&'a crate::OrderOverrides>,
     pub store: &'a crate::SchemaStore,
 }
 
@@ -13,6 +14,34 @@ impl SchemaContext<'_> {
         self.store.strict()
     }
 
+    /// The table keys order of the config overrides for the accessors.
+    ///
+    /// The later override takes precedence.
+    pub fn table_keys_order(
+        &self,
+        accessors: &[SchemaAccessor],
+    ) -> Option<tombi_x_keyword::TableKeysOrder> {
+        self.order_overrides?
+            .iter()
+            .rev()
+            .filter(|order_override| order_override.accessors == accessors)
+            .find_map(|order_override| order_override.table_keys_order)
+    }
+
+    /// The array values order of the config overrides for the accessors.
+    ///
+    /// The later override takes precedence.
+    pub fn array_values_order(
+        &self,
+        accessors: &[SchemaAccessor],
+    ) -> Option<tombi_x_keyword::ArrayValuesOrder> {
+        self.order_overrides?
+            .iter()
+            .rev()
+            .filter(|order_override| order_override.accessors == accessors)
+            .find_map(|order_override| order_override.array_values_order.clone())
+    }
+
     pub async fn get_subschema(
         &self,
         accessors: &[crate::Accessor],
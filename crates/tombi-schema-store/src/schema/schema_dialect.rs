@@ -0,0 +1,451 @@
+//! JSON Schema dialects.
+//!
+//! The dialect is detected from the `$schema` keyword of the document,
+//! and the keywords whose semantics differ between the drafts are normalized
+//! before the document is converted into [`ValueSchema`](super::ValueSchema):
+//!
+//! - `exclusiveMinimum`/`exclusiveMaximum` are numbers.
+//! - Tuple validation uses `prefixItems`, and `items` applies to the rest of the array.
+//!
+//! Keywords not supported by the dialect are reported as warnings and ignored.
+
+use tombi_diagnostic::Diagnostic;
+use tombi_json::{NumberNode, ObjectNode, StringNode, ValueNode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SchemaDialect {
+    Draft04,
+    Draft06,
+    Draft07,
+    Draft2019_09,
+    Draft2020_12,
+}
+
+impl SchemaDialect {
+    /// Detect the dialect from the `$schema` url.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url.trim_end_matches('#');
+        let path = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?;
+
+        match path {
+            "json-schema.org/draft-04/schema" => Some(Self::Draft04),
+            "json-schema.org/draft-06/schema" => Some(Self::Draft06),
+            "json-schema.org/draft-07/schema" => Some(Self::Draft07),
+            "json-schema.org/draft/2019-09/schema" => Some(Self::Draft2019_09),
+            "json-schema.org/draft/2020-12/schema" | "json-schema.org/schema" => {
+                Some(Self::Draft2020_12)
+            }
+            _ => None,
+        }
+    }
+
+    /// Detect the dialect from the `$schema` keyword of the document.
+    pub(crate) fn detect(object: &ObjectNode, diagnostics: &mut Vec<Diagnostic>) -> Option<Self> {
+        match object.get("$schema") {
+            Some(ValueNode::String(dialect_url)) => {
+                let dialect = Self::from_url(&dialect_url.value);
+                if dialect.is_none() {
+                    diagnostics.push(Diagnostic::new_warning(
+                        format!("unknown JSON Schema dialect: {}", dialect_url.value),
+                        dialect_url.range,
+                    ));
+                }
+                dialect
+            }
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Draft04 => "draft-04",
+            Self::Draft06 => "draft-06",
+            Self::Draft07 => "draft-07",
+            Self::Draft2019_09 => "draft 2019-09",
+            Self::Draft2020_12 => "draft 2020-12",
+        }
+    }
+}
+
+impl std::fmt::Display for SchemaDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Keywords which are not supported by all the dialects.
+///
+/// `(keyword, since, until)`: the keyword is supported in `since..until`.
+///
+/// `definitions` is not listed, because it is still widely used with `$ref` after `$defs` was introduced.
+const DIALECT_KEYWORDS: &[(&str, SchemaDialect, Option<SchemaDialect>)] = &[
+    (
+        "dependencies",
+        SchemaDialect::Draft04,
+        Some(SchemaDialect::Draft2019_09),
+    ),
+    (
+        "additionalItems",
+        SchemaDialect::Draft04,
+        Some(SchemaDialect::Draft2020_12),
+    ),
+    ("const", SchemaDialect::Draft06, None),
+    ("contains", SchemaDialect::Draft06, None),
+    ("examples", SchemaDialect::Draft06, None),
+    ("propertyNames", SchemaDialect::Draft06, None),
+    ("$comment", SchemaDialect::Draft07, None),
+    ("if", SchemaDialect::Draft07, None),
+    ("then", SchemaDialect::Draft07, None),
+    ("else", SchemaDialect::Draft07, None),
+    ("$defs", SchemaDialect::Draft2019_09, None),
+    ("dependentRequired", SchemaDialect::Draft2019_09, None),
+    ("dependentSchemas", SchemaDialect::Draft2019_09, None),
+    ("maxContains", SchemaDialect::Draft2019_09, None),
+    ("minContains", SchemaDialect::Draft2019_09, None),
+    ("unevaluatedItems", SchemaDialect::Draft2019_09, None),
+    ("unevaluatedProperties", SchemaDialect::Draft2019_09, None),
+    ("prefixItems", SchemaDialect::Draft2020_12, None),
+];
+
+/// Keywords kept even if the dialect does not support them,
+/// because `$ref` can point into them with a JSON pointer.
+const DEFINITIONS_KEYWORDS: &[&str] = &["definitions", "$defs"];
+
+/// Report the keywords of the schema document not supported by its dialect.
+pub fn lint_schema_dialect(object: &ObjectNode) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let dialect = SchemaDialect::detect(object, &mut diagnostics);
+    normalize_schema(&mut object.clone(), dialect, &mut diagnostics);
+
+    diagnostics
+}
+
+/// Normalize the keywords of the schema document for the dialect.
+///
+/// If `dialect` is `None`, both the old and the new forms of the keywords are accepted.
+pub(crate) fn normalize_schema(
+    object: &mut ObjectNode,
+    dialect: Option<SchemaDialect>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    normalize_keywords(object, dialect, diagnostics);
+
+    for (key, value) in object.properties.iter_mut() {
+        match key.value.as_str() {
+            "properties" | "patternProperties" | "definitions" | "$defs" | "dependencies"
+            | "dependentSchemas" => {
+                if let ValueNode::Object(object) = value {
+                    for value in object.properties.values_mut() {
+                        normalize_subschema(value, dialect, diagnostics);
+                    }
+                }
+            }
+            "allOf" | "anyOf" | "oneOf" | "prefixItems" => {
+                if let ValueNode::Array(array) = value {
+                    for value in array.items.iter_mut() {
+                        normalize_subschema(value, dialect, diagnostics);
+                    }
+                }
+            }
+            "additionalProperties"
+            | "items"
+            | "additionalItems"
+            | "contains"
+            | "not"
+            | "if"
+            | "then"
+            | "else"
+            | "propertyNames"
+            | "unevaluatedItems"
+            | "unevaluatedProperties" => normalize_subschema(value, dialect, diagnostics),
+            _ => {}
+        }
+    }
+}
+
+fn normalize_subschema(
+    value: &mut ValueNode,
+    dialect: Option<SchemaDialect>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let ValueNode::Object(object) = value {
+        normalize_schema(object, dialect, diagnostics);
+    }
+}
+
+fn normalize_keywords(
+    object: &mut ObjectNode,
+    dialect: Option<SchemaDialect>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let Some(dialect) = dialect {
+        for (keyword, since, until) in DIALECT_KEYWORDS {
+            if dialect >= *since && until.is_none_or(|until| dialect < until) {
+                continue;
+            }
+            let Some((key, _)) = object.properties.as_inner().get_key_value(*keyword) else {
+                continue;
+            };
+            diagnostics.push(Diagnostic::new_warning(
+                format!("\"{keyword}\" is not supported in JSON Schema {dialect}"),
+                key.range,
+            ));
+            if !DEFINITIONS_KEYWORDS.contains(keyword) {
+                object.properties.as_inner_mut().shift_remove(*keyword);
+            }
+        }
+    }
+
+    normalize_exclusive_limit(object, "exclusiveMinimum", "minimum", dialect, diagnostics);
+    normalize_exclusive_limit(object, "exclusiveMaximum", "maximum", dialect, diagnostics);
+    normalize_tuple_items(object, dialect, diagnostics);
+}
+
+/// Draft-04 uses a boolean `exclusiveMinimum` to make `minimum` exclusive,
+/// while the later drafts use a number.
+fn normalize_exclusive_limit(
+    object: &mut ObjectNode,
+    exclusive_keyword: &str,
+    inclusive_keyword: &str,
+    dialect: Option<SchemaDialect>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let properties = object.properties.as_inner_mut();
+    match properties.get(exclusive_keyword) {
+        Some(ValueNode::Bool(_)) => {
+            let Some((key, ValueNode::Bool(exclusive))) =
+                properties.shift_remove_entry(exclusive_keyword)
+            else {
+                unreachable!();
+            };
+            if let Some(dialect) = dialect.filter(|dialect| *dialect >= SchemaDialect::Draft06) {
+                diagnostics.push(Diagnostic::new_warning(
+                    format!("\"{exclusive_keyword}\" must be a number in JSON Schema {dialect}"),
+                    exclusive.range,
+                ));
+                return;
+            }
+            if exclusive.value {
+                if let Some(limit) = properties.shift_remove(inclusive_keyword) {
+                    properties.insert(key, limit);
+                }
+            }
+        }
+        Some(ValueNode::Number(number)) if dialect == Some(SchemaDialect::Draft04) => {
+            diagnostics.push(Diagnostic::new_warning(
+                format!(
+                    "\"{exclusive_keyword}\" must be a boolean in JSON Schema {}",
+                    SchemaDialect::Draft04
+                ),
+                number.range,
+            ));
+            properties.shift_remove(exclusive_keyword);
+        }
+        _ => {}
+    }
+}
+
+/// Before draft 2020-12, the array form of `items` is used for tuple validation,
+/// and `additionalItems` applies to the rest of the array.
+fn normalize_tuple_items(
+    object: &mut ObjectNode,
+    dialect: Option<SchemaDialect>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let properties = object.properties.as_inner_mut();
+    let Some(ValueNode::Array(items)) = properties.get("items") else {
+        return;
+    };
+
+    if dialect == Some(SchemaDialect::Draft2020_12) {
+        diagnostics.push(Diagnostic::new_warning(
+            format!(
+                "array form of \"items\" is not supported in JSON Schema {}, use \"prefixItems\" instead",
+                SchemaDialect::Draft2020_12
+            ),
+            items.range,
+        ));
+        properties.shift_remove("items");
+        return;
+    }
+
+    let Some((key, prefix_items)) = properties.shift_remove_entry("items") else {
+        unreachable!();
+    };
+    let prefix_items_len = match &prefix_items {
+        ValueNode::Array(array) => array.len(),
+        _ => unreachable!(),
+    };
+    properties.insert(
+        StringNode {
+            value: "prefixItems".to_string(),
+            range: key.range,
+        },
+        prefix_items,
+    );
+
+    match properties.shift_remove_entry("additionalItems") {
+        Some((_, additional_items @ ValueNode::Object(_))) => {
+            properties.insert(key, additional_items);
+        }
+        Some((_, ValueNode::Bool(additional_items)))
+            if !additional_items.value && !properties.contains_key("maxItems") =>
+        {
+            properties.insert(
+                StringNode {
+                    value: "maxItems".to_string(),
+                    range: additional_items.range,
+                },
+                ValueNode::Number(NumberNode {
+                    value: (prefix_items_len as u64).into(),
+                    range: additional_items.range,
+                }),
+            );
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn normalize(json: &str) -> (serde_json::Value, Vec<String>) {
+        let ValueNode::Object(mut object) = ValueNode::from_str(json).unwrap() else {
+            unreachable!();
+        };
+        let dialect = object
+            .get("$schema")
+            .and_then(|value| value.as_str())
+            .and_then(SchemaDialect::from_url);
+
+        let mut diagnostics = vec![];
+        normalize_schema(&mut object, dialect, &mut diagnostics);
+
+        let value: tombi_json::Value = ValueNode::Object(object).into();
+        (
+            serde_json::to_value(value).unwrap(),
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message().to_string())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn detect_dialect() {
+        pretty_assertions::assert_eq!(
+            SchemaDialect::from_url("http://json-schema.org/draft-04/schema#"),
+            Some(SchemaDialect::Draft04)
+        );
+        pretty_assertions::assert_eq!(
+            SchemaDialect::from_url("http://json-schema.org/draft-07/schema"),
+            Some(SchemaDialect::Draft07)
+        );
+        pretty_assertions::assert_eq!(
+            SchemaDialect::from_url("https://json-schema.org/draft/2020-12/schema"),
+            Some(SchemaDialect::Draft2020_12)
+        );
+        pretty_assertions::assert_eq!(SchemaDialect::from_url("https://example.com/schema"), None);
+    }
+
+    #[test]
+    fn draft04_exclusive_minimum() {
+        let (schema, diagnostics) = normalize(
+            r#"{
+                "$schema": "http://json-schema.org/draft-04/schema#",
+                "properties": {
+                    "a": { "type": "integer", "minimum": 1, "exclusiveMinimum": true },
+                    "b": { "type": "integer", "maximum": 1, "exclusiveMaximum": false },
+                    "c": { "type": "integer", "exclusiveMinimum": 1 }
+                }
+            }"#,
+        );
+        pretty_assertions::assert_eq!(
+            schema["properties"],
+            serde_json::json!({
+                "a": { "type": "integer", "exclusiveMinimum": 1 },
+                "b": { "type": "integer", "maximum": 1 },
+                "c": { "type": "integer" }
+            })
+        );
+        pretty_assertions::assert_eq!(
+            diagnostics,
+            vec!["\"exclusiveMinimum\" must be a boolean in JSON Schema draft-04"]
+        );
+    }
+
+    #[test]
+    fn draft07_tuple_items() {
+        let (schema, diagnostics) = normalize(
+            r#"{
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "$defs": {},
+                "type": "array",
+                "items": [{ "type": "string" }, { "type": "integer" }],
+                "additionalItems": false
+            }"#,
+        );
+        pretty_assertions::assert_eq!(
+            schema,
+            serde_json::json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "$defs": {},
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, { "type": "integer" }],
+                "maxItems": 2
+            })
+        );
+        pretty_assertions::assert_eq!(
+            diagnostics,
+            vec!["\"$defs\" is not supported in JSON Schema draft-07"]
+        );
+    }
+
+    #[test]
+    fn draft2020_12_definitions() {
+        let (_, diagnostics) = normalize(
+            r##"{
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "a": { "$ref": "#/definitions/a" }
+                },
+                "definitions": {
+                    "a": { "type": "string" }
+                }
+            }"##,
+        );
+        pretty_assertions::assert_eq!(diagnostics, Vec::<String>::new());
+    }
+
+    #[test]
+    fn draft2020_12_items() {
+        let (schema, diagnostics) = normalize(
+            r#"{
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "array",
+                "items": [{ "type": "string" }],
+                "additionalItems": { "type": "string" }
+            }"#,
+        );
+        pretty_assertions::assert_eq!(
+            schema,
+            serde_json::json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "array"
+            })
+        );
+        pretty_assertions::assert_eq!(
+            diagnostics,
+            vec![
+                "\"additionalItems\" is not supported in JSON Schema draft 2020-12",
+                "array form of \"items\" is not supported in JSON Schema draft 2020-12, use \"prefixItems\" instead",
+            ]
+        );
+    }
+}
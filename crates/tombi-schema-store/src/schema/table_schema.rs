@@ -4,7 +4,7 @@ use ahash::AHashMap;
 use indexmap::IndexMap;
 use tombi_future::{BoxFuture, Boxable};
 use tombi_json::StringNode;
-use tombi_x_keyword::{TableKeysOrder, X_TOMBI_TABLE_KEYS_ORDER};
+use tombi_x_keyword::{TableKeysOrder, TableStyle, X_TOMBI_TABLE_KEYS_ORDER, X_TOMBI_TABLE_STYLE};
 
 use super::{
     CurrentSchema, FindSchemaCandidates, PropertySchema, SchemaAccessor, SchemaDefinitions,
@@ -25,6 +25,7 @@ pub struct TableSchema {
     pub min_properties: Option<usize>,
     pub max_properties: Option<usize>,
     pub keys_order: Option<TableKeysOrder>,
+    pub style: Option<TableStyle>,
     pub default: Option<tombi_json::Object>,
     pub enumerate: Option<Vec<tombi_json::Object>>,
     pub examples: Option<Vec<tombi_json::Object>>,
@@ -101,6 +102,23 @@ impl TableSchema {
             None => None,
         };
 
+        let style = match object_node.get(X_TOMBI_TABLE_STYLE) {
+            Some(tombi_json::ValueNode::String(StringNode { value: style, .. })) => {
+                match TableStyle::try_from(style.as_str()) {
+                    Ok(val) => Some(val),
+                    Err(_) => {
+                        tracing::error!("invalid {X_TOMBI_TABLE_STYLE}: {style}");
+                        None
+                    }
+                }
+            }
+            Some(style) => {
+                tracing::error!("invalid {X_TOMBI_TABLE_STYLE}: {}", style.to_string());
+                None
+            }
+            None => None,
+        };
+
         Self {
             title: object_node
                 .get("title")
@@ -145,6 +163,7 @@ impl TableSchema {
                 .get("maxProperties")
                 .and_then(|v| v.as_u64().map(|u| u as usize)),
             keys_order,
+            style,
             enumerate: object_node.get("enum").and_then(|v| v.as_array()).map(|v| {
                 v.items
                     .iter()
@@ -0,0 +1,130 @@
+//! Source formats of schemas and catalogs.
+//!
+//! Schemas and catalogs may be authored in JSON, YAML or TOML.
+//! Every format is loaded into the same [`tombi_json::ValueNode`] tree,
+//! keeping the ranges of the source file.
+
+mod toml;
+mod yaml;
+
+/// The source format of a schema or a catalog.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl SchemaFormat {
+    /// Detect the format from the extension of the url path.
+    pub fn from_url(url: &url::Url) -> Option<Self> {
+        let file_name = url.path_segments()?.next_back()?;
+        let (_, extension) = file_name.rsplit_once('.')?;
+
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    /// Detect the format from the `Content-Type` header of the response.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        match mime_type.as_str() {
+            "application/json" | "application/schema+json" => Some(Self::Json),
+            "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml" => {
+                Some(Self::Yaml)
+            }
+            "application/toml" | "text/toml" | "text/x-toml" => Some(Self::Toml),
+            _ if mime_type.ends_with("+json") => Some(Self::Json),
+            _ if mime_type.ends_with("+yaml") => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Detect the format from the url extension, then from the `Content-Type`.
+    ///
+    /// Falls back to JSON, because most of the servers return `text/plain` for raw files.
+    pub fn detect(url: &url::Url, content_type: Option<&str>) -> Self {
+        Self::from_url(url)
+            .or_else(|| content_type.and_then(Self::from_content_type))
+            .unwrap_or_default()
+    }
+
+    /// Parse the source into a JSON value, keeping the source ranges.
+    pub fn parse(&self, source: &str) -> Result<tombi_json::ValueNode, String> {
+        match self {
+            Self::Json => tombi_json::parse(source).map_err(|err| err.to_string()),
+            Self::Yaml => yaml::parse(source),
+            Self::Toml => toml::parse(source),
+        }
+    }
+
+    /// Parse the bytes into a JSON value, keeping the source ranges.
+    pub fn parse_bytes(&self, bytes: &[u8]) -> Result<tombi_json::ValueNode, String> {
+        let source = std::str::from_utf8(bytes).map_err(|err| err.to_string())?;
+        self.parse(source)
+    }
+}
+
+impl std::fmt::Display for SchemaFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json => write!(f, "JSON"),
+            Self::Yaml => write!(f, "YAML"),
+            Self::Toml => write!(f, "TOML"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_from_url() {
+        for (url, expected) in [
+            ("https://example.com/schema.json", SchemaFormat::Json),
+            ("https://example.com/schema.yaml", SchemaFormat::Yaml),
+            ("https://example.com/schema.YML", SchemaFormat::Yaml),
+            ("file:///schemas/schema.toml", SchemaFormat::Toml),
+            ("https://example.com/schema", SchemaFormat::Json),
+        ] {
+            let url = url::Url::parse(url).unwrap();
+            pretty_assertions::assert_eq!(SchemaFormat::detect(&url, None), expected);
+        }
+    }
+
+    #[test]
+    fn detect_from_content_type() {
+        let url = url::Url::parse("https://example.com/schema").unwrap();
+        for (content_type, expected) in [
+            ("application/json; charset=utf-8", SchemaFormat::Json),
+            ("application/schema+json", SchemaFormat::Json),
+            ("application/yaml", SchemaFormat::Yaml),
+            ("text/x-yaml", SchemaFormat::Yaml),
+            ("application/toml", SchemaFormat::Toml),
+            ("text/plain", SchemaFormat::Json),
+        ] {
+            pretty_assertions::assert_eq!(SchemaFormat::detect(&url, Some(content_type)), expected);
+        }
+    }
+
+    #[test]
+    fn url_extension_takes_precedence_over_content_type() {
+        let url = url::Url::parse("https://example.com/schema.yaml").unwrap();
+        pretty_assertions::assert_eq!(
+            SchemaFormat::detect(&url, Some("text/plain")),
+            SchemaFormat::Yaml
+        );
+    }
+}
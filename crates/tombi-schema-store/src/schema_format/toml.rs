@@ -0,0 +1,164 @@
+use tombi_config::TomlVersion;
+use tombi_document_tree::IntoDocumentTreeAndErrors;
+use tombi_json::{ArrayNode, BoolNode, NullNode, NumberNode, ObjectNode, StringNode, ValueNode};
+
+/// TOML-authored schemas can use all the features of the latest TOML version.
+const TOML_VERSION: TomlVersion = TomlVersion::latest();
+
+pub(super) fn parse(source: &str) -> Result<ValueNode, String> {
+    let (root, errors) = tombi_parser::parse(source, TOML_VERSION).into_root_and_errors();
+    if let Some(error) = errors.first() {
+        return Err(error_message(&error.to_message(), error.range()));
+    }
+
+    let document_tree = root.into_document_tree_and_errors(TOML_VERSION);
+    if let Some(error) = document_tree.errors.first() {
+        return Err(error_message(&error.to_message(), error.range()));
+    }
+
+    let mut object = table_to_object(document_tree.tree.into());
+    // The root table covers the whole document, like the root object of a JSON file.
+    object.range = tombi_text::Range::new(
+        tombi_text::Position::MIN,
+        tombi_text::Position::MIN.add_text(source),
+    );
+
+    Ok(ValueNode::Object(object))
+}
+
+fn error_message(message: &str, range: tombi_text::Range) -> String {
+    format!(
+        "{message} at line {}, column {}",
+        range.start.line + 1,
+        range.start.column + 1
+    )
+}
+
+fn table_to_object(table: tombi_document_tree::Table) -> ObjectNode {
+    let range = table.range();
+    let properties = table
+        .into_iter()
+        .map(|(key, value)| {
+            (
+                StringNode {
+                    value: key.to_raw_text(TOML_VERSION),
+                    range: key.range(),
+                },
+                value_to_node(value),
+            )
+        })
+        .collect();
+
+    ObjectNode { properties, range }
+}
+
+fn value_to_node(value: tombi_document_tree::Value) -> ValueNode {
+    let range = value.range();
+    match value {
+        tombi_document_tree::Value::Boolean(boolean) => ValueNode::Bool(BoolNode {
+            value: boolean.value(),
+            range,
+        }),
+        tombi_document_tree::Value::Integer(integer) => ValueNode::Number(NumberNode {
+            value: tombi_json::Number::from_i64(integer.value()),
+            range,
+        }),
+        tombi_document_tree::Value::Float(float) => ValueNode::Number(NumberNode {
+            value: tombi_json::Number::from_f64(float.value()),
+            range,
+        }),
+        tombi_document_tree::Value::String(string) => ValueNode::String(StringNode {
+            value: string.into_value(),
+            range,
+        }),
+        tombi_document_tree::Value::OffsetDateTime(date_time) => ValueNode::String(StringNode {
+            value: date_time.value().to_string(),
+            range,
+        }),
+        tombi_document_tree::Value::LocalDateTime(date_time) => ValueNode::String(StringNode {
+            value: date_time.value().to_string(),
+            range,
+        }),
+        tombi_document_tree::Value::LocalDate(date) => ValueNode::String(StringNode {
+            value: date.value().to_string(),
+            range,
+        }),
+        tombi_document_tree::Value::LocalTime(time) => ValueNode::String(StringNode {
+            value: time.value().to_string(),
+            range,
+        }),
+        tombi_document_tree::Value::Array(array) => ValueNode::Array(ArrayNode {
+            items: array.into_iter().map(value_to_node).collect(),
+            range,
+        }),
+        tombi_document_tree::Value::Table(table) => ValueNode::Object(table_to_object(table)),
+        tombi_document_tree::Value::Incomplete { .. } => ValueNode::Null(NullNode { range }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_values() {
+        let source = textwrap::dedent(
+            r#"
+            type = "object"
+            required = ["name"]
+
+            [properties.name]
+            minLength = 1
+            default = 1979-05-27
+
+            [properties.ratio]
+            maximum = 0.5
+            "#,
+        );
+        let value = tombi_json::Value::from(parse(source.trim_start()).unwrap());
+
+        pretty_assertions::assert_eq!(
+            value,
+            tombi_json::Value::from(
+                tombi_json::parse(
+                    r#"{
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"minLength": 1, "default": "1979-05-27"},
+                            "ratio": {"maximum": 0.5}
+                        }
+                    }"#
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_ranges() {
+        let source = "[properties.name]\ntype = \"string\"\n";
+        let ValueNode::Object(object) = parse(source).unwrap() else {
+            panic!("expected object");
+        };
+        let name = object
+            .get("properties")
+            .and_then(|properties| properties.as_object())
+            .and_then(|properties| properties.get("name"))
+            .and_then(|name| name.as_object())
+            .unwrap();
+
+        pretty_assertions::assert_eq!(
+            name.get("type").unwrap().range(),
+            tombi_text::Range::new(
+                tombi_text::Position::new(1, 7),
+                tombi_text::Position::new(1, 15)
+            )
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(parse("type = ").is_err());
+    }
+}
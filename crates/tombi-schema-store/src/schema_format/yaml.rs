@@ -0,0 +1,164 @@
+use tombi_json::{ArrayNode, BoolNode, NullNode, NumberNode, ObjectNode, StringNode, ValueNode};
+use tombi_yaml::{Node, Scalar, ScalarNode};
+
+pub(super) fn parse(source: &str) -> Result<ValueNode, String> {
+    match tombi_yaml::parse(source).map_err(|err| err.to_string())? {
+        Some(node) => Ok(node_to_value_node(node)),
+        // An empty document is null, like in the other YAML loaders.
+        None => Ok(ValueNode::Null(NullNode {
+            range: tombi_text::Range::default(),
+        })),
+    }
+}
+
+fn node_to_value_node(node: Node) -> ValueNode {
+    match node {
+        Node::Scalar(scalar) => scalar_to_node(scalar),
+        Node::Sequence(sequence) => ValueNode::Array(ArrayNode {
+            items: sequence.items.into_iter().map(node_to_value_node).collect(),
+            range: sequence.range,
+        }),
+        Node::Mapping(mapping) => {
+            let mut object = ObjectNode {
+                properties: Default::default(),
+                range: mapping.range,
+            };
+            for (key, value) in mapping.entries {
+                object.properties.insert(
+                    StringNode {
+                        value: key.value,
+                        range: key.range,
+                    },
+                    node_to_value_node(value),
+                );
+            }
+            ValueNode::Object(object)
+        }
+    }
+}
+
+fn scalar_to_node(scalar: ScalarNode) -> ValueNode {
+    let range = scalar.range;
+    match scalar.resolve() {
+        Some(Scalar::Null) => ValueNode::Null(NullNode { range }),
+        Some(Scalar::Boolean(value)) => ValueNode::Bool(BoolNode { value, range }),
+        Some(Scalar::Integer(value)) => ValueNode::Number(NumberNode {
+            value: tombi_json::Number::from_i64(value),
+            range,
+        }),
+        Some(Scalar::Float(value)) => ValueNode::Number(NumberNode {
+            value: tombi_json::Number::from_f64(value),
+            range,
+        }),
+        None => ValueNode::String(StringNode {
+            value: scalar.value,
+            range,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_values() {
+        let source = textwrap::dedent(
+            r#"
+            type: object
+            "required": ['name']
+            properties:
+              name:
+                minLength: 1
+                default: null
+              enabled:
+                default: true
+              ratio: {maximum: 0.5}
+            "#,
+        );
+        let value = tombi_json::Value::from(parse(source.trim_start()).unwrap());
+
+        pretty_assertions::assert_eq!(
+            value,
+            tombi_json::Value::from(
+                tombi_json::parse(
+                    r#"{
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"minLength": 1, "default": null},
+                            "enabled": {"default": true},
+                            "ratio": {"maximum": 0.5}
+                        }
+                    }"#
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_anchor() {
+        let source = textwrap::dedent(
+            r#"
+            definitions:
+              name: &name
+                type: string
+            properties:
+              first: *name
+            "#,
+        );
+        let value = tombi_json::Value::from(parse(source.trim_start()).unwrap());
+
+        pretty_assertions::assert_eq!(
+            value,
+            tombi_json::Value::from(
+                tombi_json::parse(
+                    r#"{
+                        "definitions": {"name": {"type": "string"}},
+                        "properties": {"first": {"type": "string"}}
+                    }"#
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_merge_key() {
+        let source = textwrap::dedent(
+            r#"
+            definitions:
+              base: &base
+                type: string
+            properties:
+              name:
+                <<: *base
+                minLength: 1
+            "#,
+        );
+        let value = tombi_json::Value::from(parse(source.trim_start()).unwrap());
+
+        pretty_assertions::assert_eq!(
+            value,
+            tombi_json::Value::from(
+                tombi_json::parse(
+                    r#"{
+                        "definitions": {"base": {"type": "string"}},
+                        "properties": {"name": {"minLength": 1, "type": "string"}}
+                    }"#
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(parse("key: [1, 2").is_err());
+        pretty_assertions::assert_eq!(
+            parse("key: 1\nkey: 2\n").unwrap_err(),
+            "duplicate key at line 2, column 1"
+        );
+    }
+}
@@ -1,14 +1,17 @@
-use std::{ops::Deref, str::FromStr, sync::Arc};
+use std::{ops::Deref, sync::Arc};
 
 use crate::{
+    cache::{read_cache, refresh_cache, save_cache},
     get_tombi_scheme_content,
     json::{CatalogUrl, JsonCatalog},
-    DocumentSchema, HttpClient, SchemaAccessor, SchemaAccessors, SchemaUrl, SourceSchema,
+    CacheValidators, ConditionalResponse, DocumentSchema, FetchError, HttpClient, SchemaAccessor,
+    SchemaAccessors, SchemaFormat, SchemaUrl, SourceSchema,
 };
 use ahash::AHashMap;
+use bytes::Bytes;
 use itertools::Either;
 use tokio::sync::RwLock;
-use tombi_config::{Schema, SchemaOptions};
+use tombi_config::{OverrideItem, Schema, SchemaOptions};
 use tombi_future::{BoxFuture, Boxable};
 use tombi_url::url_to_file_path;
 
@@ -17,7 +20,8 @@ pub struct SchemaStore {
     http_client: HttpClient,
     document_schemas:
         Arc<tokio::sync::RwLock<AHashMap<SchemaUrl, Result<DocumentSchema, crate::Error>>>>,
-    schemas: Arc<RwLock<Vec<crate::Schema>>>,
+    pub(crate) schemas: Arc<RwLock<Vec<crate::Schema>>>,
+    order_overrides: Arc<RwLock<Vec<crate::OrderOverride>>>,
     options: crate::Options,
 }
 
@@ -45,6 +49,7 @@ impl SchemaStore {
             http_client: HttpClient::new(),
             document_schemas: Arc::new(RwLock::default()),
             schemas: Arc::new(RwLock::new(Vec::new())),
+            order_overrides: Arc::new(RwLock::new(Vec::new())),
             options,
         }
     }
@@ -59,6 +64,122 @@ impl SchemaStore {
         self.options.strict.unwrap_or(true)
     }
 
+    /// Cache directory of remote schemas and catalogs
+    fn cache_dir_path(&self) -> Option<std::path::PathBuf> {
+        self.options
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.cache_dir_path())
+    }
+
+    /// Whether the remote url is available in the on-disk cache
+    fn is_cached(&self, url: &url::Url) -> bool {
+        self.cache_dir_path()
+            .is_some_and(|cache_dir_path| read_cache(&cache_dir_path, url).is_some())
+    }
+
+    /// Fetch bytes from the remote url through the on-disk cache,
+    /// with the format detected from the url or the `Content-Type`.
+    ///
+    /// A cached file within the TTL is used without any request, unless `revalidate` is true.
+    /// An expired file is revalidated with `ETag`/`Last-Modified`,
+    /// and is still used if the remote server can not be reached.
+    ///
+    /// Returns `Ok(None)` in offline mode if the url is not cached.
+    async fn fetch_remote_bytes(
+        &self,
+        url: &url::Url,
+        revalidate: bool,
+    ) -> Result<Option<(Bytes, SchemaFormat)>, FetchError> {
+        let Some((cache_dir_path, cache_ttl)) = self.options.cache.as_ref().and_then(|cache| {
+            cache
+                .cache_dir_path()
+                .map(|cache_dir_path| (cache_dir_path, cache.cache_ttl()))
+        }) else {
+            if self.offline() {
+                return Ok(None);
+            }
+            return self.fetch_remote_bytes_without_cache(url).await.map(Some);
+        };
+
+        let cache = read_cache(&cache_dir_path, url);
+        if let Some((metadata, bytes)) = &cache {
+            if self.offline() || (!revalidate && !metadata.is_expired(cache_ttl)) {
+                tracing::debug!("use cached file of url: {}", url);
+                return Ok(Some((
+                    bytes.clone(),
+                    SchemaFormat::detect(url, metadata.content_type.as_deref()),
+                )));
+            }
+        } else if self.offline() {
+            return Ok(None);
+        }
+
+        let validators = cache
+            .as_ref()
+            .map(|(metadata, _)| metadata.validators.clone())
+            .unwrap_or_default();
+
+        match self
+            .http_client
+            .get_bytes_if_modified(url.as_str(), &validators)
+            .await
+        {
+            Ok(ConditionalResponse::Modified {
+                bytes,
+                validators,
+                content_type,
+            }) => {
+                let format = SchemaFormat::detect(url, content_type.as_deref());
+                if let Err(err) = save_cache(&cache_dir_path, url, &bytes, validators, content_type)
+                {
+                    tracing::warn!("failed to save cache of url: {}, reason: {}", url, err);
+                }
+                Ok(Some((bytes, format)))
+            }
+            Ok(ConditionalResponse::NotModified) => match cache {
+                Some((metadata, bytes)) => {
+                    tracing::debug!("cached file is not modified: {}", url);
+                    let format = SchemaFormat::detect(url, metadata.content_type.as_deref());
+                    if let Err(err) = refresh_cache(&cache_dir_path, url, metadata) {
+                        tracing::warn!("failed to refresh cache of url: {}, reason: {}", url, err);
+                    }
+                    Ok(Some((bytes, format)))
+                }
+                None => self.fetch_remote_bytes_without_cache(url).await.map(Some),
+            },
+            Err(err) => match cache {
+                Some((metadata, bytes)) => {
+                    tracing::warn!("use stale cached file of url: {}, reason: {}", url, err);
+                    Ok(Some((
+                        bytes,
+                        SchemaFormat::detect(url, metadata.content_type.as_deref()),
+                    )))
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    async fn fetch_remote_bytes_without_cache(
+        &self,
+        url: &url::Url,
+    ) -> Result<(Bytes, SchemaFormat), FetchError> {
+        match self
+            .http_client
+            .get_bytes_if_modified(url.as_str(), &CacheValidators::default())
+            .await?
+        {
+            ConditionalResponse::Modified {
+                bytes,
+                content_type,
+                ..
+            } => Ok((bytes, SchemaFormat::detect(url, content_type.as_deref()))),
+            // Without validators, the server never answers `304 Not Modified`.
+            ConditionalResponse::NotModified => Err(FetchError::StatusNotOk { status: 304 }),
+        }
+    }
+
     pub async fn load_config(
         &self,
         config: &tombi_config::Config,
@@ -70,6 +191,12 @@ impl SchemaStore {
             None => &SchemaOptions::default(),
         };
 
+        self.load_overrides(match &config.overrides {
+            Some(overrides) => overrides,
+            None => &[],
+        })
+        .await;
+
         if schema_options.enabled.unwrap_or_default().value() {
             self.load_schemas(
                 match &config.schemas {
@@ -134,29 +261,47 @@ impl SchemaStore {
         .await;
     }
 
+    pub async fn load_overrides(&self, overrides: &[OverrideItem]) {
+        let mut order_overrides = self.order_overrides.write().await;
+        for override_item in overrides {
+            for key in &override_item.keys {
+                // NOTE: An empty key targets the root table.
+                let accessors = if key.is_empty() {
+                    Vec::with_capacity(0)
+                } else if let Some(accessors) = SchemaAccessor::parse(key) {
+                    accessors
+                } else {
+                    tracing::error!("invalid override keys: {}", key);
+                    continue;
+                };
+
+                order_overrides.push(crate::OrderOverride {
+                    include: override_item.include.clone(),
+                    accessors,
+                    table_keys_order: override_item.table_keys_order,
+                    array_values_order: override_item.array_values_order.clone(),
+                });
+            }
+        }
+    }
+
     async fn load_json_catalog_from_catalog_url(
         &self,
         catalog_url: &CatalogUrl,
     ) -> Result<Option<JsonCatalog>, crate::Error> {
         Ok(Some(match catalog_url.scheme() {
             "http" | "https" => {
-                if self.offline() {
-                    tracing::debug!("offline mode, skip fetch catalog from url: {}", catalog_url);
-                    return Ok(None);
-                }
                 tracing::debug!("loading schema catalog: {}", catalog_url);
 
-                match self.http_client.get_bytes(catalog_url.as_str()).await {
-                    Ok(result) => match serde_json::from_slice::<crate::json::JsonCatalog>(&result)
-                    {
-                        Ok(catalog) => catalog,
-                        Err(err) => {
-                            return Err(crate::Error::InvalidJsonFormat {
-                                url: catalog_url.deref().clone(),
-                                reason: err.to_string(),
-                            })
-                        }
-                    },
+                match self.fetch_remote_bytes(catalog_url, false).await {
+                    Ok(Some((bytes, format))) => parse_json_catalog(catalog_url, &bytes, format)?,
+                    Ok(None) => {
+                        tracing::debug!(
+                            "offline mode, skip fetch catalog from url: {}",
+                            catalog_url
+                        );
+                        return Ok(None);
+                    }
                     Err(err) => {
                         return Err(crate::Error::CatalogUrlFetchFailed {
                             catalog_url: catalog_url.clone(),
@@ -172,16 +317,17 @@ impl SchemaStore {
                     }
                 })?;
 
-                let content = std::fs::read_to_string(&catalog_path).map_err(|_| {
+                let bytes = std::fs::read(&catalog_path).map_err(|_| {
                     crate::Error::CatalogFileReadFailed {
                         catalog_path: catalog_path.to_path_buf(),
                     }
                 })?;
 
-                serde_json::from_str(&content).map_err(|err| crate::Error::InvalidJsonFormat {
-                    url: catalog_url.deref().clone(),
-                    reason: err.to_string(),
-                })?
+                parse_json_catalog(
+                    catalog_url,
+                    &bytes,
+                    SchemaFormat::from_url(catalog_url).unwrap_or_default(),
+                )?
             }
             "tombi" => {
                 if catalog_url.path() != "/json/catalog.json" {
@@ -235,7 +381,7 @@ impl SchemaStore {
         if has_key {
             self.document_schemas.write().await.insert(
                 schema_url.clone(),
-                self.fetch_document_schema(schema_url).await,
+                self.fetch_document_schema(schema_url, true).await,
             );
 
             tracing::debug!("update schema: {}", schema_url);
@@ -246,11 +392,13 @@ impl SchemaStore {
         }
     }
 
-    async fn fetch_document_schema(
+    /// Fetch the raw content of the schema, with its format.
+    pub(crate) async fn fetch_schema_bytes(
         &self,
         schema_url: &SchemaUrl,
-    ) -> Result<DocumentSchema, crate::Error> {
-        let tombi_json::ValueNode::Object(schema) = match schema_url.scheme() {
+        revalidate: bool,
+    ) -> Result<(Bytes, SchemaFormat), crate::Error> {
+        match schema_url.scheme() {
             "file" => {
                 let schema_path =
                     url_to_file_path(schema_url).map_err(|_| crate::Error::InvalidSchemaUrl {
@@ -261,29 +409,28 @@ impl SchemaStore {
                         schema_path: schema_path.clone(),
                     });
                 }
-                let file = std::fs::File::open(&schema_path)
+
+                let bytes = std::fs::read(&schema_path)
                     .map_err(|_| crate::Error::SchemaFileReadFailed { schema_path })?;
 
-                tombi_json::ValueNode::from_reader(file)
+                Ok((
+                    Bytes::from(bytes),
+                    SchemaFormat::from_url(schema_url).unwrap_or_default(),
+                ))
             }
             "http" | "https" => {
-                assert!(
-                    !self.offline(),
-                    "offline mode, store don't have online schema url: {schema_url}",
-                );
-
                 tracing::debug!("fetch schema from url: {}", schema_url);
 
-                let bytes = self
-                    .http_client
-                    .get_bytes(schema_url.as_ref())
+                self.fetch_remote_bytes(schema_url, revalidate)
                     .await
                     .map_err(|err| crate::Error::SchemaFetchFailed {
                         schema_url: schema_url.clone(),
                         reason: err.to_string(),
-                    })?;
-
-                tombi_json::ValueNode::from_reader(std::io::Cursor::new(bytes))
+                    })?
+                    .ok_or_else(|| crate::Error::SchemaFetchFailed {
+                        schema_url: schema_url.clone(),
+                        reason: "offline mode, and the schema is not cached".to_string(),
+                    })
             }
             "tombi" => {
                 let Some(content) = get_tombi_scheme_content(schema_url) else {
@@ -291,25 +438,71 @@ impl SchemaStore {
                         schema_url: schema_url.to_owned(),
                     });
                 };
-                tombi_json::ValueNode::from_str(content)
-            }
-            _ => {
-                return Err(crate::Error::UnsupportedSchemaUrl {
-                    schema_url: schema_url.to_owned(),
-                })
+                Ok((Bytes::from_static(content.as_bytes()), SchemaFormat::Json))
             }
+            _ => Err(crate::Error::UnsupportedSchemaUrl {
+                schema_url: schema_url.to_owned(),
+            }),
         }
-        .map_err(|err| crate::Error::SchemaFileParseFailed {
-            schema_url: schema_url.to_owned(),
-            reason: err.to_string(),
-        })?
+    }
+
+    /// Get the schema document as a JSON value, keeping the source ranges.
+    ///
+    /// Returns `None` in offline mode if the remote schema is not cached.
+    pub async fn try_get_schema_value(
+        &self,
+        schema_url: &SchemaUrl,
+    ) -> Result<Option<tombi_json::ValueNode>, crate::Error> {
+        if self.offline()
+            && matches!(schema_url.scheme(), "http" | "https")
+            && !self.is_cached(schema_url)
+        {
+            return Ok(None);
+        }
+
+        self.fetch_schema_value(schema_url, false).await.map(Some)
+    }
+
+    /// Fetch the schema and parse it as a JSON value, whatever the source format is.
+    pub(crate) async fn fetch_schema_value(
+        &self,
+        schema_url: &SchemaUrl,
+        revalidate: bool,
+    ) -> Result<tombi_json::ValueNode, crate::Error> {
+        let (bytes, format) = self.fetch_schema_bytes(schema_url, revalidate).await?;
+
+        format
+            .parse_bytes(&bytes)
+            .map_err(|reason| crate::Error::SchemaFileParseFailed {
+                schema_url: schema_url.to_owned(),
+                reason,
+            })
+    }
+
+    async fn fetch_document_schema(
+        &self,
+        schema_url: &SchemaUrl,
+        revalidate: bool,
+    ) -> Result<DocumentSchema, crate::Error> {
+        let tombi_json::ValueNode::Object(schema) =
+            self.fetch_schema_value(schema_url, revalidate).await?
         else {
             return Err(crate::Error::SchemaMustBeObject {
                 schema_url: schema_url.to_owned(),
             });
         };
 
-        Ok(DocumentSchema::new(schema, schema_url.clone()))
+        let document_schema = DocumentSchema::new(schema, schema_url.clone());
+        for diagnostic in &document_schema.diagnostics {
+            tracing::warn!(
+                "{schema_url}:{}:{}: {}",
+                diagnostic.position().line + 1,
+                diagnostic.position().column + 1,
+                diagnostic.message()
+            );
+        }
+
+        Ok(document_schema)
     }
 
     pub fn try_get_document_schema<'a: 'b, 'b>(
@@ -317,7 +510,10 @@ impl SchemaStore {
         schema_url: &'a SchemaUrl,
     ) -> BoxFuture<'b, Result<Option<DocumentSchema>, crate::Error>> {
         async move {
-            if self.offline() && matches!(schema_url.scheme(), "http" | "https") {
+            if self.offline()
+                && matches!(schema_url.scheme(), "http" | "https")
+                && !self.is_cached(schema_url)
+            {
                 return Ok(None);
             }
 
@@ -328,7 +524,7 @@ impl SchemaStore {
                 };
             }
 
-            let document_schema = self.fetch_document_schema(schema_url).await;
+            let document_schema = self.fetch_document_schema(schema_url, false).await;
 
             self.document_schemas
                 .write()
@@ -340,15 +536,18 @@ impl SchemaStore {
         .boxed()
     }
 
-    #[inline]
-    async fn try_get_source_schema_from_remote_url(
+    /// Resolve the source schema of the schema URL given explicitly, such as by `#:schema`.
+    pub async fn resolve_source_schema_from_schema_url(
         &self,
         schema_url: &SchemaUrl,
     ) -> Result<Option<SourceSchema>, crate::Error> {
-        Ok(Some(SourceSchema {
-            root_schema: self.try_get_document_schema(schema_url).await?,
-            sub_schema_url_map: Default::default(),
-        }))
+        Ok(self
+            .try_get_document_schema(schema_url)
+            .await?
+            .map(|document_schema| SourceSchema {
+                root_schema: Some(document_schema),
+                sub_schema_url_map: Default::default(),
+            }))
     }
 
     pub async fn resolve_source_schema_from_ast(
@@ -378,7 +577,7 @@ impl SchemaStore {
                 }
             };
             return self
-                .try_get_source_schema_from_remote_url(&SchemaUrl::new(schema_url))
+                .resolve_source_schema_from_schema_url(&SchemaUrl::new(schema_url))
                 .await
                 .map_err(|err| (err, url_range));
         }
@@ -401,19 +600,7 @@ impl SchemaStore {
         let schemas = self.schemas.read().await;
         let matching_schemas = schemas
             .iter()
-            .filter(|schema| {
-                schema.include.iter().any(|pat| {
-                    let pattern = if !pat.contains("*") {
-                        format!("**/{}", pat)
-                    } else {
-                        pat.to_string()
-                    };
-                    glob::Pattern::new(&pattern)
-                        .ok()
-                        .map(|glob_pat| glob_pat.matches_path(source_path))
-                        .unwrap_or(false)
-                })
-            })
+            .filter(|schema| matches_include(&schema.include, source_path))
             .collect::<Vec<_>>();
 
         let mut source_schema: Option<SourceSchema> = None;
@@ -477,6 +664,34 @@ impl SchemaStore {
         Ok(source_schema)
     }
 
+    /// Resolve the order overrides of the config matching the source.
+    ///
+    /// The overrides apply even if no schema matches the source.
+    pub async fn resolve_order_overrides(
+        &self,
+        source_url_or_path: Option<Either<&url::Url, &std::path::Path>>,
+    ) -> crate::OrderOverrides {
+        let source_path = match source_url_or_path {
+            Some(Either::Left(url)) => match url.scheme() {
+                "file" => url_to_file_path(url).ok(),
+                _ => None,
+            },
+            Some(Either::Right(path)) => Some(path.to_path_buf()),
+            None => None,
+        };
+        let Some(source_path) = source_path else {
+            return Default::default();
+        };
+
+        self.order_overrides
+            .read()
+            .await
+            .iter()
+            .filter(|order_override| matches_include(&order_override.include, &source_path))
+            .cloned()
+            .collect()
+    }
+
     async fn resolve_source_schema_from_url(
         &self,
         source_url: &url::Url,
@@ -532,3 +747,79 @@ impl SchemaStore {
         });
     }
 }
+
+fn matches_include(include: &[String], source_path: &std::path::Path) -> bool {
+    include.iter().any(|pat| {
+        let pattern = if !pat.contains("*") {
+            format!("**/{}", pat)
+        } else {
+            pat.to_string()
+        };
+        glob::Pattern::new(&pattern)
+            .ok()
+            .map(|glob_pat| glob_pat.matches_path(source_path))
+            .unwrap_or(false)
+    })
+}
+
+/// Parse the catalog, resolving the relative schema urls against the catalog url.
+fn parse_json_catalog(
+    catalog_url: &CatalogUrl,
+    bytes: &[u8],
+    format: SchemaFormat,
+) -> Result<JsonCatalog, crate::Error> {
+    format
+        .parse_bytes(bytes)
+        .and_then(|value| {
+            let mut value = serde_json::to_value(tombi_json::Value::from(value))
+                .map_err(|err| err.to_string())?;
+            if let Some(schemas) = value
+                .get_mut("schemas")
+                .and_then(serde_json::Value::as_array_mut)
+            {
+                for schema in schemas {
+                    if let Some(serde_json::Value::String(url)) = schema.get_mut("url") {
+                        if let Ok(schema_url) = catalog_url.join(url) {
+                            *url = schema_url.to_string();
+                        }
+                    }
+                }
+            }
+            serde_json::from_value(value).map_err(|err| err.to_string())
+        })
+        .map_err(|reason| crate::Error::InvalidCatalogFormat {
+            catalog_url: catalog_url.clone(),
+            format,
+            reason,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_json_catalog_with_relative_urls() {
+        let catalog_url = CatalogUrl::new(url::Url::parse("file:///vendor/catalog.json").unwrap());
+        let catalog = parse_json_catalog(
+            &catalog_url,
+            br#"{
+                "schemas": [
+                    { "name": "a", "description": "", "fileMatch": ["a.toml"], "url": "./a.json" },
+                    { "name": "b", "description": "", "url": "https://example.com/b.json" }
+                ]
+            }"#,
+            SchemaFormat::Json,
+        )
+        .unwrap();
+
+        pretty_assertions::assert_eq!(
+            catalog
+                .schemas
+                .iter()
+                .map(|schema| schema.url.as_str())
+                .collect::<Vec<_>>(),
+            vec!["file:///vendor/a.json", "https://example.com/b.json"]
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Vendoring of schemas for hermetic builds.
+//!
+//! The schemas and their transitive `$ref`s are written into a local directory,
+//! and the references between them are rewritten to relative paths,
+//! so that the directory can be committed and used without network access.
+
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+use ahash::{AHashMap, AHashSet};
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+use crate::{get_schema_name, SchemaStore, SchemaUrl};
+
+/// File name of the catalog written into the vendor directory.
+pub const VENDORED_CATALOG_FILENAME: &str = "catalog.json";
+
+#[derive(Debug, Clone)]
+pub struct VendoredSchema {
+    /// The original url of the schema.
+    pub schema_url: SchemaUrl,
+
+    /// The path of the vendored schema file.
+    pub file_path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct VendorResult {
+    /// The path of the generated catalog.
+    pub catalog_path: PathBuf,
+
+    /// The vendored schemas, including the transitive references.
+    pub schemas: Vec<VendoredSchema>,
+}
+
+impl SchemaStore {
+    /// Vendor the schemas and their transitive `$ref`s into the output directory.
+    ///
+    /// The generated catalog contains the root schemas with their file match patterns in the store,
+    /// and refers to the vendored schemas by the paths relative to the catalog,
+    /// so that the output directory can be moved.
+    /// The schemas without file match patterns, used by `#:schema` for example, are also listed.
+    pub async fn vendor_schemas(
+        &self,
+        schema_urls: &[SchemaUrl],
+        output_dir_path: &Path,
+    ) -> Result<VendorResult, crate::Error> {
+        let mut documents = IndexMap::<SchemaUrl, serde_json::Value>::new();
+        let mut queue = schema_urls
+            .iter()
+            .map(|schema_url| document_url(schema_url))
+            .collect::<VecDeque<_>>();
+
+        while let Some(schema_url) = queue.pop_front() {
+            if documents.contains_key(&schema_url) {
+                continue;
+            }
+            tracing::debug!("vendor schema: {}", schema_url);
+
+            // YAML and TOML schemas are vendored as JSON.
+            let value = self.fetch_schema_value(&schema_url, false).await?;
+            let document = serde_json::to_value(tombi_json::Value::from(value)).map_err(|err| {
+                crate::Error::SchemaFileParseFailed {
+                    schema_url: schema_url.clone(),
+                    reason: err.to_string(),
+                }
+            })?;
+
+            visit_references(&document, &mut |reference| {
+                if let Some(reference_url) = resolve_reference_url(&schema_url, reference) {
+                    queue.push_back(reference_url);
+                }
+            });
+            documents.insert(schema_url, document);
+        }
+
+        let mut used_file_names = AHashSet::from([VENDORED_CATALOG_FILENAME.to_string()]);
+        let file_names = documents
+            .keys()
+            .map(|schema_url| {
+                (
+                    schema_url.clone(),
+                    unique_file_name(schema_url, &mut used_file_names),
+                )
+            })
+            .collect::<AHashMap<_, _>>();
+
+        std::fs::create_dir_all(output_dir_path).map_err(|err| {
+            crate::Error::VendorFileWriteFailed {
+                path: output_dir_path.to_path_buf(),
+                reason: err.to_string(),
+            }
+        })?;
+        let output_dir_path =
+            output_dir_path
+                .canonicalize()
+                .map_err(|err| crate::Error::VendorFileWriteFailed {
+                    path: output_dir_path.to_path_buf(),
+                    reason: err.to_string(),
+                })?;
+
+        let mut vendored_schemas = Vec::with_capacity(documents.len());
+        for (schema_url, mut document) in documents {
+            rewrite_references(&mut document, &mut |reference| {
+                let reference_url = resolve_reference_url(&schema_url, reference)?;
+                let file_name = file_names.get(&reference_url)?;
+                Some(match reference.split_once('#') {
+                    Some((_, fragment)) => format!("{file_name}#{fragment}"),
+                    None => file_name.to_owned(),
+                })
+            });
+
+            let file_path = output_dir_path.join(&file_names[&schema_url]);
+            write_json(&file_path, &document)?;
+
+            vendored_schemas.push(VendoredSchema {
+                schema_url,
+                file_path,
+            });
+        }
+
+        let catalog = self.vendored_catalog(schema_urls, &vendored_schemas).await;
+        let catalog_path = output_dir_path.join(VENDORED_CATALOG_FILENAME);
+        write_json(&catalog_path, &catalog)?;
+
+        Ok(VendorResult {
+            catalog_path,
+            schemas: vendored_schemas,
+        })
+    }
+
+    async fn vendored_catalog(
+        &self,
+        schema_urls: &[SchemaUrl],
+        vendored_schemas: &[VendoredSchema],
+    ) -> serde_json::Value {
+        let schemas = self.schemas.read().await;
+
+        let catalog_schemas = schema_urls
+            .iter()
+            .unique()
+            .filter_map(|schema_url| {
+                let file_match = schemas
+                    .iter()
+                    .filter(|schema| &schema.url == schema_url && schema.sub_root_keys.is_none())
+                    .flat_map(|schema| schema.include.iter().cloned())
+                    .unique()
+                    .collect_vec();
+
+                let vendored_schema = vendored_schemas.iter().find(|vendored_schema| {
+                    vendored_schema.schema_url == document_url(schema_url)
+                })?;
+                let file_name = vendored_schema.file_path.file_name()?.to_str()?;
+
+                Some(serde_json::json!({
+                    "name": get_schema_name(schema_url).unwrap_or(schema_url.as_str()),
+                    "description": format!("Vendored from {schema_url}"),
+                    "fileMatch": file_match,
+                    "url": format!("./{file_name}"),
+                }))
+            })
+            .collect_vec();
+
+        serde_json::json!({
+            "$schema": "https://json.schemastore.org/schema-catalog.json",
+            "version": 1,
+            "schemas": catalog_schemas,
+        })
+    }
+}
+
+/// The url of the document, without the fragment.
+fn document_url(url: &url::Url) -> SchemaUrl {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    SchemaUrl::new(url)
+}
+
+/// Resolve the `$ref` against the schema url.
+///
+/// Returns `None` for references in the same document.
+fn resolve_reference_url(schema_url: &SchemaUrl, reference: &str) -> Option<SchemaUrl> {
+    if reference.starts_with('#') {
+        return None;
+    }
+    schema_url
+        .join(reference)
+        .ok()
+        .map(|reference_url| document_url(&reference_url))
+}
+
+/// Keywords whose values are instances, not schemas.
+const INSTANCE_KEYWORDS: &[&str] = &["const", "default", "enum", "examples"];
+
+fn visit_references(value: &serde_json::Value, f: &mut impl FnMut(&str)) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object {
+                match (key.as_str(), value) {
+                    ("$ref", serde_json::Value::String(reference)) => f(reference),
+                    (key, _) if INSTANCE_KEYWORDS.contains(&key) => {}
+                    _ => visit_references(value, f),
+                }
+            }
+        }
+        serde_json::Value::Array(array) => {
+            for value in array {
+                visit_references(value, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rewrite_references(value: &mut serde_json::Value, f: &mut impl FnMut(&str) -> Option<String>) {
+    match value {
+        serde_json::Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match (key.as_str(), value) {
+                    ("$ref", serde_json::Value::String(reference)) => {
+                        if let Some(new_reference) = f(reference) {
+                            *reference = new_reference;
+                        }
+                    }
+                    (key, _) if INSTANCE_KEYWORDS.contains(&key) => {}
+                    (_, value) => rewrite_references(value, f),
+                }
+            }
+        }
+        serde_json::Value::Array(array) => {
+            for value in array {
+                rewrite_references(value, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn unique_file_name(schema_url: &SchemaUrl, used_file_names: &mut AHashSet<String>) -> String {
+    let name = get_schema_name(schema_url)
+        .unwrap_or("schema")
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' | '_' => c,
+            _ => '_',
+        })
+        .collect::<String>();
+    let stem = [".json", ".yaml", ".yml", ".toml"]
+        .into_iter()
+        .find_map(|extension| name.strip_suffix(extension))
+        .unwrap_or(&name);
+
+    let mut file_name = format!("{stem}.json");
+    let mut count = 1;
+    while used_file_names.contains(&file_name) {
+        file_name = format!("{stem}-{count}.json");
+        count += 1;
+    }
+    used_file_names.insert(file_name.clone());
+
+    file_name
+}
+
+fn write_json(path: &Path, value: &serde_json::Value) -> Result<(), crate::Error> {
+    let content =
+        serde_json::to_string_pretty(value).map_err(|err| crate::Error::VendorFileWriteFailed {
+            path: path.to_path_buf(),
+            reason: err.to_string(),
+        })?;
+
+    std::fs::write(path, content + "\n").map_err(|err| crate::Error::VendorFileWriteFailed {
+        path: path.to_path_buf(),
+        reason: err.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_remote_references() {
+        let schema_url = SchemaUrl::parse("https://example.com/schemas/root.json").unwrap();
+        let mut document = serde_json::json!({
+            "properties": {
+                "a": { "$ref": "https://example.com/a.json" },
+                "b": { "$ref": "b.json#/definitions/b" },
+                "c": { "$ref": "#/definitions/c" },
+                "d": { "default": { "$ref": "https://example.com/a.json" } },
+            }
+        });
+
+        let mut references = vec![];
+        visit_references(&document, &mut |reference| {
+            references.extend(resolve_reference_url(&schema_url, reference))
+        });
+        pretty_assertions::assert_eq!(
+            references
+                .iter()
+                .map(|reference| reference.as_str())
+                .collect_vec(),
+            vec![
+                "https://example.com/a.json",
+                "https://example.com/schemas/b.json"
+            ]
+        );
+
+        let mut used_file_names = AHashSet::default();
+        let file_names = references
+            .iter()
+            .map(|reference| {
+                (
+                    reference.clone(),
+                    unique_file_name(reference, &mut used_file_names),
+                )
+            })
+            .collect::<AHashMap<_, _>>();
+
+        rewrite_references(&mut document, &mut |reference| {
+            let reference_url = resolve_reference_url(&schema_url, reference)?;
+            let file_name = file_names.get(&reference_url)?;
+            Some(match reference.split_once('#') {
+                Some((_, fragment)) => format!("{file_name}#{fragment}"),
+                None => file_name.to_owned(),
+            })
+        });
+
+        pretty_assertions::assert_eq!(
+            document,
+            serde_json::json!({
+                "properties": {
+                    "a": { "$ref": "a.json" },
+                    "b": { "$ref": "b.json#/definitions/b" },
+                    "c": { "$ref": "#/definitions/c" },
+                    "d": { "default": { "$ref": "https://example.com/a.json" } },
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn unique_file_names() {
+        let mut used_file_names = AHashSet::from([VENDORED_CATALOG_FILENAME.to_string()]);
+        let file_names = [
+            "https://example.com/a/schema.json",
+            "https://example.com/b/schema.json",
+            "https://example.com/catalog.json",
+            "https://example.com/",
+            "https://example.com/c/schema.yaml",
+        ]
+        .into_iter()
+        .map(|url| unique_file_name(&SchemaUrl::parse(url).unwrap(), &mut used_file_names))
+        .collect_vec();
+
+        pretty_assertions::assert_eq!(
+            file_names,
+            vec![
+                "schema.json",
+                "schema-1.json",
+                "catalog-1.json",
+                "example.com.json",
+                "schema-2.json"
+            ]
+        );
+    }
+
+    #[test]
+    fn vendored_catalog_with_relative_paths() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+        for (file_name, content) in [
+            ("root.json", r#"{ "$ref": "./sub.json" }"#),
+            ("sub.json", r#"{ "type": "object" }"#),
+            ("directive.json", r#"{ "type": "object" }"#),
+        ] {
+            std::fs::write(source_dir.path().join(file_name), content).unwrap();
+        }
+        let schema_url = |file_name: &str| {
+            SchemaUrl::new(
+                tombi_url::url_from_file_path(source_dir.path().join(file_name)).unwrap(),
+            )
+        };
+
+        let schema_store = SchemaStore::new();
+        futures::executor::block_on(async {
+            schema_store
+                .associate_schema(schema_url("root.json"), vec!["root.toml".to_string()])
+                .await;
+            schema_store
+                .vendor_schemas(
+                    &[schema_url("root.json"), schema_url("directive.json")],
+                    output_dir.path(),
+                )
+                .await
+                .unwrap();
+        });
+
+        let catalog: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(output_dir.path().join(VENDORED_CATALOG_FILENAME)).unwrap(),
+        )
+        .unwrap();
+        pretty_assertions::assert_eq!(
+            catalog["schemas"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|schema| (schema["url"].clone(), schema["fileMatch"].clone()))
+                .collect_vec(),
+            vec![
+                ("./root.json".into(), serde_json::json!(["root.toml"])),
+                ("./directive.json".into(), serde_json::json!([])),
+            ]
+        );
+    }
+}
@@ -39,3 +39,21 @@ pub fn x_tombi_table_keys_order_schema_path() -> PathBuf {
         .join("schemas")
         .join("x-tombi-table-keys-order.schema.json")
 }
+
+pub fn x_tombi_array_values_order_by_schema_path() -> PathBuf {
+    project_root_path()
+        .join("schemas")
+        .join("x-tombi-array-values-order-by.schema.json")
+}
+
+pub fn x_tombi_table_style_schema_path() -> PathBuf {
+    project_root_path()
+        .join("schemas")
+        .join("x-tombi-table-style.schema.json")
+}
+
+pub fn x_tombi_array_values_unique_schema_path() -> PathBuf {
+    project_root_path()
+        .join("schemas")
+        .join("x-tombi-array-values-unique.schema.json")
+}
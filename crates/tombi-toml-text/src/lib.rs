@@ -313,7 +313,39 @@ pub fn to_literal_string(value: &str) -> String {
 }
 
 pub fn to_multi_line_basic_string(value: &str) -> String {
-    format!("\"\"\"\n{}\"\"\"", value)
+    let mut result = String::with_capacity(value.len() + 7);
+    result.push_str("\"\"\"\n");
+    let mut quote_count = 0;
+    for c in value.chars() {
+        match c {
+            // NOTE: Escape every third quote so that the quotes never close the string.
+            '"' if quote_count == 2 => {
+                result.push_str("\\\"");
+                quote_count = 0;
+                continue;
+            }
+            '"' => {
+                result.push('"');
+                quote_count += 1;
+                continue;
+            }
+            '\\' => result.push_str("\\\\"),
+            '\n' | '\t' => result.push(c),
+            '\x0d' => result.push_str("\\r"),
+            c if c.is_control() => {
+                result.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => result.push(c),
+        }
+        quote_count = 0;
+    }
+    // NOTE: The quotes just before the closing delimiter would be read as the delimiter.
+    if quote_count > 0 {
+        result.truncate(result.len() - quote_count);
+        result.push_str(&"\\\"".repeat(quote_count));
+    }
+    result.push_str("\"\"\"");
+    result
 }
 
 pub fn to_multi_line_literal_string(value: &str) -> String {
@@ -104,6 +104,38 @@ impl Validate for tombi_document_tree::Array {
                     _ => unreachable!("Expected an Array schema"),
                 };
 
+                let new_accessors = accessors
+                    .iter()
+                    .cloned()
+                    .chain(std::iter::once(tombi_schema_store::SchemaAccessor::Index))
+                    .collect::<Vec<_>>();
+
+                let mut prefix_items_len = 0;
+                if let Some(prefix_items) = &array_schema.prefix_items {
+                    prefix_items_len = prefix_items.len();
+                    for (value, item) in self.values().iter().zip(prefix_items) {
+                        let Some(item) = item else {
+                            continue;
+                        };
+                        let mut referable_schema = item.write().await;
+                        if let Ok(Some(current_schema)) = referable_schema
+                            .resolve(
+                                current_schema.schema_url.clone(),
+                                current_schema.definitions.clone(),
+                                schema_context.store,
+                            )
+                            .await
+                        {
+                            if let Err(schema_diagnostics) = value
+                                .validate(&new_accessors, Some(&current_schema), schema_context)
+                                .await
+                            {
+                                diagnostics.extend(schema_diagnostics);
+                            }
+                        }
+                    }
+                }
+
                 if let Some(items) = &array_schema.items {
                     let mut referable_schema = items.write().await;
                     if let Ok(Some(current_schema)) = referable_schema
@@ -114,11 +146,6 @@ impl Validate for tombi_document_tree::Array {
                         )
                         .await
                     {
-                        let new_accessors = accessors
-                            .iter()
-                            .cloned()
-                            .chain(std::iter::once(tombi_schema_store::SchemaAccessor::Index))
-                            .collect::<Vec<_>>();
                         if current_schema.value_schema.deprecated().await == Some(true) {
                             crate::Warning {
                                 kind: crate::WarningKind::Deprecated(
@@ -129,7 +156,7 @@ impl Validate for tombi_document_tree::Array {
                             .set_diagnostics(&mut diagnostics);
                         }
 
-                        for value in self.values().iter() {
+                        for value in self.values().iter().skip(prefix_items_len) {
                             if let Err(schema_diagnostics) = value
                                 .validate(&new_accessors, Some(&current_schema), schema_context)
                                 .await
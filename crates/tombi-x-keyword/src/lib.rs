@@ -1,8 +1,11 @@
 pub const X_TOMBI_TOML_VERSION: &str = "x-tombi-toml-version";
 pub const X_TOMBI_ARRAY_VALUES_ORDER: &str = "x-tombi-array-values-order";
+pub const X_TOMBI_ARRAY_VALUES_ORDER_BY: &str = "x-tombi-array-values-order-by";
+pub const X_TOMBI_ARRAY_VALUES_UNIQUE: &str = "x-tombi-array-values-unique";
 pub const X_TOMBI_TABLE_KEYS_ORDER: &str = "x-tombi-table-keys-order";
+pub const X_TOMBI_TABLE_STYLE: &str = "x-tombi-table-style";
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 #[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
@@ -70,3 +73,41 @@ impl<'a> TryFrom<&'a str> for TableKeysOrder {
         }
     }
 }
+
+/// The preferred way to write a table in TOML.
+///
+/// - `inline`: `key = { a = 1, b = 2 }`
+/// - `dotted`: `key.a = 1` and `key.b = 2`
+/// - `section`: `[parent.key]` followed by `a = 1` and `b = 2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "jsonschema", derive(schemars::JsonSchema))]
+pub enum TableStyle {
+    Inline,
+    Dotted,
+    Section,
+}
+
+impl std::fmt::Display for TableStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Inline => write!(f, "inline"),
+            Self::Dotted => write!(f, "dotted"),
+            Self::Section => write!(f, "section"),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for TableStyle {
+    type Error = &'a str;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "inline" => Ok(Self::Inline),
+            "dotted" => Ok(Self::Dotted),
+            "section" => Ok(Self::Section),
+            _ => Err(value),
+        }
+    }
+}
@@ -0,0 +1,481 @@
+use ahash::{AHashMap, AHashSet};
+use yaml_rust2::{
+    parser::{Event, Tag},
+    scanner::{Marker, TScalarStyle},
+};
+
+use crate::{Error, KeyNode, MappingNode, Node, PathSegment, ScalarNode, SequenceNode};
+
+const MERGE_KEY: &str = "<<";
+
+/// Build the node tree from the YAML events.
+///
+/// The parser only gives the start [`Marker`] of each event,
+/// so the end positions are found by scanning the source.
+pub(crate) struct NodeBuilder {
+    chars: Vec<char>,
+    line_starts: Vec<usize>,
+    events: std::iter::Peekable<std::vec::IntoIter<(Event, Marker)>>,
+    anchors: AHashMap<usize, Node>,
+}
+
+impl NodeBuilder {
+    pub(crate) fn new(source: &str, events: Vec<(Event, Marker)>) -> Self {
+        let chars = source.chars().collect::<Vec<_>>();
+        let line_starts = std::iter::once(0)
+            .chain(
+                chars
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| **c == '\n')
+                    .map(|(index, _)| index + 1),
+            )
+            .collect();
+
+        Self {
+            chars,
+            line_starts,
+            events: events.into_iter().peekable(),
+            anchors: AHashMap::new(),
+        }
+    }
+
+    /// Build the node of the first document, or `None` if the stream is empty.
+    pub(crate) fn build(mut self) -> Result<Option<Node>, Error> {
+        while let Some((event, _)) = self.events.peek() {
+            match event {
+                Event::StreamStart | Event::DocumentStart | Event::Nothing => {
+                    self.events.next();
+                }
+                Event::StreamEnd => break,
+                _ => return self.next_node().map(Some),
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn next_node(&mut self) -> Result<Node, Error> {
+        let Some((event, marker)) = self.events.next() else {
+            return Err(unexpected_end("stream"));
+        };
+
+        let (node, anchor_id) = match event {
+            Event::Scalar(value, style, anchor_id, tag) => {
+                let range = self.scalar_range(&value, style, marker);
+                let plain = style == TScalarStyle::Plain && !is_str_tag(tag.as_ref());
+                (
+                    Node::Scalar(ScalarNode {
+                        value,
+                        plain,
+                        range,
+                    }),
+                    anchor_id,
+                )
+            }
+            Event::SequenceStart(anchor_id, _) => {
+                let mut items = Vec::new();
+                let end_marker = loop {
+                    match self.events.peek() {
+                        Some((Event::SequenceEnd, end_marker)) => {
+                            let end_marker = *end_marker;
+                            self.events.next();
+                            break end_marker;
+                        }
+                        Some(_) => items.push(self.next_node().map_err(|error| {
+                            error.with_path_segment(PathSegment::Index(items.len()))
+                        })?),
+                        None => return Err(unexpected_end("sequence")),
+                    }
+                };
+                let range = self.collection_range(
+                    marker,
+                    end_marker,
+                    items.first().map(Node::range),
+                    items.last().map(Node::range),
+                );
+                (Node::Sequence(SequenceNode { items, range }), anchor_id)
+            }
+            Event::MappingStart(anchor_id, _) => {
+                let mut entries = Vec::new();
+                let mut keys = AHashSet::new();
+                let mut merged_mappings = Vec::new();
+                let end_marker = loop {
+                    match self.events.peek() {
+                        Some((Event::MappingEnd, end_marker)) => {
+                            let end_marker = *end_marker;
+                            self.events.next();
+                            break end_marker;
+                        }
+                        Some(_) => {
+                            let (key, is_merge_key) = self.next_key()?;
+                            let with_key = |error: Error| {
+                                error.with_path_segment(PathSegment::Key(key.value.clone()))
+                            };
+
+                            let value = self.next_node().map_err(with_key)?;
+                            if is_merge_key {
+                                merged_mappings
+                                    .extend(merge_value_mappings(value).map_err(with_key)?);
+                                continue;
+                            }
+                            if !keys.insert(key.value.clone()) {
+                                return Err(with_key(Error::new(
+                                    "duplicate key",
+                                    Some(key.range.start),
+                                )));
+                            }
+                            entries.push((key, value));
+                        }
+                        None => return Err(unexpected_end("mapping")),
+                    }
+                };
+                let range = self.collection_range(
+                    marker,
+                    end_marker,
+                    entries.first().map(|(key, _)| key.range),
+                    entries.last().map(|(_, value)| value.range()),
+                );
+
+                // The keys of the mapping override the merged keys,
+                // and the earlier merged mappings override the later ones.
+                for merged_mapping in merged_mappings {
+                    for (key, value) in merged_mapping.entries {
+                        if keys.insert(key.value.clone()) {
+                            entries.push((key, value));
+                        }
+                    }
+                }
+                (Node::Mapping(MappingNode { entries, range }), anchor_id)
+            }
+            Event::Alias(anchor_id) => {
+                return self
+                    .anchors
+                    .get(&anchor_id)
+                    .cloned()
+                    .ok_or_else(|| self.error("unknown YAML anchor", marker));
+            }
+            event => return Err(self.error(format!("unexpected YAML event {event:?}"), marker)),
+        };
+
+        if anchor_id != 0 {
+            self.anchors.insert(anchor_id, node.clone());
+        }
+
+        Ok(node)
+    }
+
+    /// Also returns whether the key is the merge key `<<`.
+    fn next_key(&mut self) -> Result<(KeyNode, bool), Error> {
+        match self.events.next() {
+            Some((Event::Scalar(value, style, _, _), marker)) => {
+                let is_merge_key = style == TScalarStyle::Plain && value == MERGE_KEY;
+                let range = self.scalar_range(&value, style, marker);
+                Ok((KeyNode { value, range }, is_merge_key))
+            }
+            Some((_, marker)) => Err(self.error("YAML mapping key must be a scalar", marker)),
+            None => Err(unexpected_end("mapping")),
+        }
+    }
+
+    fn scalar_range(&self, value: &str, style: TScalarStyle, marker: Marker) -> tombi_text::Range {
+        let start = marker.index();
+        let end = match style {
+            TScalarStyle::Plain => self.plain_scalar_end(start, value),
+            TScalarStyle::SingleQuoted => self.quoted_scalar_end(start, '\'', None),
+            TScalarStyle::DoubleQuoted => self.quoted_scalar_end(start, '"', Some('\\')),
+            TScalarStyle::Literal | TScalarStyle::Folded => self.block_scalar_end(start, value),
+        };
+
+        tombi_text::Range::new(self.position(start), self.position(end))
+    }
+
+    /// Match the folded value against the source, skipping the line breaks and the indents.
+    fn plain_scalar_end(&self, start: usize, value: &str) -> usize {
+        let mut index = start;
+        let mut value_chars = value.chars().peekable();
+        while let (Some(&c), Some(&v)) = (self.chars.get(index), value_chars.peek()) {
+            if c == v {
+                index += 1;
+                value_chars.next();
+            } else if c.is_whitespace() {
+                index += 1;
+            } else if v.is_whitespace() {
+                value_chars.next();
+            } else {
+                break;
+            }
+        }
+        index
+    }
+
+    fn quoted_scalar_end(&self, start: usize, quote: char, escape: Option<char>) -> usize {
+        let mut index = start + 1;
+        while let Some(&c) = self.chars.get(index) {
+            if Some(c) == escape {
+                index += 2;
+            } else if c == quote {
+                // In single quoted scalars, the quote is escaped by doubling it.
+                if escape.is_none() && self.chars.get(index + 1) == Some(&quote) {
+                    index += 2;
+                } else {
+                    return index + 1;
+                }
+            } else {
+                index += 1;
+            }
+        }
+        self.chars.len()
+    }
+
+    /// The marker of a block scalar points at its first content line,
+    /// and the content continues while the lines are indented as deep as it.
+    fn block_scalar_end(&self, start: usize, value: &str) -> usize {
+        if value.is_empty() {
+            return start;
+        }
+
+        let first_line = self.line_index(start);
+        let content_indent = self.line_indent(first_line);
+        let mut end = self.line_end(first_line);
+        for line in first_line + 1..self.line_starts.len() {
+            let line_end = self.line_end(line);
+            let indent = self.line_indent(line);
+            if self.line_starts[line] + indent == line_end {
+                continue;
+            }
+            if indent < content_indent {
+                break;
+            }
+            end = line_end;
+        }
+        end
+    }
+
+    /// Flow collections are enclosed by the brackets,
+    /// and block collections span from their first item to their last item.
+    ///
+    /// The marker of a block mapping points at the first `:`, not at the first key.
+    fn collection_range(
+        &self,
+        start_marker: Marker,
+        end_marker: Marker,
+        first_item_range: Option<tombi_text::Range>,
+        last_item_range: Option<tombi_text::Range>,
+    ) -> tombi_text::Range {
+        let start = self.position(start_marker.index());
+        match self.chars.get(start_marker.index()) {
+            Some('[' | '{') => tombi_text::Range::new(
+                start,
+                self.position((end_marker.index() + 1).min(self.chars.len())),
+            ),
+            _ => tombi_text::Range::new(
+                first_item_range.map_or(start, |range| range.start.min(start)),
+                last_item_range.map_or(start, |range| range.end),
+            ),
+        }
+    }
+
+    fn line_index(&self, index: usize) -> usize {
+        self.line_starts
+            .partition_point(|line_start| *line_start <= index)
+            .saturating_sub(1)
+    }
+
+    /// The end of the line, excluding the line break.
+    fn line_end(&self, line: usize) -> usize {
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(self.chars.len(), |next_line_start| next_line_start - 1);
+        if end > self.line_starts[line] && self.chars.get(end - 1) == Some(&'\r') {
+            end - 1
+        } else {
+            end
+        }
+    }
+
+    fn line_indent(&self, line: usize) -> usize {
+        self.chars[self.line_starts[line]..self.line_end(line)]
+            .iter()
+            .take_while(|c| **c == ' ')
+            .count()
+    }
+
+    fn position(&self, index: usize) -> tombi_text::Position {
+        let line = self.line_index(index);
+        tombi_text::Position::new(
+            line as tombi_text::Line,
+            (index - self.line_starts[line]) as tombi_text::Column,
+        )
+    }
+
+    fn error(&self, message: impl Into<String>, marker: Marker) -> Error {
+        Error::new(message, Some(self.position(marker.index())))
+    }
+}
+
+/// The value of the merge key is a mapping or a sequence of mappings.
+fn merge_value_mappings(value: Node) -> Result<Vec<MappingNode>, Error> {
+    let position = value.range().start;
+    let invalid_merge = || {
+        Error::new(
+            "the value of the merge key must be a mapping or a sequence of mappings",
+            Some(position),
+        )
+    };
+
+    match value {
+        Node::Mapping(mapping) => Ok(vec![mapping]),
+        Node::Sequence(sequence) => sequence
+            .items
+            .into_iter()
+            .map(|item| match item {
+                Node::Mapping(mapping) => Ok(mapping),
+                _ => Err(invalid_merge()),
+            })
+            .collect(),
+        Node::Scalar(_) => Err(invalid_merge()),
+    }
+}
+
+fn is_str_tag(tag: Option<&Tag>) -> bool {
+    tag.is_some_and(|tag| {
+        matches!(tag.handle.as_str(), "!!" | "tag:yaml.org,2002:") && tag.suffix == "str"
+    })
+}
+
+fn unexpected_end(collection: &str) -> Error {
+    Error::new(format!("unexpected end of YAML {collection}"), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, Node, PathSegment, Scalar};
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> tombi_text::Range {
+        tombi_text::Range::new(
+            tombi_text::Position::new(start.0, start.1),
+            tombi_text::Position::new(end.0, end.1),
+        )
+    }
+
+    fn parse_mapping(source: &str) -> crate::MappingNode {
+        match parse(&textwrap::dedent(source)).unwrap() {
+            Some(Node::Mapping(mapping)) => mapping,
+            node => panic!("expected mapping, got {node:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_ranges() {
+        let mapping = parse_mapping(
+            r#"
+            "title": 'it''s'
+            properties:
+              name: {type: string}
+              description: |
+                multi
+                line
+            items: [1, 2]
+            "#,
+        );
+
+        let (title_key, title) = &mapping.entries[0];
+        pretty_assertions::assert_eq!(title_key.range, range((1, 0), (1, 7)));
+        pretty_assertions::assert_eq!(title.range(), range((1, 9), (1, 16)));
+
+        let properties = mapping.get("properties").unwrap();
+        pretty_assertions::assert_eq!(properties.range(), range((3, 2), (6, 8)));
+
+        let Node::Mapping(properties) = properties else {
+            panic!("expected mapping");
+        };
+        pretty_assertions::assert_eq!(
+            properties.get("name").unwrap().range(),
+            range((3, 8), (3, 22))
+        );
+        pretty_assertions::assert_eq!(
+            properties.get("description").unwrap().range(),
+            range((5, 4), (6, 8))
+        );
+
+        pretty_assertions::assert_eq!(
+            mapping.get("items").unwrap().range(),
+            range((7, 7), (7, 13))
+        );
+    }
+
+    #[test]
+    fn plain_scalars() {
+        let mapping = parse_mapping(
+            r#"
+            plain: 1
+            quoted: '1'
+            tagged: !!str 1
+            "#,
+        );
+
+        let resolved = mapping
+            .entries
+            .iter()
+            .map(|(_, value)| match value {
+                Node::Scalar(scalar) => scalar.resolve(),
+                _ => panic!("expected scalar"),
+            })
+            .collect::<Vec<_>>();
+        pretty_assertions::assert_eq!(resolved, vec![Some(Scalar::Integer(1)), None, None]);
+    }
+
+    #[test]
+    fn merge_keys() {
+        let mapping = parse_mapping(
+            r#"
+            base: &base
+              host: localhost
+              port: 8080
+            server:
+              <<: *base
+              port: 80
+            "#,
+        );
+
+        let Some(Node::Mapping(server)) = mapping.get("server") else {
+            panic!("expected mapping");
+        };
+        pretty_assertions::assert_eq!(
+            server
+                .entries
+                .iter()
+                .map(|(key, _)| key.value.as_str())
+                .collect::<Vec<_>>(),
+            vec!["port", "host"]
+        );
+    }
+
+    #[test]
+    fn duplicate_key() {
+        let error = parse(&textwrap::dedent(
+            r#"
+            server:
+              port: 80
+              port: 8080
+            "#,
+        ))
+        .unwrap_err();
+
+        pretty_assertions::assert_eq!(
+            error.path(),
+            &[
+                PathSegment::Key("server".to_string()),
+                PathSegment::Key("port".to_string())
+            ]
+        );
+        pretty_assertions::assert_eq!(error.to_string(), "duplicate key at line 4, column 3");
+    }
+
+    #[test]
+    fn empty_stream() {
+        pretty_assertions::assert_eq!(parse("").unwrap(), None);
+    }
+}
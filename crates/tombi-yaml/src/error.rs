@@ -0,0 +1,61 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// The error of loading the YAML stream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    message: String,
+    position: Option<tombi_text::Position>,
+    path: Vec<PathSegment>,
+}
+
+impl Error {
+    pub(crate) fn new(message: impl Into<String>, position: Option<tombi_text::Position>) -> Self {
+        Self {
+            message: message.into(),
+            position,
+            path: Vec::new(),
+        }
+    }
+
+    /// The error message without the position and the key path.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The position in the YAML source, if the error is located.
+    pub fn position(&self) -> Option<tombi_text::Position> {
+        self.position
+    }
+
+    /// The key path to the value that failed to load, from the root.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// Prepend the segment to the key path of the error.
+    pub(crate) fn with_path_segment(mut self, segment: PathSegment) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some(position) => write!(
+                f,
+                "{} at line {}, column {}",
+                self.message,
+                position.line + 1,
+                position.column + 1
+            ),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
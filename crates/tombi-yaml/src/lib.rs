@@ -0,0 +1,51 @@
+//! YAML loader shared by the YAML schemas and the YAML conversion of the documents.
+//!
+//! The YAML stream is loaded into the [`Node`] tree with the source ranges.
+//! The anchors are resolved, the merge keys `<<` are merged,
+//! and the duplicate keys are rejected while loading.
+
+mod builder;
+mod error;
+mod node;
+
+pub use error::{Error, PathSegment};
+pub use node::{KeyNode, MappingNode, Node, Scalar, ScalarNode, SequenceNode};
+use yaml_rust2::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+    Yaml,
+};
+
+/// Load the first document of the YAML stream.
+///
+/// Returns `None` if the stream is empty.
+pub fn parse(source: &str) -> Result<Option<Node>, Error> {
+    let mut events = EventCollector::default();
+    Parser::new_from_str(source)
+        .load(&mut events, false)
+        .map_err(|error| Error::new(error.to_string(), None))?;
+
+    builder::NodeBuilder::new(source, events.0).build()
+}
+
+/// Resolve the plain scalar by the YAML core schema.
+///
+/// Returns `None` for the strings.
+pub fn resolve_scalar(value: &str) -> Option<Scalar> {
+    match Yaml::from_str(value) {
+        Yaml::Null => Some(Scalar::Null),
+        Yaml::Boolean(boolean) => Some(Scalar::Boolean(boolean)),
+        Yaml::Integer(integer) => Some(Scalar::Integer(integer)),
+        real @ Yaml::Real(_) => real.as_f64().map(Scalar::Float),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct EventCollector(Vec<(Event, Marker)>);
+
+impl MarkedEventReceiver for EventCollector {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        self.0.push((event, marker));
+    }
+}
@@ -0,0 +1,84 @@
+use tombi_text::Range;
+
+/// A YAML node with the source range.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Scalar(ScalarNode),
+    Sequence(SequenceNode),
+    Mapping(MappingNode),
+}
+
+impl Node {
+    pub fn range(&self) -> Range {
+        match self {
+            Self::Scalar(node) => node.range,
+            Self::Sequence(node) => node.range,
+            Self::Mapping(node) => node.range,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalarNode {
+    pub value: String,
+
+    /// Whether the scalar is resolved by the YAML core schema,
+    /// that is, it is neither quoted, a block scalar nor tagged with `!!str`.
+    pub plain: bool,
+
+    pub range: Range,
+}
+
+impl ScalarNode {
+    /// Resolve the plain scalar by the YAML core schema.
+    ///
+    /// Returns `None` for the strings.
+    pub fn resolve(&self) -> Option<Scalar> {
+        if self.plain {
+            crate::resolve_scalar(&self.value)
+        } else {
+            None
+        }
+    }
+}
+
+/// The scalar resolved by the YAML core schema.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scalar {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceNode {
+    pub items: Vec<Node>,
+    pub range: Range,
+}
+
+/// A YAML mapping.
+///
+/// The entries of the merge keys `<<` follow the entries of the mapping itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingNode {
+    pub entries: Vec<(KeyNode, Node)>,
+    pub range: Range,
+}
+
+impl MappingNode {
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.entries
+            .iter()
+            .find_map(|(entry_key, value)| (entry_key.value == key).then_some(value))
+    }
+}
+
+/// A YAML mapping key.
+///
+/// The keys of the other types than the strings are used as they are written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyNode {
+    pub value: String,
+    pub range: Range,
+}
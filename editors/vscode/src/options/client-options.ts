@@ -1,4 +1,4 @@
-import { SUPPORT_TOML_LANGUAGES } from "@/extention";
+import { SUPPORT_JSON_LANGUAGES, SUPPORT_TOML_LANGUAGES } from "@/extention";
 import * as vscode from "vscode";
 import type * as languageclient from "vscode-languageclient";
 
@@ -6,10 +6,18 @@ export function clientOptions(
   workspaceFolder?: vscode.WorkspaceFolder,
 ): languageclient.LanguageClientOptions {
   const options = {
-    documentSelector: SUPPORT_TOML_LANGUAGES.flatMap((language) => [
-      { scheme: "file", language },
-      { scheme: "untitled", language },
-    ]),
+    documentSelector: [
+      ...SUPPORT_TOML_LANGUAGES.flatMap((language) => [
+        { scheme: "file", language },
+        { scheme: "untitled", language },
+      ]),
+      // JSON Schema files, only for diagnostics
+      ...SUPPORT_JSON_LANGUAGES.map((language) => ({
+        scheme: "file",
+        language,
+        pattern: "**/*.schema.json",
+      })),
+    ],
     workspaceFolder,
     synchronize: {
       // Notify the server about file changes to tombi.toml and JSON files contained in the workspace
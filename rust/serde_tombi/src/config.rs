@@ -15,19 +15,11 @@ pub(crate) fn from_str(
     toml_text: &str,
     config_path: &std::path::Path,
 ) -> Result<Config, crate::de::Error> {
-    let deserializer = crate::Deserializer::builder()
+    crate::Deserializer::builder()
         .config_path(config_path)
-        .build();
-
-    let toml_version = TOMBI_CONFIG_TOML_VERSION;
-    let parsed = tombi_parser::parse(toml_text, toml_version);
-    let root = tombi_ast::Root::cast(parsed.syntax_node()).expect("AST Root must be present");
-    // Check if there are any parsing errors
-    if !parsed.errors.is_empty() {
-        return Err(parsed.errors.into());
-    }
-
-    deserializer.from_document(deserializer.try_to_document(root, TOMBI_CONFIG_TOML_VERSION)?)
+        .toml_version(TOMBI_CONFIG_TOML_VERSION)
+        .build()
+        .from_str(toml_text)
 }
 
 #[doc(hidden)]
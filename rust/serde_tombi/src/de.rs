@@ -1,8 +1,9 @@
+mod document_tree;
 mod error;
 
 pub use error::Error;
 use itertools::Either;
-use serde::de::DeserializeOwned;
+use serde::de::{Deserialize, DeserializeOwned};
 use tombi_ast::AstNode;
 use tombi_document::IntoDocument;
 use tombi_document_tree::IntoDocumentTreeAndErrors;
@@ -48,6 +49,81 @@ where
     Deserializer::new().from_str_async(toml_text).await
 }
 
+/// Deserialize a TOML string into a Rust data structure.
+///
+/// Unlike [from_str_async], this function neither loads the config nor resolves the schema,
+/// so it requires no async runtime and touches neither the filesystem nor the network.
+/// The TOML text is parsed as the default TOML version.
+/// Use [Deserializer::builder] to specify the TOML version.
+///
+/// The strings without the escapes are borrowed from the TOML text,
+/// so they can be deserialized into `&str` and `Cow::Borrowed` fields.
+/// The strings with the escapes are unescaped into an owned string,
+/// which `Cow<str>` accepts but `&str` does not.
+/// Note that this is not zero-copy: the document tree parsed from the TOML text
+/// still holds an owned copy of every string while deserializing.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     ip: String,
+///     port: u16,
+///     keys: Vec<String>,
+/// }
+///
+/// let toml = r#"
+/// ip = "127.0.0.1"
+/// port = 8080
+/// keys = ["key1", "key2"]
+/// "#;
+///
+/// let config: Config = serde_tombi::from_str(toml).unwrap();
+/// ```
+pub fn from_str<'de, T>(toml_text: &'de str) -> Result<T, crate::de::Error>
+where
+    T: Deserialize<'de>,
+{
+    Deserializer::new().from_str(toml_text)
+}
+
+/// Deserialize a UTF-8 encoded TOML bytes into a Rust data structure.
+///
+/// See [from_str] for details.
+pub fn from_slice<'de, T>(toml_bytes: &'de [u8]) -> Result<T, crate::de::Error>
+where
+    T: Deserialize<'de>,
+{
+    Deserializer::new().from_slice(toml_bytes)
+}
+
+/// Deserialize a TOML from the reader into a Rust data structure.
+///
+/// See [from_str] for details.
+pub fn from_reader<R, T>(reader: R) -> Result<T, crate::de::Error>
+where
+    R: std::io::Read,
+    T: DeserializeOwned,
+{
+    Deserializer::new().from_reader(reader)
+}
+
+/// Deserialize a document tree into a Rust data structure.
+///
+/// Unlike [from_document], [crate::Spanned] captures the ranges of the keys and the values,
+/// and the errors have the range of the value that failed to deserialize.
+pub fn from_document_tree<T>(
+    document_tree: tombi_document_tree::DocumentTree,
+) -> Result<T, crate::de::Error>
+where
+    T: DeserializeOwned,
+{
+    Deserializer::new().from_document_tree(document_tree)
+}
+
 pub fn from_document<T>(document: tombi_document::Document) -> Result<T, crate::de::Error>
 where
     T: DeserializeOwned,
@@ -69,6 +145,22 @@ pub struct Deserializer<'de> {
 
     #[builder(default, setter(into, strip_option))]
     schema_store: Option<&'de tombi_schema_store::SchemaStore>,
+
+    /// The TOML version to parse the TOML text.
+    ///
+    /// Takes precedence over the TOML version of the config and the schema.
+    #[builder(default, setter(into, strip_option))]
+    toml_version: Option<TomlVersion>,
+
+    /// Validate the TOML text with the schema before deserializing.
+    ///
+    /// The schema is resolved from the `#:schema` directive or the `source_path`,
+    /// and the errors are reported with the same diagnostics as the language server.
+    /// If no schema is resolved, an error is returned instead of skipping the validation
+    /// (see [Error::is_schema_not_found]).
+    /// Only [Deserializer::from_str_async] validates, because resolving the schema may require the network.
+    #[builder(default)]
+    validate: bool,
 }
 
 impl Default for Deserializer<'_> {
@@ -84,6 +176,8 @@ impl Deserializer<'_> {
             config_path: None,
             source_path: None,
             schema_store: None,
+            toml_version: None,
+            validate: false,
         }
     }
 
@@ -91,14 +185,153 @@ impl Deserializer<'_> {
     where
         T: DeserializeOwned,
     {
-        let toml_version = self.get_toml_version(toml_text).await?;
+        if !self.validate {
+            if let Some(toml_version) = self.toml_version {
+                return self.deserialize_str_with_toml_version(toml_text, toml_version);
+            }
+        }
+
+        let schema_store = match self.schema_store {
+            Some(schema_store) => schema_store,
+            None => &SchemaStore::new(),
+        };
+
+        let (toml_version, source_schema) = self
+            .get_toml_version_and_source_schema(toml_text, schema_store)
+            .await?;
+        let toml_version = self.toml_version.unwrap_or(toml_version);
+
+        if self.validate {
+            let Some(source_schema) = source_schema else {
+                return Err(crate::de::Error::schema_not_found());
+            };
+
+            let document_tree = self.parse_document_tree(toml_text, toml_version)?;
+            let schema_context = tombi_schema_store::SchemaContext {
+                toml_version,
+                root_schema: source_schema.root_schema.as_ref(),
+                sub_schema_url_map: Some(&source_schema.sub_schema_url_map),
+                order_overrides: None,
+                store: schema_store,
+            };
+
+            if let Err(diagnostics) =
+                tombi_validator::validate(document_tree.clone(), &source_schema, &schema_context)
+                    .await
+            {
+                if diagnostics
+                    .iter()
+                    .any(|diagnostic| diagnostic.level() == tombi_diagnostic::Level::ERROR)
+                {
+                    return Err(diagnostics.into());
+                }
+            }
+
+            return self.deserialize_document_tree_with_source(
+                &document_tree,
+                document_tree::Source::new(Some(toml_text), toml_version),
+            );
+        }
+
+        self.deserialize_str_with_toml_version(toml_text, toml_version)
+    }
+
+    /// Deserialize a TOML string without loading the config and the schema.
+    ///
+    /// The TOML version is taken from the builder, then the given config, and then the default.
+    pub fn from_str<'a, T>(&self, toml_text: &'a str) -> Result<T, crate::de::Error>
+    where
+        T: Deserialize<'a>,
+    {
+        let toml_version = self
+            .toml_version
+            .or_else(|| self.config.and_then(|config| config.toml_version))
+            .unwrap_or_default();
+        self.deserialize_str_with_toml_version(toml_text, toml_version)
+    }
+
+    pub fn from_slice<'a, T>(&self, toml_bytes: &'a [u8]) -> Result<T, crate::de::Error>
+    where
+        T: Deserialize<'a>,
+    {
+        self.from_str(std::str::from_utf8(toml_bytes)?)
+    }
+
+    pub fn from_reader<R, T>(&self, mut reader: R) -> Result<T, crate::de::Error>
+    where
+        R: std::io::Read,
+        T: DeserializeOwned,
+    {
+        let mut toml_text = std::string::String::new();
+        reader.read_to_string(&mut toml_text)?;
+        self.from_str(&toml_text)
+    }
+
+    pub(crate) fn deserialize_str_with_toml_version<'a, T>(
+        &self,
+        toml_text: &'a str,
+        toml_version: TomlVersion,
+    ) -> Result<T, crate::de::Error>
+    where
+        T: Deserialize<'a>,
+    {
+        let document_tree = self.parse_document_tree(toml_text, toml_version)?;
+        self.deserialize_document_tree_with_source(
+            &document_tree,
+            document_tree::Source::new(Some(toml_text), toml_version),
+        )
+    }
+
+    fn parse_document_tree(
+        &self,
+        toml_text: &str,
+        toml_version: TomlVersion,
+    ) -> Result<tombi_document_tree::DocumentTree, crate::de::Error> {
         let parsed = tombi_parser::parse(toml_text, toml_version);
         let root = tombi_ast::Root::cast(parsed.syntax_node()).expect("AST Root must be present");
         // Check if there are any parsing errors
         if !parsed.errors.is_empty() {
             return Err(parsed.errors.into());
         }
-        from_document(self.try_to_document(root, toml_version)?)
+
+        let (document_tree, errors) = root.into_document_tree_and_errors(toml_version).into();
+        if !errors.is_empty() {
+            return Err(errors.into());
+        }
+
+        Ok(document_tree)
+    }
+
+    /// Deserialize a document tree without loading the config and the schema.
+    ///
+    /// The TOML version is taken from the builder, then the given config, and then the default.
+    pub fn from_document_tree<T>(
+        &self,
+        document_tree: tombi_document_tree::DocumentTree,
+    ) -> Result<T, crate::de::Error>
+    where
+        T: DeserializeOwned,
+    {
+        let toml_version = self
+            .toml_version
+            .or_else(|| self.config.and_then(|config| config.toml_version))
+            .unwrap_or_default();
+        self.deserialize_document_tree_with_source(
+            &document_tree,
+            document_tree::Source::new(None, toml_version),
+        )
+    }
+
+    fn deserialize_document_tree_with_source<'a, T>(
+        &self,
+        document_tree: &tombi_document_tree::DocumentTree,
+        source: document_tree::Source<'a>,
+    ) -> Result<T, crate::de::Error>
+    where
+        T: Deserialize<'a>,
+    {
+        T::deserialize(document_tree::TableDeserializer::new(document_tree, source))
+            .map_err(|error| crate::de::Error::from_deserialize_error(error, document_tree))
     }
 
     pub fn from_document<T>(
@@ -111,13 +344,13 @@ impl Deserializer<'_> {
         Ok(T::deserialize(&document)?)
     }
 
-    async fn get_toml_version(&self, toml_text: &str) -> Result<TomlVersion, crate::de::Error> {
-        let schema_store = match self.schema_store {
-            Some(schema_store) => schema_store,
-            None => &SchemaStore::new(),
-        };
-
+    async fn get_toml_version_and_source_schema(
+        &self,
+        toml_text: &str,
+        schema_store: &SchemaStore,
+    ) -> Result<(TomlVersion, Option<SourceSchema>), crate::de::Error> {
         let mut toml_version = TomlVersion::default();
+        let mut source_schema = None;
 
         if self.schema_store.is_none() {
             match self.config {
@@ -148,27 +381,29 @@ impl Deserializer<'_> {
             .expect("AST Root must be present");
         let root = parsed.tree();
 
-        if let Some(source_path) = self.source_path {
+        if self.source_path.is_some() || self.validate {
             match schema_store
-                .resolve_source_schema_from_ast(&root, Some(Either::Right(source_path)))
+                .resolve_source_schema_from_ast(&root, self.source_path.map(Either::Right))
                 .await
             {
-                Ok(Some(SourceSchema {
-                    root_schema: Some(root_schema),
-                    ..
-                })) => {
-                    if let Some(new_toml_version) = root_schema.toml_version() {
+                Ok(Some(new_source_schema)) => {
+                    if let Some(new_toml_version) = new_source_schema
+                        .root_schema
+                        .as_ref()
+                        .and_then(|root_schema| root_schema.toml_version())
+                    {
                         toml_version = new_toml_version;
                     }
+                    source_schema = Some(new_source_schema);
                 }
+                Ok(None) => {}
                 Err((error, _)) => {
                     return Err(error.into());
                 }
-                _ => {}
             }
         }
 
-        Ok(toml_version)
+        Ok((toml_version, source_schema))
     }
 
     pub(crate) fn try_to_document(
@@ -672,4 +907,342 @@ optional_string = "provided"
         let first_schema = &schemas[0];
         pretty_assertions::assert_eq!(first_schema.path(), "schemas/type-test.schema.json");
     }
+
+    #[test]
+    fn test_from_str_without_runtime() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            name: String,
+            values: Vec<i64>,
+        }
+
+        let toml = r#"
+name = "tombi"
+values = [1, 2, 3]
+"#;
+
+        let expected = Test {
+            name: "tombi".to_string(),
+            values: vec![1, 2, 3],
+        };
+
+        let result: Test = from_str(toml).expect("TOML deserialization failed");
+        pretty_assertions::assert_eq!(result, expected);
+
+        let result: Test = from_slice(toml.as_bytes()).expect("TOML deserialization failed");
+        pretty_assertions::assert_eq!(result, expected);
+
+        let result: Test = from_reader(toml.as_bytes()).expect("TOML deserialization failed");
+        pretty_assertions::assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_from_slice_invalid_utf8() {
+        let result: Result<IndexMap<String, String>, _> = from_slice(&[0xff, 0xfe]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_str_with_toml_version() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Inline {
+            a: i64,
+            b: i64,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            inline: Inline,
+        }
+
+        let toml = r#"
+inline = { a = 1, b = 2, }
+"#;
+
+        let result: Result<Test, _> = from_str(toml);
+        assert!(result.is_err());
+
+        let result: Test = Deserializer::builder()
+            .toml_version(TomlVersion::V1_1_0_Preview)
+            .build()
+            .from_str(toml)
+            .expect("TOML deserialization failed");
+        pretty_assertions::assert_eq!(
+            result,
+            Test {
+                inline: Inline { a: 1, b: 2 }
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_borrowed_str() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test<'a> {
+            basic: &'a str,
+            literal: &'a str,
+            multi_line: &'a str,
+            #[serde(borrow)]
+            map: IndexMap<&'a str, &'a str>,
+            #[serde(borrow)]
+            values: Vec<&'a str>,
+        }
+
+        let toml = r#"
+basic = "hello"
+literal = 'C:\Users'
+multi_line = """
+line 1
+line 2"""
+map = { "quoted key" = "value" }
+values = ["a", 'b']
+"#;
+
+        let result: Test = from_str(toml).expect("TOML deserialization failed");
+        pretty_assertions::assert_eq!(
+            result,
+            Test {
+                basic: "hello",
+                literal: r"C:\Users",
+                multi_line: "line 1\nline 2",
+                map: IndexMap::from([("quoted key", "value")]),
+                values: vec!["a", "b"],
+            }
+        );
+
+        let toml_range = toml.as_bytes().as_ptr_range();
+        assert!(toml_range.contains(&result.basic.as_ptr()));
+        assert!(toml_range.contains(&result.multi_line.as_ptr()));
+    }
+
+    #[test]
+    fn test_deserialize_cow_str_with_escapes() {
+        use std::borrow::Cow;
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test<'a> {
+            #[serde(borrow)]
+            plain: Cow<'a, str>,
+            #[serde(borrow)]
+            escaped: Cow<'a, str>,
+        }
+
+        let toml = r#"
+plain = "hello"
+escaped = "tab\there"
+"#;
+
+        let result: Test = from_str(toml).expect("TOML deserialization failed");
+        pretty_assertions::assert_eq!(result.plain, "hello");
+        pretty_assertions::assert_eq!(result.escaped, "tab\there");
+        assert!(matches!(result.plain, Cow::Borrowed(_)));
+        assert!(matches!(result.escaped, Cow::Owned(_)));
+
+        let result: Result<IndexMap<String, &str>, _> = from_str(toml);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_error_with_path_and_range() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Server {
+            host: String,
+            port: u16,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Test {
+            server: Server,
+        }
+
+        let toml = r#"[server]
+host = "localhost"
+port = "8080"
+"#;
+
+        let error = from_str::<Test>(toml).unwrap_err();
+        pretty_assertions::assert_eq!(error.path(), Some("server.port"));
+        pretty_assertions::assert_eq!(
+            error.range(),
+            Some(tombi_text::Range::new(
+                tombi_text::Position::new(2, 7),
+                tombi_text::Position::new(2, 13)
+            ))
+        );
+        pretty_assertions::assert_eq!(
+            error.to_string(),
+            r#"invalid type: string "8080", expected u16 for `server.port` at line 3 column 8"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_error_with_array_index() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Bin {
+            name: String,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Test {
+            bin: Vec<Bin>,
+        }
+
+        let toml = r#"[[bin]]
+name = "a"
+
+[[bin]]
+name = 1
+"#;
+
+        let error = from_str::<Test>(toml).unwrap_err();
+        pretty_assertions::assert_eq!(error.path(), Some("bin[1].name"));
+        pretty_assertions::assert_eq!(error.range().map(|range| range.start.line), Some(4));
+    }
+
+    #[test]
+    fn test_deserialize_error_diagnostics() {
+        use tombi_diagnostic::SetDiagnostics;
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Server {
+            port: u16,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Test {
+            server: Server,
+        }
+
+        let toml = r#"[server]
+host = "localhost"
+"#;
+
+        let error = from_str::<Test>(toml).unwrap_err();
+        pretty_assertions::assert_eq!(error.path(), Some("server"));
+
+        let mut diagnostics = vec![];
+        error.set_diagnostics(&mut diagnostics);
+        pretty_assertions::assert_eq!(diagnostics.len(), 1);
+        pretty_assertions::assert_eq!(
+            diagnostics[0].message(),
+            "missing field `port` for `server`"
+        );
+        pretty_assertions::assert_eq!(diagnostics[0].position(), tombi_text::Position::new(0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_with_validation_error() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Package {
+            name: tombi_document::Value,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Manifest {
+            package: Package,
+        }
+
+        let toml = r#"#:schema ./schemas/cargo.schema.json
+
+[package]
+name = 1
+"#;
+
+        let schema_store = SchemaStore::new_with_options(tombi_schema_store::Options {
+            offline: Some(true),
+            ..Default::default()
+        });
+        let source_path = project_root_path().join("Cargo.toml");
+
+        let error = Deserializer::builder()
+            .schema_store(&schema_store)
+            .source_path(source_path.as_path())
+            .validate(true)
+            .build()
+            .from_str_async::<Manifest>(toml)
+            .await
+            .unwrap_err();
+
+        let diagnostics = error
+            .validation_diagnostics()
+            .expect("validation diagnostics must be present");
+        pretty_assertions::assert_eq!(diagnostics.len(), 1);
+        pretty_assertions::assert_eq!(diagnostics[0].level(), tombi_diagnostic::Level::ERROR);
+        pretty_assertions::assert_eq!(error.range().map(|range| range.start.line), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_with_validation() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Package {
+            name: String,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Manifest {
+            package: Package,
+        }
+
+        let toml = r#"#:schema ./schemas/cargo.schema.json
+
+[package]
+name = "tombi"
+"#;
+
+        let schema_store = SchemaStore::new_with_options(tombi_schema_store::Options {
+            offline: Some(true),
+            ..Default::default()
+        });
+        let source_path = project_root_path().join("Cargo.toml");
+
+        let manifest = Deserializer::builder()
+            .schema_store(&schema_store)
+            .source_path(source_path.as_path())
+            .validate(true)
+            .build()
+            .from_str_async::<Manifest>(toml)
+            .await
+            .expect("TOML deserialization failed");
+
+        pretty_assertions::assert_eq!(
+            manifest,
+            Manifest {
+                package: Package {
+                    name: "tombi".to_string()
+                }
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_with_validation_without_schema() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Config {
+            key: String,
+        }
+
+        let schema_store = SchemaStore::new_with_options(tombi_schema_store::Options {
+            offline: Some(true),
+            ..Default::default()
+        });
+
+        let error = Deserializer::builder()
+            .schema_store(&schema_store)
+            .validate(true)
+            .build()
+            .from_str_async::<Config>(r#"key = "value""#)
+            .await
+            .unwrap_err();
+
+        assert!(error.is_schema_not_found());
+    }
 }
@@ -0,0 +1,575 @@
+use serde::de::IntoDeserializer;
+use serde::forward_to_deserialize_any;
+use tombi_document::de::PathSegment;
+use tombi_toml_version::TomlVersion;
+
+use crate::spanned;
+
+/// The source text of the document tree, to borrow the strings from.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Source<'de> {
+    text: Option<&'de str>,
+    toml_version: TomlVersion,
+}
+
+impl<'de> Source<'de> {
+    pub(crate) fn new(text: Option<&'de str>, toml_version: TomlVersion) -> Self {
+        Self { text, toml_version }
+    }
+
+    /// Borrows the unescaped text from the source text.
+    ///
+    /// The owned value of the document tree is compared with the text between the quotes,
+    /// so the string is still copied once when the document tree is built.
+    /// Returns `None` if the text between the quotes differs from the unescaped text,
+    /// such as the strings with the escapes.
+    fn borrow_str(
+        &self,
+        span: tombi_text::Span,
+        quote_len: usize,
+        value: &str,
+    ) -> Option<&'de str> {
+        let quoted = self
+            .text?
+            .get(usize::from(span.start())..usize::from(span.end()))?;
+        let mut text = quoted.get(quote_len..quoted.len().checked_sub(quote_len)?)?;
+        if quote_len == 3 {
+            // NOTE: The line break right after the opening delimiter is trimmed.
+            text = text
+                .strip_prefix("\r\n")
+                .or_else(|| text.strip_prefix('\n'))
+                .unwrap_or(text);
+        }
+
+        (text == value).then_some(text)
+    }
+}
+
+/// Deserializer of the document tree.
+///
+/// Provides the ranges of the keys and the values to [crate::Spanned],
+/// and borrows the strings without the escapes from the source text.
+pub(crate) struct TableDeserializer<'a, 'de> {
+    table: &'a tombi_document_tree::Table,
+    source: Source<'de>,
+}
+
+impl<'a, 'de> TableDeserializer<'a, 'de> {
+    pub(crate) fn new(table: &'a tombi_document_tree::Table, source: Source<'de>) -> Self {
+        Self { table, source }
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for TableDeserializer<'_, 'de> {
+    type Error = tombi_document::de::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_map(TableAccess {
+            iter: self.table.key_values().iter(),
+            next: None,
+            source: self.source,
+        })
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let mut iter = self.table.key_values().iter();
+        match (iter.next(), iter.next()) {
+            // enums are encoded as tables with a single key
+            (Some((key, value)), None) => visitor.visit_enum(EnumAccess {
+                variant: KeyDeserializer {
+                    key,
+                    source: self.source,
+                },
+                value: Some(ValueDeserializer {
+                    value,
+                    key_range: Some(key.range()),
+                    source: self.source,
+                }),
+            }),
+            _ => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Map,
+                &"map with a single key",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+struct ValueDeserializer<'a, 'de> {
+    value: &'a tombi_document_tree::Value,
+    key_range: Option<tombi_text::Range>,
+    source: Source<'de>,
+}
+
+impl<'de> ValueDeserializer<'_, 'de> {
+    fn visit_str<V>(
+        &self,
+        value: &tombi_document_tree::String,
+        visitor: V,
+    ) -> Result<V::Value, tombi_document::de::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let (token, quote_len) = match value.kind() {
+            tombi_document_tree::StringKind::BasicString(node) => (node.token(), 1),
+            tombi_document_tree::StringKind::LiteralString(node) => (node.token(), 1),
+            tombi_document_tree::StringKind::MultiLineBasicString(node) => (node.token(), 3),
+            tombi_document_tree::StringKind::MultiLineLiteralString(node) => (node.token(), 3),
+        };
+
+        match token.and_then(|token| {
+            self.source
+                .borrow_str(token.span(), quote_len, value.value())
+        }) {
+            Some(text) => visitor.visit_borrowed_str(text),
+            None => visitor.visit_str(value.value()),
+        }
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for ValueDeserializer<'_, 'de> {
+    type Error = tombi_document::de::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value {
+            tombi_document_tree::Value::Boolean(boolean) => visitor.visit_bool(boolean.value()),
+            tombi_document_tree::Value::Integer(integer) => visitor.visit_i64(integer.value()),
+            tombi_document_tree::Value::Float(float) => visitor.visit_f64(float.value()),
+            tombi_document_tree::Value::String(string) => self.visit_str(string, visitor),
+            tombi_document_tree::Value::OffsetDateTime(date_time) => {
+                visitor.visit_newtype_struct(date_time_deserializer(date_time.value()))
+            }
+            tombi_document_tree::Value::LocalDateTime(date_time) => {
+                visitor.visit_newtype_struct(date_time_deserializer(date_time.value()))
+            }
+            tombi_document_tree::Value::LocalDate(date) => {
+                visitor.visit_newtype_struct(date_time_deserializer(date.value()))
+            }
+            tombi_document_tree::Value::LocalTime(time) => {
+                visitor.visit_newtype_struct(date_time_deserializer(time.value()))
+            }
+            tombi_document_tree::Value::Array(array) => visitor.visit_seq(ArrayAccess {
+                iter: array.values().iter().enumerate(),
+                source: self.source,
+            }),
+            tombi_document_tree::Value::Table(table) => {
+                TableDeserializer::new(table, self.source).deserialize_any(visitor)
+            }
+            tombi_document_tree::Value::Incomplete { .. } => {
+                Err(serde::de::Error::custom("incomplete value"))
+            }
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        if name == spanned::NAME && fields == spanned::FIELDS {
+            visitor.visit_map(SpannedAccess {
+                key_range: self.key_range,
+                range: Some(self.value.range()),
+                value: Some(self),
+            })
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value {
+            tombi_document_tree::Value::OffsetDateTime(_)
+            | tombi_document_tree::Value::LocalDateTime(_)
+            | tombi_document_tree::Value::LocalDate(_)
+            | tombi_document_tree::Value::LocalTime(_) => self.deserialize_any(visitor),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value {
+            tombi_document_tree::Value::Table(table) => {
+                TableDeserializer::new(table, self.source).deserialize_enum(name, variants, visitor)
+            }
+            tombi_document_tree::Value::String(_) => visitor.visit_enum(EnumAccess {
+                variant: self,
+                value: None::<ValueDeserializer>,
+            }),
+            value => Err(serde::de::Error::invalid_type(
+                unexpected(value),
+                &"string or map",
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map identifier
+    }
+}
+
+struct KeyDeserializer<'a, 'de> {
+    key: &'a tombi_document_tree::Key,
+    source: Source<'de>,
+}
+
+impl<'de> serde::Deserializer<'de> for KeyDeserializer<'_, 'de> {
+    type Error = tombi_document::de::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.key.to_raw_text(self.source.toml_version);
+        let quote_len = match self.key.kind() {
+            tombi_document_tree::KeyKind::BareKey => 0,
+            tombi_document_tree::KeyKind::BasicString
+            | tombi_document_tree::KeyKind::LiteralString => 1,
+        };
+
+        match self.source.borrow_str(self.key.span(), quote_len, &value) {
+            Some(text) => visitor.visit_borrowed_str(text),
+            None => visitor.visit_string(value),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
+
+struct TableAccess<'a, 'de, I> {
+    iter: I,
+    next: Option<(&'a tombi_document_tree::Key, ValueDeserializer<'a, 'de>)>,
+    source: Source<'de>,
+}
+
+impl<'a, 'de, I> serde::de::MapAccess<'de> for TableAccess<'a, 'de, I>
+where
+    I: Iterator<Item = (&'a tombi_document_tree::Key, &'a tombi_document_tree::Value)>,
+{
+    type Error = tombi_document::de::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.next = Some((
+                    key,
+                    ValueDeserializer {
+                        value,
+                        key_range: Some(key.range()),
+                        source: self.source,
+                    },
+                ));
+                seed.deserialize(KeyDeserializer {
+                    key,
+                    source: self.source,
+                })
+                .map(Some)
+                .map_err(|error| error.with_path_segment(key_path_segment(key, self.source)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        match self.next.take() {
+            Some((key, value)) => seed
+                .deserialize(value)
+                .map_err(|error| error.with_path_segment(key_path_segment(key, self.source))),
+            None => Err(serde::de::Error::custom("value is missing")),
+        }
+    }
+}
+
+struct ArrayAccess<'de, I> {
+    iter: I,
+    source: Source<'de>,
+}
+
+impl<'a, 'de, I> serde::de::SeqAccess<'de> for ArrayAccess<'de, I>
+where
+    I: Iterator<Item = (usize, &'a tombi_document_tree::Value)>,
+{
+    type Error = tombi_document::de::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((index, value)) => seed
+                .deserialize(ValueDeserializer {
+                    value,
+                    key_range: None,
+                    source: self.source,
+                })
+                .map(Some)
+                .map_err(|error| error.with_path_segment(PathSegment::Index(index))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Provides the variant and the value of the enum.
+///
+/// The unit variants are strings, and the other variants are tables with a single key.
+struct EnumAccess<K, V> {
+    variant: K,
+    value: Option<V>,
+}
+
+impl<'de, K, V> serde::de::EnumAccess<'de> for EnumAccess<K, V>
+where
+    K: serde::Deserializer<'de, Error = tombi_document::de::Error>,
+    V: serde::Deserializer<'de, Error = tombi_document::de::Error>,
+{
+    type Error = tombi_document::de::Error;
+    type Variant = VariantAccess<V>;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error>
+    where
+        S: serde::de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.variant)
+            .map(|variant| (variant, VariantAccess { value: self.value }))
+    }
+}
+
+struct VariantAccess<V> {
+    value: Option<V>,
+}
+
+impl<'de, V> serde::de::VariantAccess<'de> for VariantAccess<V>
+where
+    V: serde::Deserializer<'de, Error = tombi_document::de::Error>,
+{
+    type Error = tombi_document::de::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            Some(value) => serde::Deserialize::deserialize(value),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(serde::de::Error::invalid_type(
+                serde::de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<T>(self, _len: usize, visitor: T) -> Result<T::Value, Self::Error>
+    where
+        T: serde::de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => value.deserialize_seq(visitor),
+            None => Err(serde::de::Error::invalid_type(
+                serde::de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<T>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: T,
+    ) -> Result<T::Value, Self::Error>
+    where
+        T: serde::de::Visitor<'de>,
+    {
+        match self.value {
+            Some(value) => value.deserialize_map(visitor),
+            None => Err(serde::de::Error::invalid_type(
+                serde::de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+/// Provides the ranges and the value to [crate::Spanned] as a map of the private fields.
+struct SpannedAccess<'a, 'de> {
+    key_range: Option<tombi_text::Range>,
+    range: Option<tombi_text::Range>,
+    value: Option<ValueDeserializer<'a, 'de>>,
+}
+
+impl<'de> serde::de::MapAccess<'de> for SpannedAccess<'_, 'de> {
+    type Error = tombi_document::de::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        let field = if self.key_range.is_some() {
+            spanned::KEY_RANGE_FIELD
+        } else if self.range.is_some() {
+            spanned::RANGE_FIELD
+        } else if self.value.is_some() {
+            spanned::VALUE_FIELD
+        } else {
+            return Ok(None);
+        };
+
+        seed.deserialize(field.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        if let Some(key_range) = self.key_range.take() {
+            seed.deserialize(range_deserializer(key_range))
+        } else if let Some(range) = self.range.take() {
+            seed.deserialize(range_deserializer(range))
+        } else if let Some(value) = self.value.take() {
+            seed.deserialize(value)
+        } else {
+            Err(serde::de::Error::custom("value is missing"))
+        }
+    }
+}
+
+fn range_deserializer(
+    range: tombi_text::Range,
+) -> serde::de::value::SeqDeserializer<std::array::IntoIter<u32, 4>, tombi_document::de::Error> {
+    serde::de::value::SeqDeserializer::new(spanned::range_to_array(range).into_iter())
+}
+
+/// The date-time types of [tombi_date_time] are deserialized from the newtype of the string.
+fn date_time_deserializer(
+    value: &impl std::fmt::Display,
+) -> serde::de::value::StringDeserializer<tombi_document::de::Error> {
+    value.to_string().into_deserializer()
+}
+
+fn key_path_segment(key: &tombi_document_tree::Key, source: Source) -> PathSegment {
+    PathSegment::Key(key.to_raw_text(source.toml_version))
+}
+
+fn unexpected(value: &tombi_document_tree::Value) -> serde::de::Unexpected<'_> {
+    match value {
+        tombi_document_tree::Value::Boolean(boolean) => {
+            serde::de::Unexpected::Bool(boolean.value())
+        }
+        tombi_document_tree::Value::Integer(integer) => {
+            serde::de::Unexpected::Signed(integer.value())
+        }
+        tombi_document_tree::Value::Float(float) => serde::de::Unexpected::Float(float.value()),
+        tombi_document_tree::Value::String(string) => serde::de::Unexpected::Str(string.value()),
+        tombi_document_tree::Value::OffsetDateTime(_) => {
+            serde::de::Unexpected::Other(tombi_date_time::OffsetDateTime::type_name())
+        }
+        tombi_document_tree::Value::LocalDateTime(_) => {
+            serde::de::Unexpected::Other(tombi_date_time::LocalDateTime::type_name())
+        }
+        tombi_document_tree::Value::LocalDate(_) => {
+            serde::de::Unexpected::Other(tombi_date_time::LocalDate::type_name())
+        }
+        tombi_document_tree::Value::LocalTime(_) => {
+            serde::de::Unexpected::Other(tombi_date_time::LocalTime::type_name())
+        }
+        tombi_document_tree::Value::Array(_) => serde::de::Unexpected::Seq,
+        tombi_document_tree::Value::Table(_) => serde::de::Unexpected::Map,
+        tombi_document_tree::Value::Incomplete { .. } => {
+            serde::de::Unexpected::Other("incomplete value")
+        }
+    }
+}
@@ -7,20 +7,33 @@ enum InnerError {
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
+    #[error(transparent)]
+    Utf8(#[from] std::str::Utf8Error),
+
     #[error(transparent)]
     Config(#[from] tombi_config::Error),
 
     #[error(transparent)]
     SchemaStore(#[from] tombi_schema_store::Error),
 
-    #[error(transparent)]
-    DocumentDeserialize(#[from] tombi_document::de::Error),
+    #[error("{}", deserialize_message(.message, .path, .range))]
+    DocumentDeserialize {
+        message: std::string::String,
+        path: std::string::String,
+        range: Option<tombi_text::Range>,
+    },
 
     #[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", "))]
     Parser(Vec<tombi_parser::Error>),
 
     #[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", "))]
     DocumentTree(Vec<tombi_document_tree::Error>),
+
+    #[error("{}", .0.iter().map(|diagnostic| diagnostic.message()).collect::<Vec<_>>().join(", "))]
+    Validation(Vec<tombi_diagnostic::Diagnostic>),
+
+    #[error("no schema found to validate the TOML text")]
+    SchemaNotFound,
 }
 
 impl From<InnerError> for Error {
@@ -35,6 +48,12 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<std::str::Utf8Error> for Error {
+    fn from(error: std::str::Utf8Error) -> Self {
+        Self(Box::new(InnerError::Utf8(error)))
+    }
+}
+
 impl From<tombi_config::Error> for Error {
     fn from(error: tombi_config::Error) -> Self {
         Self(Box::new(InnerError::Config(error)))
@@ -53,6 +72,12 @@ impl From<Vec<tombi_document_tree::Error>> for Error {
     }
 }
 
+impl From<Vec<tombi_diagnostic::Diagnostic>> for Error {
+    fn from(diagnostics: Vec<tombi_diagnostic::Diagnostic>) -> Self {
+        Self(Box::new(InnerError::Validation(diagnostics)))
+    }
+}
+
 impl From<tombi_schema_store::Error> for Error {
     fn from(error: tombi_schema_store::Error) -> Self {
         Self(Box::new(InnerError::SchemaStore(error)))
@@ -61,6 +86,138 @@ impl From<tombi_schema_store::Error> for Error {
 
 impl From<tombi_document::de::Error> for Error {
     fn from(error: tombi_document::de::Error) -> Self {
-        Self(Box::new(InnerError::DocumentDeserialize(error)))
+        Self(Box::new(InnerError::DocumentDeserialize {
+            message: error.message(),
+            path: tombi_document::de::join_path(error.path()),
+            range: None,
+        }))
+    }
+}
+
+impl Error {
+    pub(crate) fn schema_not_found() -> Self {
+        Self(Box::new(InnerError::SchemaNotFound))
+    }
+
+    /// Create an error with the range of the value at the key path in the document tree.
+    pub(crate) fn from_deserialize_error(
+        error: tombi_document::de::Error,
+        document_tree: &tombi_document_tree::DocumentTree,
+    ) -> Self {
+        let range = (!error.path().is_empty())
+            .then(|| find_range(document_tree, error.path()))
+            .flatten();
+
+        Self(Box::new(InnerError::DocumentDeserialize {
+            message: error.message(),
+            path: tombi_document::de::join_path(error.path()),
+            range,
+        }))
+    }
+
+    /// The dotted key path to the value that failed to deserialize, such as `server.port`.
+    pub fn path(&self) -> Option<&str> {
+        match self.0.as_ref() {
+            InnerError::DocumentDeserialize { path, .. } if !path.is_empty() => Some(path),
+            _ => None,
+        }
+    }
+
+    /// The range of the source text where the error occurred.
+    pub fn range(&self) -> Option<tombi_text::Range> {
+        match self.0.as_ref() {
+            InnerError::DocumentDeserialize { range, .. } => *range,
+            InnerError::Parser(errors) => errors.first().map(|error| error.range()),
+            InnerError::DocumentTree(errors) => errors.first().map(|error| error.range()),
+            InnerError::Validation(diagnostics) => {
+                diagnostics.first().map(|diagnostic| diagnostic.range())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the validation was requested, but no schema was resolved for the TOML text.
+    pub fn is_schema_not_found(&self) -> bool {
+        matches!(self.0.as_ref(), InnerError::SchemaNotFound)
+    }
+
+    /// The diagnostics of the schema validation, including the warnings.
+    pub fn validation_diagnostics(&self) -> Option<&[tombi_diagnostic::Diagnostic]> {
+        match self.0.as_ref() {
+            InnerError::Validation(diagnostics) => Some(diagnostics),
+            _ => None,
+        }
+    }
+}
+
+impl tombi_diagnostic::SetDiagnostics for Error {
+    fn set_diagnostics(self, diagnostics: &mut Vec<tombi_diagnostic::Diagnostic>) {
+        match *self.0 {
+            InnerError::Parser(errors) => errors.set_diagnostics(diagnostics),
+            InnerError::DocumentTree(errors) => errors.set_diagnostics(diagnostics),
+            InnerError::Validation(validation_diagnostics) => {
+                diagnostics.extend(validation_diagnostics)
+            }
+            InnerError::DocumentDeserialize {
+                message,
+                path,
+                range,
+            } => {
+                let message = if path.is_empty() {
+                    message
+                } else {
+                    format!("{message} for `{path}`")
+                };
+                diagnostics.push(tombi_diagnostic::Diagnostic::new_error(
+                    message,
+                    range.unwrap_or_default(),
+                ));
+            }
+            error => diagnostics.push(tombi_diagnostic::Diagnostic::new_error(
+                error.to_string(),
+                tombi_text::Range::default(),
+            )),
+        }
+    }
+}
+
+fn find_range(
+    document_tree: &tombi_document_tree::DocumentTree,
+    path: &[tombi_document::de::PathSegment],
+) -> Option<tombi_text::Range> {
+    let mut value: &tombi_document_tree::Value = document_tree.into();
+    for segment in path {
+        value = match (value, segment) {
+            (
+                tombi_document_tree::Value::Table(table),
+                tombi_document::de::PathSegment::Key(key),
+            ) => table.get(key.as_str())?,
+            (
+                tombi_document_tree::Value::Array(array),
+                tombi_document::de::PathSegment::Index(index),
+            ) => array.get(*index)?,
+            _ => return None,
+        };
+    }
+
+    Some(value.range())
+}
+
+fn deserialize_message(
+    message: &str,
+    path: &str,
+    range: &Option<tombi_text::Range>,
+) -> std::string::String {
+    let mut text = message.to_string();
+    if !path.is_empty() {
+        text.push_str(&format!(" for `{path}`"));
+    }
+    if let Some(range) = range {
+        text.push_str(&format!(
+            " at line {} column {}",
+            range.start.line + 1,
+            range.start.column + 1
+        ));
     }
+    text
 }
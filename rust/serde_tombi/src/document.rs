@@ -36,9 +36,9 @@ impl ToTomlString for (&tombi_document::Key, &tombi_document::Value) {
                     parent_keys.iter().chain(&[key]).copied().join(".")
                 ));
                 value.to_toml_string(result, &[]);
+                result.push('\n');
             }
         }
-        result.push('\n');
     }
 }
 
@@ -49,9 +49,9 @@ impl ToTomlString for tombi_document::Value {
         parent_keys: &[&tombi_document::Key],
     ) {
         match self {
-            tombi_document::Value::String(s) => result.push_str(&format!("\"{}\"", s.value())),
+            tombi_document::Value::String(s) => s.to_toml_string(result, parent_keys),
             tombi_document::Value::Integer(i) => result.push_str(&i.value().to_string()),
-            tombi_document::Value::Float(f) => result.push_str(&f.value().to_string()),
+            tombi_document::Value::Float(f) => f.to_toml_string(result, parent_keys),
             tombi_document::Value::Boolean(b) => result.push_str(&b.value().to_string()),
             tombi_document::Value::Array(a) => a.to_toml_string(result, parent_keys),
             tombi_document::Value::Table(t) => t.to_toml_string(result, parent_keys),
@@ -138,14 +138,8 @@ impl ToTomlString for tombi_document::Table {
                     if i != 0 {
                         result.push_str(", ");
                     }
-                    result.push_str(&format!(
-                        "{} = ",
-                        parent_keys.iter().chain(&[key]).copied().join(".")
-                    ));
-                    value.to_toml_string(
-                        result,
-                        &parent_keys.iter().chain(&[key]).copied().collect_vec(),
-                    );
+                    result.push_str(&format!("{key} = "));
+                    value.to_toml_string(result, &[]);
                 }
                 result.push('}');
             }
@@ -172,7 +166,7 @@ impl ToTomlString for tombi_document::Array {
                         if i != 0 {
                             result.push_str(", ");
                         }
-                        value.to_toml_string(result, parent_keys);
+                        value.to_toml_string(result, &[]);
                     }
                 }
                 result.push(']');
@@ -193,20 +187,14 @@ impl ToTomlString for tombi_document::Array {
                                 tombi_document::Value::Table(table)
                                     if table.kind() == tombi_document::TableKind::KeyValue =>
                                 {
-                                    table.to_toml_string(
-                                        result,
-                                        &parent_keys.iter().chain(&[key]).copied().collect_vec(),
-                                    );
+                                    (key, value).to_toml_string(result, &[]);
                                 }
                                 _ => {
-                                    result.push_str(&format!("{} = ", key));
-                                    value.to_toml_string(
-                                        result,
-                                        &parent_keys.iter().chain(&[key]).copied().collect_vec(),
-                                    );
+                                    result.push_str(&format!("{key} = "));
+                                    value.to_toml_string(result, &[]);
+                                    result.push('\n');
                                 }
                             }
-                            result.push('\n');
                         }
                     }
                 }
@@ -240,7 +228,9 @@ impl ToTomlString for tombi_document::Float {
         } else if self.value().is_nan() {
             result.push_str("nan");
         } else {
-            result.push_str(&self.value().to_string());
+            // NOTE: `Debug` keeps the fraction of the integral floats, such as `1.0`,
+            //       and uses the exponent for the large ones, such as `1e300`.
+            result.push_str(&format!("{:?}", self.value()));
         }
     }
 }
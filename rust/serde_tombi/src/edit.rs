@@ -0,0 +1,1864 @@
+//! Format-preserving editing of TOML documents.
+//!
+//! The edits are applied to the syntax tree of the source in place,
+//! so the comments and the layout of the untouched parts are kept as is.
+
+mod error;
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use serde::Serialize;
+use tombi_ast::AstNode;
+use tombi_ast_editor::Change;
+use tombi_document::IntoDocument;
+use tombi_document_tree::IntoDocumentTreeAndErrors;
+use tombi_formatter::formatter::definitions::FormatDefinitions;
+use tombi_formatter::FormatOptions;
+use tombi_schema_store::{Accessor, Accessors, SchemaStore};
+use tombi_syntax::{SyntaxElement, SyntaxKind, SyntaxNode};
+use tombi_toml_version::TomlVersion;
+
+use crate::document::ToTomlString;
+pub use error::Error;
+
+/// A TOML document that can be edited in place.
+///
+/// Unlike [crate::Document], the edits are applied to the syntax tree of the source,
+/// so the comments and the layout of the untouched parts are preserved.
+///
+/// The values are addressed by the key path, such as `package.version` or `bin[0].name`.
+/// The keys can be quoted as in TOML, such as `package.metadata."docs.rs"`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_tombi::DocumentMut;
+///
+/// let mut document: DocumentMut = r#"
+/// [package]
+/// name = "tombi"
+/// version = "0.1.0"  # bumped by the release script
+/// "#
+/// .parse()
+/// .unwrap();
+///
+/// document.insert("package.version", &"0.2.0").unwrap();
+///
+/// assert_eq!(
+///     document.to_string(),
+///     r#"
+/// [package]
+/// name = "tombi"
+/// version = "0.2.0"  # bumped by the release script
+/// "#
+/// );
+/// ```
+#[derive(Debug)]
+pub struct DocumentMut {
+    root: tombi_ast::Root,
+    toml_version: TomlVersion,
+    line_ending: &'static str,
+    touched: Vec<SyntaxNode>,
+}
+
+impl DocumentMut {
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        Self::parse_with_toml_version(source, TomlVersion::default())
+    }
+
+    pub fn parse_with_toml_version(source: &str, toml_version: TomlVersion) -> Result<Self, Error> {
+        let root = tombi_parser::parse(source, toml_version)
+            .try_into_root()
+            .map_err(Error::Parser)?;
+
+        let (_, errors) = root
+            .clone()
+            .into_document_tree_and_errors(toml_version)
+            .into();
+        if !errors.is_empty() {
+            return Err(Error::DocumentTree(errors));
+        }
+
+        Ok(Self {
+            root: root.clone_for_update(),
+            toml_version,
+            line_ending: if source.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            touched: Vec::new(),
+        })
+    }
+
+    /// Convert into the document, dropping the comments and the layout.
+    pub fn to_document(&self) -> tombi_document::Document {
+        let (document_tree, _) = self
+            .root
+            .clone()
+            .into_document_tree_and_errors(self.toml_version)
+            .into();
+
+        document_tree.into_document(self.toml_version)
+    }
+
+    /// Get the value of the key path.
+    pub fn get(&self, key_path: &str) -> Result<Option<tombi_document::Value>, Error> {
+        let accessors = parse_key_path(key_path)?;
+
+        Ok(self.get_by_accessors(&accessors))
+    }
+
+    /// Insert the value into the key path, or replace the existing value.
+    ///
+    /// The replaced value keeps its comments.
+    /// The new key-value is appended to the nearest table of the key path,
+    /// and the tables are written as inline tables.
+    ///
+    /// Fails with [Error::AlreadyExists] if the key path is defined only implicitly,
+    /// such as `dependencies` of `[dependencies.serde]`.
+    pub fn insert<T>(&mut self, key_path: &str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+    {
+        let accessors = parse_key_path(key_path)?;
+        let value_text = to_inline_text(&serialize_value(value, &accessors)?);
+        let entries = self.entries();
+
+        if let Some(entry) = find_entry(&entries, &accessors) {
+            return match &entry.node {
+                EntryNode::KeyValue(key_value) => {
+                    if let Some(value) = key_value.value() {
+                        self.replace_value(value.syntax(), &value_text)?;
+                    }
+                    Ok(())
+                }
+                EntryNode::ArrayValue(value) => self.replace_value(value.syntax(), &value_text),
+                EntryNode::Table(_) | EntryNode::ArrayOfTable(_) => {
+                    Err(Error::KeyValueRequired(Accessors::new(accessors)))
+                }
+            };
+        }
+        // NOTE: The value may exist only implicitly, such as `dependencies` of `[dependencies.serde]`.
+        if self.get_by_accessors(&accessors).is_some() {
+            return Err(Error::AlreadyExists(Accessors::new(accessors)));
+        }
+
+        for parent_len in (0..accessors.len()).rev() {
+            let (parent_accessors, key_accessors) = accessors.split_at(parent_len);
+            let Some(keys_text) = keys_text(key_accessors) else {
+                return Err(Error::NotFound(Accessors::new(accessors)));
+            };
+            let key_value_text = format!("{keys_text} = {value_text}");
+
+            if parent_accessors.is_empty() {
+                return self.append_root_key_value(&key_value_text);
+            }
+
+            let table_required = || Error::TableRequired(Accessors::new(parent_accessors.to_vec()));
+            match find_entry(&entries, parent_accessors).map(|entry| &entry.node) {
+                Some(EntryNode::Table(table)) => {
+                    let base = match table.key_values().last() {
+                        Some(key_value) => SyntaxElement::Node(key_value.syntax().clone()),
+                        None => header_end(table.syntax()).ok_or_else(table_required)?,
+                    };
+                    self.append_key_value(base, &key_value_text)?;
+                }
+                Some(EntryNode::ArrayOfTable(array_of_table)) => {
+                    let base = match array_of_table.key_values().last() {
+                        Some(key_value) => SyntaxElement::Node(key_value.syntax().clone()),
+                        None => header_end(array_of_table.syntax()).ok_or_else(table_required)?,
+                    };
+                    self.append_key_value(base, &key_value_text)?;
+                }
+                Some(EntryNode::KeyValue(key_value)) => match key_value.value() {
+                    Some(tombi_ast::Value::InlineTable(inline_table)) => {
+                        self.append_inline_table_key_value(&inline_table, &key_value_text)?
+                    }
+                    _ => return Err(table_required()),
+                },
+                Some(EntryNode::ArrayValue(tombi_ast::Value::InlineTable(inline_table))) => {
+                    self.append_inline_table_key_value(inline_table, &key_value_text)?
+                }
+                Some(EntryNode::ArrayValue(_)) => return Err(table_required()),
+                None => {
+                    // NOTE: The parent may be a table defined implicitly by the dotted keys or the headers,
+                    //       but an array of tables cannot be extended by the dotted keys.
+                    if entries.iter().any(|entry| {
+                        entry.accessors.starts_with(parent_accessors)
+                            && entry
+                                .accessors
+                                .get(parent_len)
+                                .is_some_and(Accessor::is_index)
+                    }) {
+                        return Err(table_required());
+                    }
+                    continue;
+                }
+            }
+            return Ok(());
+        }
+
+        Ok(())
+    }
+
+    /// Add an empty table, such as `[dependencies.serde]`.
+    ///
+    /// The table is placed after the tables sharing the longest key path prefix,
+    /// or at the end of the document.
+    pub fn insert_table(&mut self, key_path: &str) -> Result<(), Error> {
+        let accessors = parse_key_path(key_path)?;
+        if accessors.iter().any(Accessor::is_index) {
+            return Err(Error::IndexInTableHeader(Accessors::new(accessors)));
+        }
+        if self.get_by_accessors(&accessors).is_some() {
+            return Err(Error::AlreadyExists(Accessors::new(accessors)));
+        }
+
+        let entries = self.entries();
+        for parent_len in 1..accessors.len() {
+            let parent_accessors = &accessors[..parent_len];
+            let is_table = match self.get_by_accessors(parent_accessors) {
+                Some(tombi_document::Value::Table(_)) => !matches!(
+                    find_entry(&entries, parent_accessors).map(|entry| &entry.node),
+                    Some(EntryNode::KeyValue(_))
+                ),
+                Some(_) => false,
+                None => true,
+            };
+            if !is_table {
+                return Err(Error::TableRequired(Accessors::new(
+                    parent_accessors.to_vec(),
+                )));
+            }
+        }
+
+        let header_text = keys_text(&accessors).unwrap_or_default();
+        let base = last_related_item(&entries, &accessors, 1);
+        self.insert_section(&format!("[{header_text}]"), base)
+    }
+
+    /// Append the value to the array of the key path.
+    ///
+    /// If the key path is an array of tables, such as `[[bin]]`,
+    /// the table is appended as a new `[[bin]]` after the existing ones.
+    /// If nothing exists in the key path, the table starts a new array of tables.
+    pub fn append<T>(&mut self, key_path: &str, value: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+    {
+        let accessors = parse_key_path(key_path)?;
+        let value = serialize_value(value, &accessors)?;
+        let entries = self.entries();
+
+        match find_entry(&entries, &accessors).map(|entry| &entry.node) {
+            Some(EntryNode::KeyValue(key_value)) => match key_value.value() {
+                Some(tombi_ast::Value::Array(array)) => {
+                    self.append_array_value(&array, &to_inline_text(&value))
+                }
+                _ => Err(Error::ArrayRequired(Accessors::new(accessors))),
+            },
+            Some(EntryNode::ArrayValue(tombi_ast::Value::Array(array))) => {
+                self.append_array_value(array, &to_inline_text(&value))
+            }
+            Some(_) => Err(Error::ArrayRequired(Accessors::new(accessors))),
+            None => {
+                let tombi_document::Value::Table(table) = &value else {
+                    return Err(match self.get_by_accessors(&accessors) {
+                        Some(_) => Error::ArrayRequired(Accessors::new(accessors)),
+                        None => Error::NotFound(Accessors::new(accessors)),
+                    });
+                };
+
+                let last_array_of_table = entries
+                    .iter()
+                    .filter_map(|entry| match &entry.node {
+                        EntryNode::ArrayOfTable(array_of_table)
+                            if entry.accessors.len() == accessors.len() + 1
+                                && entry.accessors.starts_with(&accessors) =>
+                        {
+                            Some(array_of_table)
+                        }
+                        _ => None,
+                    })
+                    .next_back();
+
+                let header_text = match last_array_of_table {
+                    Some(array_of_table) => array_of_table
+                        .header()
+                        .map(|header| header.syntax().to_string().trim().to_string())
+                        .unwrap_or_default(),
+                    None => {
+                        if accessors.iter().any(Accessor::is_index) {
+                            return Err(Error::IndexInTableHeader(Accessors::new(accessors)));
+                        }
+                        if self.get_by_accessors(&accessors).is_some() {
+                            return Err(Error::ArrayRequired(Accessors::new(accessors)));
+                        }
+                        keys_text(&accessors).unwrap_or_default()
+                    }
+                };
+
+                let mut section_text = format!("[[{header_text}]]");
+                for (key, value) in table.key_values() {
+                    section_text.push_str(self.line_ending);
+                    section_text.push_str(&format!(
+                        "{} = {}",
+                        key_text(key.value()),
+                        to_inline_text(value)
+                    ));
+                }
+
+                let base = match last_array_of_table {
+                    Some(_) => last_related_item(&entries, &accessors, accessors.len()),
+                    None => last_related_item(&entries, &accessors, 1),
+                };
+                self.insert_section(&section_text, base)
+            }
+        }
+    }
+
+    /// Remove the value of the key path, and return the removed value.
+    ///
+    /// The key-values and the tables under the key path are removed together with their comments.
+    pub fn remove(&mut self, key_path: &str) -> Result<Option<tombi_document::Value>, Error> {
+        let accessors = parse_key_path(key_path)?;
+        let Some(value) = self.get_by_accessors(&accessors) else {
+            return Ok(None);
+        };
+
+        let targets = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.accessors.starts_with(&accessors))
+            .collect_vec();
+        let target_nodes = targets
+            .iter()
+            .map(|entry| entry.node.syntax().clone())
+            .collect_vec();
+
+        let mut changes = vec![];
+        let mut removes_last_item = false;
+        for entry in &targets {
+            let node = entry.node.syntax();
+            if node
+                .ancestors()
+                .skip(1)
+                .any(|ancestor| target_nodes.contains(&ancestor))
+            {
+                continue;
+            }
+
+            match &entry.node {
+                EntryNode::KeyValue(_) if is_item_key_value(node) => {
+                    changes.extend(remove_line(node));
+                }
+                EntryNode::KeyValue(_) | EntryNode::ArrayValue(_) => {
+                    self.touch(node);
+                    changes.extend(remove_element(node));
+                }
+                EntryNode::Table(_) | EntryNode::ArrayOfTable(_) => {
+                    if node.next_sibling().is_none() {
+                        removes_last_item = true;
+                    }
+                    changes.push(Change::Remove {
+                        target: SyntaxElement::Node(node.clone()),
+                    });
+                }
+            }
+        }
+        tombi_ast_editor::apply_changes(&self.root, changes);
+
+        if removes_last_item {
+            self.trim_end();
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Rename the last key of the key path.
+    ///
+    /// The headers of the subtables are renamed together,
+    /// such as `[dependencies.serde.features]` for `dependencies.serde`.
+    pub fn rename(&mut self, key_path: &str, new_key: &str) -> Result<(), Error> {
+        let accessors = parse_key_path(key_path)?;
+        let Some((Accessor::Key(_), parent_accessors)) = accessors.split_last() else {
+            return Err(Error::InvalidKeyPath(key_path.to_string()));
+        };
+
+        if self.get_by_accessors(&accessors).is_none() {
+            return Err(Error::NotFound(Accessors::new(accessors)));
+        }
+        let new_accessors = parent_accessors
+            .iter()
+            .cloned()
+            .chain(std::iter::once(Accessor::Key(new_key.to_string())))
+            .collect_vec();
+        if self.get_by_accessors(&new_accessors).is_some() {
+            return Err(Error::AlreadyExists(Accessors::new(new_accessors)));
+        }
+
+        let key_index = parent_accessors.len();
+        let mut changes = vec![];
+        for entry in self.entries() {
+            if !entry.accessors.starts_with(&accessors) {
+                continue;
+            }
+            if let Some(Some(key)) = entry.keys.get(key_index) {
+                let new_key_node = self.make_key(new_key)?;
+                changes.push(Change::ReplaceRange {
+                    old: SyntaxElement::Node(key.syntax().clone())
+                        ..=SyntaxElement::Node(key.syntax().clone()),
+                    new: vec![SyntaxElement::Node(new_key_node.clone())],
+                });
+                self.touched.push(new_key_node);
+            }
+        }
+        tombi_ast_editor::apply_changes(&self.root, changes);
+
+        let touched = std::mem::take(&mut self.touched);
+        for node in &touched {
+            self.touch(node);
+        }
+
+        Ok(())
+    }
+
+    /// Format the key-values touched by the edits with the options,
+    /// leaving the rest of the document as is.
+    pub fn format_touched(&mut self, format_options: &FormatOptions) -> Result<(), Error> {
+        let schema_store = SchemaStore::new();
+        let mut formatter = tombi_formatter::Formatter::new(
+            self.toml_version,
+            FormatDefinitions::default(),
+            format_options,
+            None,
+            &schema_store,
+        );
+
+        let mut changes = vec![];
+        for node in std::mem::take(&mut self.touched) {
+            if !node
+                .ancestors()
+                .any(|ancestor| ancestor == *self.root.syntax())
+            {
+                continue;
+            }
+            let Some(key_value) = tombi_ast::KeyValue::cast(node.clone()) else {
+                continue;
+            };
+
+            let formatted_text = formatter.format_to_string(&key_value)?;
+            changes.push(Change::ReplaceRange {
+                old: SyntaxElement::Node(node.clone())..=SyntaxElement::Node(node),
+                new: vec![SyntaxElement::Node(self.make_key_value(&formatted_text)?)],
+            });
+        }
+        tombi_ast_editor::apply_changes(&self.root, changes);
+
+        Ok(())
+    }
+
+    fn get_by_accessors(&self, accessors: &[Accessor]) -> Option<tombi_document::Value> {
+        entries_value(&self.entries(), accessors, self.toml_version)
+    }
+
+    /// Collect the key-values, the array values and the tables with their key paths.
+    fn entries(&self) -> Vec<Entry> {
+        let mut entries = Vec::new();
+        collect_key_values(&[], self.root.key_values(), self.toml_version, &mut entries);
+
+        let mut array_of_tables_counts: HashMap<Vec<Accessor>, usize> = HashMap::new();
+        for table_or_array_of_table in self.root.table_or_array_of_tables() {
+            let Some(header_keys) = table_or_array_of_table.header().and_then(|header| {
+                header
+                    .keys()
+                    .map(|key| {
+                        key.try_to_raw_text(self.toml_version)
+                            .ok()
+                            .map(|key_text| (key, key_text))
+                    })
+                    .collect::<Option<Vec<_>>>()
+            }) else {
+                continue;
+            };
+
+            let header_keys_len = header_keys.len();
+            let mut accessors = Vec::with_capacity(header_keys_len);
+            let mut keys = Vec::with_capacity(header_keys_len);
+            for (i, (key, key_text)) in header_keys.into_iter().enumerate() {
+                accessors.push(Accessor::Key(key_text));
+                keys.push(Some(key));
+
+                let index = if i + 1 == header_keys_len
+                    && matches!(
+                        table_or_array_of_table,
+                        tombi_ast::TableOrArrayOfTable::ArrayOfTable(_)
+                    ) {
+                    let count = array_of_tables_counts.entry(accessors.clone()).or_default();
+                    *count += 1;
+                    Some(*count - 1)
+                } else {
+                    array_of_tables_counts
+                        .get(&accessors)
+                        .map(|count| count - 1)
+                };
+
+                if let Some(index) = index {
+                    accessors.push(Accessor::Index(index));
+                    keys.push(None);
+                }
+            }
+
+            let node = match table_or_array_of_table {
+                tombi_ast::TableOrArrayOfTable::Table(table) => {
+                    collect_key_values(
+                        &accessors,
+                        table.key_values(),
+                        self.toml_version,
+                        &mut entries,
+                    );
+                    EntryNode::Table(table)
+                }
+                tombi_ast::TableOrArrayOfTable::ArrayOfTable(array_of_table) => {
+                    collect_key_values(
+                        &accessors,
+                        array_of_table.key_values(),
+                        self.toml_version,
+                        &mut entries,
+                    );
+                    EntryNode::ArrayOfTable(array_of_table)
+                }
+            };
+
+            entries.push(Entry {
+                accessors,
+                keys,
+                node,
+            });
+        }
+
+        entries
+    }
+
+    fn replace_value(&mut self, old: &SyntaxNode, value_text: &str) -> Result<(), Error> {
+        let tailing_trivia_text = tailing_trivia(old)
+            .iter()
+            .map(|token| token.text().to_string())
+            .collect::<String>();
+        let new = self.make_value(&format!("{value_text}{tailing_trivia_text}"))?;
+
+        // NOTE: The leading comments and the indentation in the array are kept.
+        let leading_trivia = old
+            .children_with_tokens()
+            .take_while(|element| is_trivia(element.kind()))
+            .collect_vec();
+        new.splice_children(0..0, leading_trivia);
+
+        tombi_ast_editor::apply_changes(
+            &self.root,
+            vec![Change::ReplaceRange {
+                old: SyntaxElement::Node(old.clone())..=SyntaxElement::Node(old.clone()),
+                new: vec![SyntaxElement::Node(new.clone())],
+            }],
+        );
+        self.touch(&new);
+
+        Ok(())
+    }
+
+    fn append_key_value(&mut self, base: SyntaxElement, key_value_text: &str) -> Result<(), Error> {
+        let key_value = self.make_key_value(key_value_text)?;
+        tombi_ast_editor::apply_changes(
+            &self.root,
+            vec![Change::Append {
+                base,
+                new: vec![
+                    self.make_line_break()?,
+                    SyntaxElement::Node(key_value.clone()),
+                ],
+            }],
+        );
+        self.touch(&key_value);
+
+        Ok(())
+    }
+
+    fn append_root_key_value(&mut self, key_value_text: &str) -> Result<(), Error> {
+        if let Some(key_value) = self.root.key_values().last() {
+            return self.append_key_value(
+                SyntaxElement::Node(key_value.syntax().clone()),
+                key_value_text,
+            );
+        }
+
+        let key_value = self.make_key_value(key_value_text)?;
+        let change = match self.root.items().next() {
+            Some(item) => {
+                let new = vec![
+                    SyntaxElement::Node(key_value.clone()),
+                    self.make_line_break()?,
+                    self.make_line_break()?,
+                ];
+                match item.syntax().prev_sibling_or_token() {
+                    Some(base) => Change::Append { base, new },
+                    None => Change::AppendTop { new },
+                }
+            }
+            None => match self.root.syntax().last_child_or_token() {
+                Some(base) => {
+                    let mut new = vec![];
+                    if base.kind() != SyntaxKind::LINE_BREAK {
+                        new.push(self.make_line_break()?);
+                    }
+                    new.push(SyntaxElement::Node(key_value.clone()));
+                    new.push(self.make_line_break()?);
+                    Change::Append { base, new }
+                }
+                None => Change::AppendTop {
+                    new: vec![
+                        SyntaxElement::Node(key_value.clone()),
+                        self.make_line_break()?,
+                    ],
+                },
+            },
+        };
+        tombi_ast_editor::apply_changes(&self.root, vec![change]);
+        self.touch(&key_value);
+
+        Ok(())
+    }
+
+    fn append_inline_table_key_value(
+        &mut self,
+        inline_table: &tombi_ast::InlineTable,
+        key_value_text: &str,
+    ) -> Result<(), Error> {
+        match inline_table.key_values_with_comma().last() {
+            Some((key_value, comma)) => self.append_element(
+                key_value.syntax(),
+                comma.as_ref().map(AstNode::syntax),
+                key_value_text,
+                ("{ _ = 0,", "}"),
+            ),
+            None => self.replace_value(inline_table.syntax(), &format!("{{ {key_value_text} }}")),
+        }
+    }
+
+    fn append_array_value(
+        &mut self,
+        array: &tombi_ast::Array,
+        value_text: &str,
+    ) -> Result<(), Error> {
+        match array.values_with_comma().last() {
+            Some((value, comma)) => self.append_element(
+                value.syntax(),
+                comma.as_ref().map(AstNode::syntax),
+                value_text,
+                ("[0,", "]"),
+            ),
+            None => self.replace_value(array.syntax(), &format!("[{value_text}]")),
+        }
+    }
+
+    /// Append the element after the last element of the array or the inline table,
+    /// following the indentation and the trailing comma of the last element.
+    fn append_element(
+        &mut self,
+        last: &SyntaxNode,
+        comma: Option<&SyntaxNode>,
+        element_text: &str,
+        (template_start, template_end): (&str, &str),
+    ) -> Result<(), Error> {
+        let mut leading_text = leading_whitespace(last, self.line_ending);
+
+        let (base, template) = match comma {
+            Some(comma) => (
+                comma.clone(),
+                format!("{template_start}{leading_text}{element_text},{template_end}"),
+            ),
+            None => {
+                // NOTE: The comma is placed before the tailing comment of the last element.
+                let tailing_trivia = tailing_trivia(last);
+                let tailing_trivia_text = tailing_trivia
+                    .iter()
+                    .map(|token| token.text().to_string())
+                    .collect::<String>();
+                if tailing_trivia_text.contains('#') && !leading_text.contains('\n') {
+                    leading_text = self.line_ending.to_string();
+                }
+                for token in tailing_trivia {
+                    token.detach();
+                }
+                (
+                    last.clone(),
+                    format!(
+                        "{template_start}{tailing_trivia_text}{leading_text}{element_text}{template_end}"
+                    ),
+                )
+            }
+        };
+
+        let template = self.make_value(&template)?;
+        let new = template
+            .children_with_tokens()
+            .skip_while(|element| element.kind() != SyntaxKind::COMMA)
+            .skip(usize::from(comma.is_some()))
+            .take_while(|element| {
+                !matches!(
+                    element.kind(),
+                    SyntaxKind::BRACKET_END | SyntaxKind::BRACE_END
+                )
+            })
+            .collect_vec();
+        let new_nodes = new
+            .iter()
+            .filter_map(|element| element.as_node().cloned())
+            .collect_vec();
+
+        tombi_ast_editor::apply_changes(
+            &self.root,
+            vec![Change::Append {
+                base: SyntaxElement::Node(base),
+                new,
+            }],
+        );
+        for node in new_nodes {
+            self.touch(&node);
+        }
+
+        Ok(())
+    }
+
+    /// Insert the table section after the base item, or at the end of the document.
+    fn insert_section(
+        &mut self,
+        section_text: &str,
+        base: Option<SyntaxNode>,
+    ) -> Result<(), Error> {
+        let base = base
+            .map(SyntaxElement::Node)
+            .or_else(|| self.root.syntax().last_child_or_token());
+
+        let Some(base) = base else {
+            let section = self.make_nodes(&format!("{section_text}{}", self.line_ending));
+            let key_values = section_key_values(&section);
+            tombi_ast_editor::apply_changes(&self.root, vec![Change::AppendTop { new: section }]);
+            for key_value in key_values {
+                self.touch(&key_value);
+            }
+            return Ok(());
+        };
+
+        let tailing_line_breaks = match &base {
+            SyntaxElement::Node(node) => count_tailing_line_breaks(node),
+            SyntaxElement::Token(token) => usize::from(token.kind() == SyntaxKind::LINE_BREAK),
+        };
+        let is_last = base.next_sibling_or_token().is_none();
+
+        let text = if is_last {
+            format!(
+                "{}{section_text}{}",
+                self.line_ending
+                    .repeat(2_usize.saturating_sub(tailing_line_breaks)),
+                self.line_ending
+            )
+        } else {
+            format!(
+                "{section_text}{}",
+                self.line_ending.repeat(tailing_line_breaks.max(1))
+            )
+        };
+
+        let section = self.make_nodes(&text);
+        let key_values = section_key_values(&section);
+        tombi_ast_editor::apply_changes(&self.root, vec![Change::Append { base, new: section }]);
+        for key_value in key_values {
+            self.touch(&key_value);
+        }
+
+        Ok(())
+    }
+
+    /// Remove the redundant line breaks at the end of the document.
+    fn trim_end(&mut self) {
+        let mut line_breaks = vec![];
+        let mut token = self.root.syntax().last_token();
+        while let Some(current) = token {
+            match current.kind() {
+                SyntaxKind::LINE_BREAK => line_breaks.push(current.clone()),
+                SyntaxKind::WHITESPACE => {}
+                _ => break,
+            }
+            token = current.prev_token();
+        }
+
+        for line_break in line_breaks.into_iter().rev().skip(1) {
+            line_break.detach();
+        }
+    }
+
+    /// Record the key-value of the root or the table containing the node for [DocumentMut::format_touched].
+    fn touch(&mut self, node: &SyntaxNode) {
+        if let Some(key_value) = std::iter::once(node.clone())
+            .chain(node.ancestors())
+            .filter(is_item_key_value)
+            .last()
+        {
+            if !self.touched.contains(&key_value) {
+                self.touched.push(key_value);
+            }
+        }
+    }
+
+    fn make_nodes(&self, text: &str) -> Vec<SyntaxElement> {
+        tombi_parser::parse(text, self.toml_version)
+            .into_syntax_node_mut()
+            .children_with_tokens()
+            .collect_vec()
+    }
+
+    fn make_key_value(&self, text: &str) -> Result<SyntaxNode, Error> {
+        tombi_parser::parse(text, self.toml_version)
+            .into_syntax_node_mut()
+            .children()
+            .find(|node| node.kind() == SyntaxKind::KEY_VALUE)
+            .ok_or_else(|| Error::InvalidSyntax(text.to_string()))
+    }
+
+    fn make_value(&self, text: &str) -> Result<SyntaxNode, Error> {
+        tombi_ast::KeyValue::cast(self.make_key_value(&format!("_ = {text}"))?)
+            .and_then(|key_value| key_value.value())
+            .map(|value| value.syntax().clone())
+            .ok_or_else(|| Error::InvalidSyntax(text.to_string()))
+    }
+
+    fn make_key(&self, key: &str) -> Result<SyntaxNode, Error> {
+        let text = key_text(key);
+        tombi_ast::KeyValue::cast(self.make_key_value(&format!("{text} = 0"))?)
+            .and_then(|key_value| key_value.keys())
+            .and_then(|keys| keys.keys().next())
+            .map(|key| key.syntax().clone())
+            .ok_or(Error::InvalidSyntax(text))
+    }
+
+    fn make_line_break(&self) -> Result<SyntaxElement, Error> {
+        tombi_parser::parse(self.line_ending, self.toml_version)
+            .into_syntax_node_mut()
+            .first_child_or_token()
+            .ok_or_else(|| Error::InvalidSyntax(self.line_ending.to_string()))
+    }
+}
+
+impl std::str::FromStr for DocumentMut {
+    type Err = Error;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+impl std::fmt::Display for DocumentMut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.root.syntax())
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    accessors: Vec<Accessor>,
+    /// The keys written in the source, aligned with the accessors.
+    keys: Vec<Option<tombi_ast::Key>>,
+    node: EntryNode,
+}
+
+#[derive(Debug)]
+enum EntryNode {
+    KeyValue(tombi_ast::KeyValue),
+    ArrayValue(tombi_ast::Value),
+    Table(tombi_ast::Table),
+    ArrayOfTable(tombi_ast::ArrayOfTable),
+}
+
+impl EntryNode {
+    fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::KeyValue(key_value) => key_value.syntax(),
+            Self::ArrayValue(value) => value.syntax(),
+            Self::Table(table) => table.syntax(),
+            Self::ArrayOfTable(array_of_table) => array_of_table.syntax(),
+        }
+    }
+
+    fn is_item(&self) -> bool {
+        matches!(self, Self::Table(_) | Self::ArrayOfTable(_))
+    }
+}
+
+fn collect_key_values(
+    parent_accessors: &[Accessor],
+    key_values: impl Iterator<Item = tombi_ast::KeyValue>,
+    toml_version: TomlVersion,
+    entries: &mut Vec<Entry>,
+) {
+    for key_value in key_values {
+        let Some(keys) = key_value.keys().and_then(|keys| {
+            keys.keys()
+                .map(|key| {
+                    key.try_to_raw_text(toml_version)
+                        .ok()
+                        .map(|key_text| (key, key_text))
+                })
+                .collect::<Option<Vec<_>>>()
+        }) else {
+            continue;
+        };
+
+        let mut accessors = parent_accessors.to_vec();
+        let mut entry_keys = vec![None; parent_accessors.len()];
+        for (key, key_text) in keys {
+            accessors.push(Accessor::Key(key_text));
+            entry_keys.push(Some(key));
+        }
+
+        if let Some(value) = key_value.value() {
+            collect_value(&accessors, &value, toml_version, entries);
+        }
+
+        entries.push(Entry {
+            accessors,
+            keys: entry_keys,
+            node: EntryNode::KeyValue(key_value),
+        });
+    }
+}
+
+fn collect_value(
+    accessors: &[Accessor],
+    value: &tombi_ast::Value,
+    toml_version: TomlVersion,
+    entries: &mut Vec<Entry>,
+) {
+    match value {
+        tombi_ast::Value::InlineTable(inline_table) => {
+            collect_key_values(accessors, inline_table.key_values(), toml_version, entries);
+        }
+        tombi_ast::Value::Array(array) => {
+            for (index, value) in array.values().enumerate() {
+                let value_accessors = accessors
+                    .iter()
+                    .cloned()
+                    .chain(std::iter::once(Accessor::Index(index)))
+                    .collect_vec();
+
+                collect_value(&value_accessors, &value, toml_version, entries);
+
+                entries.push(Entry {
+                    keys: vec![None; value_accessors.len()],
+                    accessors: value_accessors,
+                    node: EntryNode::ArrayValue(value),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+fn find_entry<'a>(entries: &'a [Entry], accessors: &[Accessor]) -> Option<&'a Entry> {
+    entries.iter().find(|entry| entry.accessors == accessors)
+}
+
+/// Returns the value of the key path, converting only the syntax nodes under it.
+///
+/// The tables defined by the headers or the dotted keys are built from the entries under them.
+fn entries_value(
+    entries: &[Entry],
+    accessors: &[Accessor],
+    toml_version: TomlVersion,
+) -> Option<tombi_document::Value> {
+    let entry = find_entry(entries, accessors);
+    let value = match entry.map(|entry| &entry.node) {
+        Some(EntryNode::KeyValue(key_value)) => key_value.value(),
+        Some(EntryNode::ArrayValue(value)) => Some(value.clone()),
+        _ => None,
+    };
+    if let Some(value) = value {
+        let (value, _) = value.into_document_tree_and_errors(toml_version).into();
+        return Some(value.into_document(toml_version));
+    }
+
+    let descendants = entries
+        .iter()
+        .filter(|entry| {
+            entry.accessors.len() > accessors.len() && entry.accessors.starts_with(accessors)
+        })
+        .collect_vec();
+    if entry.is_none() && descendants.is_empty() {
+        return None;
+    }
+
+    let children = descendants
+        .iter()
+        .map(|entry| &entry.accessors[accessors.len()])
+        .unique()
+        .collect_vec();
+    if let Some(Accessor::Index(_)) = children.first() {
+        let mut array = tombi_document::Array::new(tombi_document::ArrayKind::ArrayOfTable);
+        for index in children.into_iter().sorted_by_key(|child| child.as_index()) {
+            let child_accessors = accessors.iter().chain([index]).cloned().collect_vec();
+            array.push(entries_value(entries, &child_accessors, toml_version)?);
+        }
+        return Some(tombi_document::Value::Array(array));
+    }
+
+    // NOTE: The table holding the key-values of the dotted keys, such as `b` of `a.b.c = 1`,
+    //       is a key-value table as in the document.
+    let is_key_value_table = descendants.iter().any(|entry| {
+        entry.accessors.len() == accessors.len() + 1
+            && matches!(entry.node, EntryNode::KeyValue(_))
+            && entry.keys[..accessors.len()]
+                .last()
+                .is_some_and(Option::is_some)
+    });
+    let mut table = tombi_document::Table::new(if is_key_value_table {
+        tombi_document::TableKind::KeyValue
+    } else {
+        tombi_document::TableKind::Table
+    });
+    for key in children.into_iter().filter_map(Accessor::as_key) {
+        let child_accessors = accessors
+            .iter()
+            .cloned()
+            .chain([Accessor::Key(key.to_string())])
+            .collect_vec();
+        // NOTE: Keep the kind of the key written in the source, such as `"docs.rs"`.
+        let document_key = descendants
+            .iter()
+            .find_map(|entry| {
+                entry
+                    .accessors
+                    .starts_with(&child_accessors)
+                    .then(|| entry.keys[accessors.len()].clone())
+                    .flatten()
+            })
+            .and_then(|key| {
+                let (key, _) = key.into_document_tree_and_errors(toml_version).into();
+                key
+            })
+            .map(|key| key.into_document(toml_version))
+            .unwrap_or_else(|| document_key(key));
+
+        table.insert(
+            document_key,
+            entries_value(entries, &child_accessors, toml_version)?,
+        );
+    }
+
+    Some(tombi_document::Value::Table(table))
+}
+
+/// Returns the last table or array of tables sharing the longest key path prefix,
+/// so that the new section is placed after the related tables and their subtables.
+fn last_related_item(
+    entries: &[Entry],
+    accessors: &[Accessor],
+    min_prefix_len: usize,
+) -> Option<SyntaxNode> {
+    let keys = accessors.iter().filter_map(Accessor::as_key).collect_vec();
+
+    entries
+        .iter()
+        .filter(|entry| entry.node.is_item())
+        .filter_map(|entry| {
+            let prefix_len = entry
+                .accessors
+                .iter()
+                .filter_map(Accessor::as_key)
+                .zip(keys.iter())
+                .take_while(|(a, b)| a == *b)
+                .count();
+            (prefix_len >= min_prefix_len).then_some((prefix_len, entry.node.syntax()))
+        })
+        .max_set_by_key(|(prefix_len, _)| *prefix_len)
+        .pop()
+        .map(|(_, node)| node.clone())
+}
+
+fn section_key_values(section: &[SyntaxElement]) -> Vec<SyntaxNode> {
+    section
+        .iter()
+        .filter_map(|element| element.as_node())
+        .flat_map(|node| node.children())
+        .filter(|node| node.kind() == SyntaxKind::KEY_VALUE)
+        .collect_vec()
+}
+
+/// Returns the line break and the whitespace of the key-value in the root or the table
+/// to remove, keeping the smaller number of the blank lines around it.
+fn remove_line(node: &SyntaxNode) -> Vec<Change> {
+    let mut changes = vec![Change::Remove {
+        target: SyntaxElement::Node(node.clone()),
+    }];
+
+    let prev_trivia = std::iter::successors(node.prev_sibling_or_token(), |element| {
+        element.prev_sibling_or_token()
+    })
+    .take_while(|element| is_whitespace(element.kind()))
+    .collect_vec();
+    let next_trivia = std::iter::successors(node.next_sibling_or_token(), |element| {
+        element.next_sibling_or_token()
+    })
+    .take_while(|element| is_whitespace(element.kind()))
+    .collect_vec();
+
+    let is_first = node.prev_sibling_or_token().is_none()
+        || prev_trivia.len()
+            == std::iter::successors(node.prev_sibling_or_token(), |element| {
+                element.prev_sibling_or_token()
+            })
+            .count()
+            && node.parent().map(|parent| parent.kind()) == Some(SyntaxKind::ROOT);
+
+    let count_line_breaks = |trivia: &[SyntaxElement]| {
+        trivia
+            .iter()
+            .filter(|element| element.kind() == SyntaxKind::LINE_BREAK)
+            .count()
+    };
+    let removed_trivia =
+        if is_first || count_line_breaks(&next_trivia) <= count_line_breaks(&prev_trivia) {
+            next_trivia
+        } else {
+            prev_trivia
+        };
+
+    changes.extend(
+        removed_trivia
+            .into_iter()
+            .map(|target| Change::Remove { target }),
+    );
+    changes
+}
+
+/// Returns the changes to remove the element of the array or the inline table with its comma.
+fn remove_element(node: &SyntaxNode) -> Vec<Change> {
+    let next_comma = std::iter::successors(node.next_sibling_or_token(), |element| {
+        element.next_sibling_or_token()
+    })
+    .find(|element| !is_whitespace(element.kind()))
+    .filter(|element| element.kind() == SyntaxKind::COMMA);
+
+    if let Some(next_comma) = next_comma {
+        let prev_whitespaces = std::iter::successors(node.prev_sibling_or_token(), |element| {
+            element.prev_sibling_or_token()
+        })
+        .take_while(|element| element.kind() == SyntaxKind::WHITESPACE)
+        .collect_vec();
+        let next_whitespaces = if prev_whitespaces.is_empty() {
+            std::iter::successors(next_comma.next_sibling_or_token(), |element| {
+                element.next_sibling_or_token()
+            })
+            .take_while(|element| element.kind() == SyntaxKind::WHITESPACE)
+            .collect_vec()
+        } else {
+            vec![]
+        };
+
+        return std::iter::successors(Some(SyntaxElement::Node(node.clone())), |element| {
+            element.next_sibling_or_token()
+        })
+        .take_while_inclusive(|element| element != &next_comma)
+        .chain(prev_whitespaces)
+        .chain(next_whitespaces)
+        .map(|target| Change::Remove { target })
+        .collect_vec();
+    }
+
+    let prev_comma = std::iter::successors(node.prev_sibling_or_token(), |element| {
+        element.prev_sibling_or_token()
+    })
+    .find(|element| !is_whitespace(element.kind()))
+    .filter(|element| element.kind() == SyntaxKind::COMMA);
+
+    match prev_comma {
+        // NOTE: The tailing comment of the previous comma is kept for the previous element.
+        Some(SyntaxElement::Node(prev_comma)) => vec![Change::ReplaceRange {
+            old: SyntaxElement::Node(prev_comma.clone())..=SyntaxElement::Node(node.clone()),
+            new: prev_comma
+                .children_with_tokens()
+                .filter(|element| element.kind() != SyntaxKind::COMMA)
+                .collect_vec(),
+        }],
+        _ => std::iter::successors(node.prev_sibling_or_token(), |element| {
+            element.prev_sibling_or_token()
+        })
+        .take_while(|element| is_whitespace(element.kind()))
+        .chain(std::iter::once(SyntaxElement::Node(node.clone())))
+        .map(|target| Change::Remove { target })
+        .collect_vec(),
+    }
+}
+
+/// Returns the end of the table header, including its tailing comment.
+fn header_end(node: &SyntaxNode) -> Option<SyntaxElement> {
+    node.children_with_tokens()
+        .skip_while(|element| {
+            !matches!(
+                element.kind(),
+                SyntaxKind::BRACKET_END | SyntaxKind::DOUBLE_BRACKET_END
+            )
+        })
+        .take_while(|element| {
+            matches!(
+                element.kind(),
+                SyntaxKind::BRACKET_END
+                    | SyntaxKind::DOUBLE_BRACKET_END
+                    | SyntaxKind::WHITESPACE
+                    | SyntaxKind::COMMENT
+            )
+        })
+        .last()
+}
+
+/// Returns the whitespace to put before the new element, such as the indentation of the multi-line array.
+fn leading_whitespace(node: &SyntaxNode, line_ending: &str) -> String {
+    let mut text = std::iter::successors(node.prev_sibling_or_token(), |element| {
+        element.prev_sibling_or_token()
+    })
+    .map_while(|element| match element {
+        SyntaxElement::Token(token) if is_whitespace(token.kind()) => {
+            Some(token.text().to_string())
+        }
+        _ => None,
+    })
+    .collect_vec();
+    text.reverse();
+    text.extend(
+        node.children_with_tokens()
+            .map_while(|element| match element {
+                SyntaxElement::Token(token) if is_trivia(token.kind()) => {
+                    Some(token.text().to_string())
+                }
+                _ => None,
+            }),
+    );
+
+    let text = text.concat();
+    match text.rfind('\n') {
+        Some(index) => format!("{line_ending}{}", &text[index + 1..]),
+        None => " ".to_string(),
+    }
+}
+
+/// Returns the tokens of the tailing comment and the whitespace before it.
+fn tailing_trivia(node: &SyntaxNode) -> Vec<tombi_syntax::SyntaxToken> {
+    let mut tokens = std::iter::successors(node.last_token(), |token| token.prev_token())
+        .take_while(|token| matches!(token.kind(), SyntaxKind::WHITESPACE | SyntaxKind::COMMENT))
+        .collect_vec();
+    if !tokens
+        .iter()
+        .any(|token| token.kind() == SyntaxKind::COMMENT)
+    {
+        return vec![];
+    }
+    tokens.reverse();
+    tokens
+}
+
+fn count_tailing_line_breaks(node: &SyntaxNode) -> usize {
+    std::iter::successors(node.last_token(), |token| token.prev_token())
+        .take_while(|token| is_whitespace(token.kind()))
+        .filter(|token| token.kind() == SyntaxKind::LINE_BREAK)
+        .count()
+}
+
+fn is_item_key_value(node: &SyntaxNode) -> bool {
+    node.kind() == SyntaxKind::KEY_VALUE
+        && node.parent().is_some_and(|parent| {
+            matches!(
+                parent.kind(),
+                SyntaxKind::ROOT | SyntaxKind::TABLE | SyntaxKind::ARRAY_OF_TABLE
+            )
+        })
+}
+
+#[inline]
+fn is_whitespace(kind: SyntaxKind) -> bool {
+    matches!(kind, SyntaxKind::WHITESPACE | SyntaxKind::LINE_BREAK)
+}
+
+#[inline]
+fn is_trivia(kind: SyntaxKind) -> bool {
+    matches!(
+        kind,
+        SyntaxKind::WHITESPACE | SyntaxKind::LINE_BREAK | SyntaxKind::COMMENT
+    )
+}
+
+fn serialize_value<T>(value: &T, accessors: &[Accessor]) -> Result<tombi_document::Value, Error>
+where
+    T: Serialize,
+{
+    crate::ser::to_value(value)?
+        .ok_or_else(|| crate::ser::Error::ValueRequired(Accessors::new(accessors.to_vec())).into())
+}
+
+/// Parse the key path, such as `package.version`, `bin[0].name` or `package.metadata."docs.rs"`.
+fn parse_key_path(key_path: &str) -> Result<Vec<Accessor>, Error> {
+    let error = || Error::InvalidKeyPath(key_path.to_string());
+
+    let mut accessors = Vec::new();
+    let mut rest = key_path;
+    loop {
+        let (key, next) = match rest.chars().next() {
+            Some('"') => {
+                let end = basic_string_end(rest).ok_or_else(error)?;
+                (
+                    tombi_toml_text::try_from_basic_string(&rest[..end], TomlVersion::latest())
+                        .map_err(|_| error())?,
+                    &rest[end..],
+                )
+            }
+            Some('\'') => {
+                let end = rest[1..].find('\'').ok_or_else(error)? + 2;
+                (
+                    tombi_toml_text::try_from_literal_string(&rest[..end]).map_err(|_| error())?,
+                    &rest[end..],
+                )
+            }
+            _ => {
+                let end = rest.find(['.', '[']).unwrap_or(rest.len());
+                if !is_bare_key(&rest[..end]) {
+                    return Err(error());
+                }
+                (rest[..end].to_string(), &rest[end..])
+            }
+        };
+        accessors.push(Accessor::Key(key));
+        rest = next;
+
+        while let Some(index_rest) = rest.strip_prefix('[') {
+            let end = index_rest.find(']').ok_or_else(error)?;
+            accessors.push(Accessor::Index(
+                index_rest[..end].parse().map_err(|_| error())?,
+            ));
+            rest = &index_rest[end + 1..];
+        }
+
+        match rest.strip_prefix('.') {
+            Some(next) => rest = next,
+            None if rest.is_empty() => return Ok(accessors),
+            None => return Err(error()),
+        }
+    }
+}
+
+fn basic_string_end(text: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn key_text(key: &str) -> String {
+    if is_bare_key(key) {
+        key.to_string()
+    } else {
+        tombi_toml_text::to_basic_string(key)
+    }
+}
+
+/// Returns the dotted keys of the accessors, or `None` if the accessors contain an index.
+fn keys_text(accessors: &[Accessor]) -> Option<String> {
+    accessors
+        .iter()
+        .map(|accessor| accessor.as_key().map(key_text))
+        .collect::<Option<Vec<_>>>()
+        .map(|keys| keys.join("."))
+}
+
+fn document_key(key: &str) -> tombi_document::Key {
+    tombi_document::Key::new(tombi_document::KeyKind::BareKey, key.to_string())
+}
+
+/// Returns the TOML text of the value, writing the tables as inline tables.
+fn to_inline_text(value: &tombi_document::Value) -> String {
+    match value {
+        tombi_document::Value::Table(table) if table.key_values().is_empty() => "{}".to_string(),
+        tombi_document::Value::Table(table) => format!(
+            "{{ {} }}",
+            table
+                .key_values()
+                .iter()
+                .map(|(key, value)| format!(
+                    "{} = {}",
+                    key_text(key.value()),
+                    to_inline_text(value)
+                ))
+                .join(", ")
+        ),
+        tombi_document::Value::Array(array) => {
+            format!("[{}]", array.values().iter().map(to_inline_text).join(", "))
+        }
+        value => {
+            let mut text = String::new();
+            value.to_toml_string(&mut text, &[]);
+            text
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(source: &str) -> DocumentMut {
+        DocumentMut::parse(source).expect("TOML parsing failed")
+    }
+
+    #[test]
+    fn test_get() {
+        let document = document(
+            r#"
+[package]
+name = "tombi"
+metadata."docs.rs".all-features = true
+
+[[bin]]
+name = "tombi"
+"#,
+        );
+
+        pretty_assertions::assert_eq!(
+            document.get("package.name").unwrap(),
+            Some(tombi_document::Value::String(tombi_document::String::new(
+                tombi_document::StringKind::BasicString,
+                "tombi".to_string()
+            )))
+        );
+        pretty_assertions::assert_eq!(
+            document
+                .get(r#"package.metadata."docs.rs".all-features"#)
+                .unwrap(),
+            Some(tombi_document::Value::Boolean(
+                tombi_document::Boolean::new(true)
+            ))
+        );
+        assert!(document.get("bin[0].name").unwrap().is_some());
+        assert!(document.get("bin[1].name").unwrap().is_none());
+        assert!(document.get("package..name").is_err());
+    }
+
+    #[test]
+    fn test_get_tables() {
+        let document = document(
+            r#"
+[package]
+name = "tombi"
+metadata."docs.rs".all-features = true
+
+[dependencies.serde]
+version = "1.0"
+features = ["derive"]
+
+[[bin]]
+name = "tombi"
+
+[[bin]]
+name = "tombi-lsp"
+"#,
+        );
+        let expected = document.to_document();
+
+        for key in ["package", "dependencies", "bin"] {
+            // NOTE: Compare the debug output, since the keys are compared without their kinds.
+            pretty_assertions::assert_eq!(
+                format!("{:?}", document.get(key).unwrap().as_ref()),
+                format!("{:?}", expected.key_values().get(&document_key(key)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_insert_replaces_value_with_comments() {
+        let mut document = document(
+            r#"# header comment
+[package]
+# leading comment
+version = "0.1.0"  # tailing comment
+edition = "2021"
+"#,
+        );
+        document.insert("package.version", &"0.2.0").unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"# header comment
+[package]
+# leading comment
+version = "0.2.0"  # tailing comment
+edition = "2021"
+"#
+        );
+    }
+
+    #[test]
+    fn test_insert_into_table() {
+        let mut document = document(
+            r#"[package]
+name = "tombi"  # comment
+
+[dependencies]
+serde = "1.0"
+"#,
+        );
+        document.insert("package.version", &"0.1.0").unwrap();
+        document
+            .insert(
+                "dependencies.tokio",
+                &maplit::btreemap! { "version" => "1" },
+            )
+            .unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"[package]
+name = "tombi"  # comment
+version = "0.1.0"
+
+[dependencies]
+serde = "1.0"
+tokio = { version = "1" }
+"#
+        );
+    }
+
+    #[test]
+    fn test_insert_into_root() {
+        let mut document = document(
+            r#"# comment
+
+[package]
+name = "tombi"
+"#,
+        );
+        document.insert("key", &1).unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"# comment
+
+key = 1
+
+[package]
+name = "tombi"
+"#
+        );
+    }
+
+    #[test]
+    fn test_insert_into_inline_table() {
+        let mut document = document(
+            r#"serde = { version = "1.0" }
+"#,
+        );
+        document.insert("serde.features", &["derive"]).unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"serde = { version = "1.0", features = ["derive"] }
+"#
+        );
+    }
+
+    #[test]
+    fn test_insert_into_scalar_fails() {
+        let mut document = document(r#"name = "tombi""#);
+
+        assert!(matches!(
+            document.insert("name.first", &"tombi"),
+            Err(Error::TableRequired(_))
+        ));
+    }
+
+    #[test]
+    fn test_insert_into_implicit_table_fails() {
+        let mut document = document(
+            r#"[dependencies.serde]
+version = "1.0"
+"#,
+        );
+
+        assert!(matches!(
+            document.insert("dependencies", &maplit::btreemap! { "tokio" => "1" }),
+            Err(Error::AlreadyExists(_))
+        ));
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"[dependencies.serde]
+version = "1.0"
+"#
+        );
+    }
+
+    #[test]
+    fn test_insert_into_dotted_keys_table_fails() {
+        let mut document = document(r#"package.metadata.key = 1"#);
+
+        assert!(matches!(
+            document.insert("package.metadata", &maplit::btreemap! { "key" => 2 }),
+            Err(Error::AlreadyExists(_))
+        ));
+    }
+
+    #[test]
+    fn test_remove_key_value() {
+        let mut document = document(
+            r#"[package]
+name = "tombi"
+# comment of version
+version = "0.1.0"
+edition = "2021"
+"#,
+        );
+        let removed = document.remove("package.version").unwrap();
+
+        assert!(removed.is_some());
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"[package]
+name = "tombi"
+edition = "2021"
+"#
+        );
+        assert!(document.remove("package.version").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_remove_array_value() {
+        let mut document = document(
+            r#"a = [1, 2, 3]
+b = [
+  1,  # one
+  2,  # two
+]
+"#,
+        );
+        document.remove("a[2]").unwrap();
+        document.remove("b[0]").unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"a = [1, 2]
+b = [
+  2,  # two
+]
+"#
+        );
+    }
+
+    #[test]
+    fn test_remove_table() {
+        let mut document = document(
+            r#"[package]
+name = "tombi"
+
+[dependencies]
+serde = "1.0"
+
+[dependencies.tokio]
+version = "1"
+"#,
+        );
+        document.remove("dependencies").unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"[package]
+name = "tombi"
+"#
+        );
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut document = document(
+            r#"[dependencies]
+serde = "1.0"  # comment
+
+[dependencies.tokio]
+version = "1"
+
+[dependencies.tokio.features]
+macros = true
+"#,
+        );
+        document.rename("dependencies.tokio", "async-std").unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"[dependencies]
+serde = "1.0"  # comment
+
+[dependencies.async-std]
+version = "1"
+
+[dependencies.async-std.features]
+macros = true
+"#
+        );
+        assert!(matches!(
+            document.rename("dependencies.serde", "async-std"),
+            Err(Error::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            document.rename("dependencies.tokio", "smol"),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_append_array_value() {
+        let mut document = document(
+            r#"a = [1, 2]
+b = [
+  "x",  # comment
+]
+c = []
+"#,
+        );
+        document.append("a", &3).unwrap();
+        document.append("b", &"y").unwrap();
+        document.append("c", &true).unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"a = [1, 2, 3]
+b = [
+  "x",  # comment
+  "y",
+]
+c = [true]
+"#
+        );
+    }
+
+    #[test]
+    fn test_append_array_of_tables() {
+        let mut document = document(
+            r#"[[bin]]
+name = "a"
+
+[package]
+name = "tombi"
+"#,
+        );
+        document
+            .append("bin", &maplit::btreemap! { "name" => "b" })
+            .unwrap();
+        document
+            .append("example", &maplit::btreemap! { "name" => "c" })
+            .unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"[[bin]]
+name = "a"
+
+[[bin]]
+name = "b"
+
+[package]
+name = "tombi"
+
+[[example]]
+name = "c"
+"#
+        );
+        assert!(document.get("bin[1].name").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_insert_table() {
+        let mut document = document(
+            r#"[package]
+name = "tombi"
+
+[dependencies.serde]
+version = "1.0"
+
+[dev-dependencies]
+"#,
+        );
+        document.insert_table("dependencies.tokio").unwrap();
+        document.insert_table("workspace").unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"[package]
+name = "tombi"
+
+[dependencies.serde]
+version = "1.0"
+
+[dependencies.tokio]
+
+[dev-dependencies]
+
+[workspace]
+"#
+        );
+        assert!(matches!(
+            document.insert_table("package"),
+            Err(Error::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            document.insert_table("package.name.first"),
+            Err(Error::TableRequired(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_touched() {
+        let mut document = document(
+            r#"untouched   =   1
+key = {a=1}
+"#,
+        );
+        document.insert("key.b", &2).unwrap();
+        document.format_touched(&FormatOptions::default()).unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            r#"untouched   =   1
+key = { a = 1, b = 2 }
+"#
+        );
+    }
+
+    #[test]
+    fn test_crlf() {
+        let mut document = document("[package]\r\nname = \"tombi\"\r\n");
+        document.insert("package.version", &"0.1.0").unwrap();
+
+        pretty_assertions::assert_eq!(
+            document.to_string(),
+            "[package]\r\nname = \"tombi\"\r\nversion = \"0.1.0\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", "))]
+    Parser(Vec<tombi_parser::Error>),
+
+    #[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", "))]
+    DocumentTree(Vec<tombi_document_tree::Error>),
+
+    #[error(transparent)]
+    Serialize(#[from] crate::ser::Error),
+
+    #[error("Formatting failed")]
+    Format(#[from] std::fmt::Error),
+
+    #[error("Invalid key path: {0}")]
+    InvalidKeyPath(String),
+
+    #[error("Value is not found for {0}")]
+    NotFound(tombi_schema_store::Accessors),
+
+    #[error("Value already exists for {0}")]
+    AlreadyExists(tombi_schema_store::Accessors),
+
+    #[error("Table is required for {0}")]
+    TableRequired(tombi_schema_store::Accessors),
+
+    #[error("Array is required for {0}")]
+    ArrayRequired(tombi_schema_store::Accessors),
+
+    #[error("Key-value is required for {0}, but it is defined by a table header")]
+    KeyValueRequired(tombi_schema_store::Accessors),
+
+    #[error("Table header cannot contain the array index for {0}")]
+    IndexInTableHeader(tombi_schema_store::Accessors),
+
+    #[error("Failed to build the syntax tree of {0:?}")]
+    InvalidSyntax(String),
+}
@@ -8,7 +8,6 @@
 //! use std::collections::HashMap;
 //!
 //! use serde::Serialize;
-//! use tokio;
 //!
 //! #[derive(Serialize)]
 //! struct Package {
@@ -23,27 +22,27 @@
 //!     dependencies: HashMap<String, String>,
 //! }
 //!
-//! #[tokio::main]
-//! async fn main() {
-//!     let cargo_toml = CargoToml {
-//!         package: Package {
-//!             name: "serde_tombi".to_string(),
-//!             version: "0.1.0".to_string(),
-//!             authors: vec!["The Tombi Team".to_string()],
-//!         },
-//!         dependencies: [
-//!             ("serde".to_string(), "1.0".to_string()),
-//!             ("thiserror".to_string(), "1.0".to_string()),
-//!         ].into_iter().collect(),
-//!     };
-//!
-//!     // Simple serialization
-//!     let toml = serde_tombi::to_string_async(&cargo_toml).await.unwrap();
-//! }
+//! let cargo_toml = CargoToml {
+//!     package: Package {
+//!         name: "serde_tombi".to_string(),
+//!         version: "0.1.0".to_string(),
+//!         authors: vec!["The Tombi Team".to_string()],
+//!     },
+//!     dependencies: [
+//!         ("serde".to_string(), "1.0".to_string()),
+//!         ("thiserror".to_string(), "1.0".to_string()),
+//!     ].into_iter().collect(),
+//! };
+//!
+//! // Simple serialization without loading the config
+//! let toml = serde_tombi::to_string(&cargo_toml).unwrap();
 //! ```
 //!
 //! ## Using TypedBuilder pattern
 //!
+//! `to_string_async` loads the config from the current directory
+//! and formats the TOML with the schema associated with the source path.
+//!
 //! ```rust
 //! use std::collections::HashMap;
 //!
@@ -91,16 +90,25 @@
 pub mod config;
 mod de;
 mod document;
+pub mod edit;
 mod ser;
+mod spanned;
+mod style;
 
-pub use de::{from_document, from_str_async, Deserializer};
+pub use de::{
+    from_document, from_document_tree, from_reader, from_slice, from_str, from_str_async,
+    Deserializer,
+};
 pub use document::{
     Array, ArrayKind, Boolean, Document, Float, Integer, IntegerKind, Key, LocalDate,
     LocalDateTime, LocalTime, OffsetDateTime, String, StringKind, Table, TableKind, Value,
 };
+pub use edit::DocumentMut;
 
-pub use ser::{to_document, to_string_async, Serializer};
+pub use ser::{to_document, to_string, to_string_async, to_writer, Serializer};
+pub use spanned::Spanned;
 use std::fmt;
+pub use style::{dotted, inline, literal, multiline};
 use thiserror::Error;
 
 /// Error that can occur when processing TOML.
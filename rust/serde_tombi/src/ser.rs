@@ -9,6 +9,7 @@ use tombi_toml_version::TomlVersion;
 use typed_builder::TypedBuilder;
 
 use crate::document::ToTomlString;
+use crate::style::Style;
 pub use error::Error;
 
 /// Serialize the given data structure as a TOML string.
@@ -44,6 +45,50 @@ where
     Serializer::new().to_string_async(value).await
 }
 
+/// Serialize the given data structure as a TOML string.
+///
+/// Unlike [to_string_async], this function uses [Serializer::new_offline],
+/// so it neither loads the config nor fetches the remote schemas.
+/// It runs its own async runtime, so it must not be called within another one.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     ip: String,
+///     port: u16,
+///     keys: Vec<String>,
+/// }
+///
+/// let config = Config {
+///     ip: "127.0.0.1".to_string(),
+///     port: 8080,
+///     keys: vec!["key1".to_string(), "key2".to_string()],
+/// };
+///
+/// let toml = serde_tombi::to_string(&config).unwrap();
+/// ```
+pub fn to_string<T>(value: &T) -> Result<String, crate::ser::Error>
+where
+    T: Serialize,
+{
+    Serializer::new_offline().to_string(value)
+}
+
+/// Serialize the given data structure as a TOML into the writer.
+///
+/// See [to_string] for details.
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), crate::ser::Error>
+where
+    W: std::io::Write,
+    T: Serialize,
+{
+    Serializer::new_offline().to_writer(writer, value)
+}
+
 /// Serialize the given data structure as a TOML Document.
 pub fn to_document<T>(value: &T) -> Result<crate::Document, crate::ser::Error>
 where
@@ -52,6 +97,17 @@ where
     Serializer::new().to_document(value)
 }
 
+/// Serialize the given data structure as a TOML value.
+pub(crate) fn to_value<T>(value: &T) -> Result<Option<tombi_document::Value>, crate::ser::Error>
+where
+    T: Serialize,
+{
+    value.serialize(&mut ValueSerializer {
+        accessors: &[],
+        style: Style::default(),
+    })
+}
+
 // Actual serializer implementation
 #[derive(TypedBuilder)]
 pub struct Serializer<'a> {
@@ -66,6 +122,45 @@ pub struct Serializer<'a> {
 
     #[builder(default, setter(into, strip_option))]
     schema_store: Option<&'a tombi_schema_store::SchemaStore>,
+
+    /// The schema to order the keys and the array values of the output,
+    /// such as `x-tombi-table-keys-order`.
+    ///
+    /// Takes precedence over the schema associated with the source path.
+    /// In the [offline](Serializer::new_offline) mode, the remote schema is used only if it is cached.
+    #[builder(default, setter(into, strip_option))]
+    schema_url: Option<&'a tombi_schema_store::SchemaUrl>,
+
+    /// The TOML version to format the TOML text.
+    ///
+    /// Takes precedence over the TOML version of the config.
+    #[builder(default, setter(into, strip_option))]
+    toml_version: Option<TomlVersion>,
+
+    /// Neither load the config from the current directory nor fetch the remote schemas.
+    ///
+    /// The given config and schema store are still used.
+    /// See [Serializer::new_offline].
+    #[builder(default)]
+    offline: bool,
+
+    /// Write the nested tables as inline tables, and the arrays of tables as arrays of inline tables.
+    ///
+    /// See [crate::inline] to choose it for each field.
+    #[builder(default)]
+    inline_tables: bool,
+
+    /// Write the strings as literal strings where possible.
+    ///
+    /// See [crate::literal] to choose it for each field.
+    #[builder(default)]
+    literal_strings: bool,
+
+    /// Write the strings containing line breaks as multi-line strings.
+    ///
+    /// See [crate::multiline] to choose it for each field.
+    #[builder(default)]
+    multiline_strings: bool,
 }
 
 impl Default for Serializer<'_> {
@@ -81,6 +176,23 @@ impl Serializer<'_> {
             config_path: None,
             source_path: None,
             schema_store: None,
+            schema_url: None,
+            toml_version: None,
+            offline: false,
+            inline_tables: false,
+            literal_strings: false,
+            multiline_strings: false,
+        }
+    }
+
+    /// Create a serializer that neither loads the config nor fetches the remote schemas.
+    ///
+    /// Without the given schema store, an offline schema store is used,
+    /// so only the local and the cached schemas order the keys.
+    pub const fn new_offline() -> Self {
+        Self {
+            offline: true,
+            ..Self::new()
         }
     }
 
@@ -88,8 +200,18 @@ impl Serializer<'_> {
     where
         T: Serialize,
     {
-        match value.serialize(&mut ValueSerializer { accessors: &[] }) {
-            Ok(Some(tombi_document::Value::Table(table))) => {
+        let style = Style {
+            inline: self.inline_tables,
+            literal: self.literal_strings,
+            multiline: self.multiline_strings,
+            ..Default::default()
+        };
+        match value.serialize(&mut ValueSerializer {
+            accessors: &[],
+            style,
+        }) {
+            Ok(Some(tombi_document::Value::Table(mut table))) => {
+                *table.kind_mut() = tombi_document::TableKind::Table;
                 Ok(tombi_document::Document::from(table))
             }
             Ok(Some(value)) => Err(crate::ser::Error::RootMustBeTable(value.kind())),
@@ -105,51 +227,102 @@ impl Serializer<'_> {
         tokio::runtime::Runtime::new()?.block_on(self.to_string_async(value))
     }
 
-    pub async fn to_string_async<T>(&self, value: &T) -> Result<String, crate::ser::Error>
+    pub fn to_writer<W, T>(&self, mut writer: W, value: &T) -> Result<(), crate::ser::Error>
     where
+        W: std::io::Write,
         T: Serialize,
     {
-        let document = to_document(value)?;
-        let mut toml_text = std::string::String::new();
-        document.to_toml_string(&mut toml_text, &[]);
+        writer.write_all(self.to_string(value)?.as_bytes())?;
+        Ok(())
+    }
 
-        let format_definitions = FormatDefinitions::default();
-        let format_options = FormatOptions::default();
+    pub async fn to_string_async<T>(&self, value: &T) -> Result<String, crate::ser::Error>
+    where
+        T: Serialize,
+    {
+        let document = self.to_document(value)?;
+        let mut toml_version = self.toml_version;
+        let mut format_options = self
+            .config
+            .and_then(|config| config.format.clone())
+            .unwrap_or_default();
 
         let schema_store = match self.schema_store {
             Some(schema_store) => schema_store,
-            None => &SchemaStore::new(),
+            None => &SchemaStore::new_with_options(tombi_schema_store::Options {
+                offline: Some(self.offline),
+                ..Default::default()
+            }),
         };
         if self.schema_store.is_none() {
             match self.config {
                 Some(config) => {
-                    if self.schema_store.is_none() {
-                        schema_store.load_config(config, self.config_path).await?;
-                    }
+                    toml_version = toml_version.or(config.toml_version);
+                    schema_store.load_config(config, self.config_path).await?;
                 }
+                None if self.offline => {}
                 None => {
                     let (config, config_path) = crate::config::load_with_path()?;
+                    toml_version = toml_version.or(config.toml_version);
                     schema_store
                         .load_config(&config, config_path.as_deref())
                         .await?;
+                    format_options = config.format.unwrap_or_default();
                 }
             }
         }
 
+        self.format_document(
+            document,
+            toml_version.unwrap_or_default(),
+            &format_options,
+            schema_store,
+        )
+        .await
+    }
+
+    async fn format_document(
+        &self,
+        document: tombi_document::Document,
+        toml_version: TomlVersion,
+        format_options: &FormatOptions,
+        schema_store: &SchemaStore,
+    ) -> Result<String, crate::ser::Error> {
+        let mut toml_text = std::string::String::new();
+        document.to_toml_string(&mut toml_text, &[]);
+
+        let format_definitions = FormatDefinitions {
+            // NOTE: Keep the string kinds chosen by the style.
+            quote_style: Some(tombi_config::QuoteStyle::Preserve),
+            ..FormatDefinitions::default()
+        };
+
         let formatter = tombi_formatter::Formatter::new(
-            TomlVersion::default(),
+            toml_version,
             format_definitions,
-            &format_options,
+            format_options,
             self.source_path.map(Either::Right),
             schema_store,
         );
 
-        match formatter.format(&toml_text).await {
+        let formatted = match self.schema_url {
+            Some(schema_url) => {
+                let source_schema = schema_store
+                    .resolve_source_schema_from_schema_url(schema_url)
+                    .await?;
+                formatter
+                    .format_with_source_schema(&toml_text, source_schema.as_ref())
+                    .await
+            }
+            None => formatter.format(&toml_text).await,
+        };
+
+        match formatted {
             Ok(formatted) => Ok(formatted),
             Err(errors) => {
                 tracing::trace!("toml_text:\n{}", toml_text);
                 tracing::trace!(?errors);
-                unreachable!("Document must be valid TOML.")
+                Err(crate::ser::Error::InvalidToml(errors))
             }
         }
     }
@@ -157,6 +330,7 @@ impl Serializer<'_> {
 
 pub struct ValueSerializer<'a> {
     accessors: &'a [tombi_schema_store::Accessor],
+    style: Style,
 }
 
 impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
@@ -227,7 +401,7 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
         Ok(Some(tombi_document::Value::String(
-            tombi_document::String::new(tombi_document::StringKind::BasicString, v.to_string()),
+            tombi_document::String::new(self.style.string_kind(v), v.to_string()),
         )))
     }
 
@@ -284,7 +458,13 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
             tombi_date_time::LOCAL_TIME_NEWTYPE_NAME => value
                 .serialize(DateTimeSerializer::new(self.accessors))
                 .map(|dt| Some(tombi_document::Value::LocalTime(dt))),
-            _ => value.serialize(self),
+            _ => match self.style.with_newtype_name(name) {
+                Some(style) => value.serialize(&mut ValueSerializer {
+                    accessors: self.accessors,
+                    style,
+                }),
+                None => value.serialize(self),
+            },
         }
     }
 
@@ -303,8 +483,9 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         Ok(SerializeArray {
-            kind: tombi_document::ArrayKind::ArrayOfTable,
+            kind: self.style.array_kind(),
             accessors: self.accessors,
+            style: self.style,
             values: match len {
                 Some(len) => Vec::with_capacity(len),
                 None => Vec::new(),
@@ -314,8 +495,9 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
 
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
         Ok(SerializeArray {
-            kind: tombi_document::ArrayKind::ArrayOfTable,
+            kind: self.style.array_kind(),
             accessors: self.accessors,
+            style: self.style,
             values: Vec::with_capacity(len),
         })
     }
@@ -326,8 +508,9 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
         len: usize,
     ) -> Result<Self::SerializeTupleStruct, Self::Error> {
         Ok(SerializeArray {
-            kind: tombi_document::ArrayKind::ArrayOfTable,
+            kind: self.style.array_kind(),
             accessors: self.accessors,
+            style: self.style,
             values: Vec::with_capacity(len),
         })
     }
@@ -340,8 +523,9 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
         len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
         Ok(SerializeArray {
-            kind: tombi_document::ArrayKind::ArrayOfTable,
+            kind: self.style.array_kind(),
             accessors: self.accessors,
+            style: self.style,
             values: Vec::with_capacity(len),
         })
     }
@@ -349,6 +533,7 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         Ok(SerializeTable {
             accessors: self.accessors,
+            style: self.style,
             key: None,
             key_values: match len {
                 Some(len) => Vec::with_capacity(len),
@@ -364,6 +549,7 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
     ) -> Result<Self::SerializeStruct, Self::Error> {
         Ok(SerializeTable {
             accessors: self.accessors,
+            style: self.style,
             key: None,
             key_values: Vec::with_capacity(len),
         })
@@ -378,6 +564,7 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
         Ok(SerializeTable {
             accessors: self.accessors,
+            style: self.style,
             key: None,
             key_values: Vec::with_capacity(len),
         })
@@ -388,6 +575,7 @@ impl<'a> serde::Serializer for &'a mut ValueSerializer<'a> {
 pub struct SerializeArray<'a> {
     kind: tombi_document::ArrayKind,
     accessors: &'a [tombi_schema_store::Accessor],
+    style: Style,
     values: Vec<tombi_document::Value>,
 }
 
@@ -401,6 +589,7 @@ impl serde::ser::SerializeSeq for SerializeArray<'_> {
     {
         let Some(mut value) = value.serialize(&mut ValueSerializer {
             accessors: self.accessors,
+            style: self.style,
         })?
         else {
             let mut accessors = self.accessors.to_vec();
@@ -411,28 +600,15 @@ impl serde::ser::SerializeSeq for SerializeArray<'_> {
             ));
         };
         match &mut value {
-            tombi_document::Value::Boolean(_)
-            | tombi_document::Value::Integer(_)
-            | tombi_document::Value::Float(_)
-            | tombi_document::Value::String(_)
-            | tombi_document::Value::LocalDate(_)
-            | tombi_document::Value::LocalDateTime(_)
-            | tombi_document::Value::LocalTime(_)
-            | tombi_document::Value::OffsetDateTime(_) => {
-                self.kind = tombi_document::ArrayKind::Array;
-            }
-            tombi_document::Value::Array(array) => {
-                if self.kind == tombi_document::ArrayKind::Array {
-                    let array_kind = array.kind_mut();
-                    *array_kind = tombi_document::ArrayKind::Array;
-                }
-            }
             tombi_document::Value::Table(table) => {
                 if self.kind == tombi_document::ArrayKind::Array {
                     let table_kind = table.kind_mut();
                     *table_kind = tombi_document::TableKind::InlineTable;
                 }
             }
+            _ => {
+                self.kind = tombi_document::ArrayKind::Array;
+            }
         }
         self.values.push(value);
 
@@ -440,14 +616,42 @@ impl serde::ser::SerializeSeq for SerializeArray<'_> {
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut array = tombi_document::Array::new(self.kind);
-        for value in self.values {
+        // NOTE: An empty array of tables has no header to write, so it is written as `[]`.
+        let kind = if self.values.is_empty() {
+            tombi_document::ArrayKind::Array
+        } else {
+            self.kind
+        };
+        let mut array = tombi_document::Array::new(kind);
+        for mut value in self.values {
+            if kind == tombi_document::ArrayKind::Array {
+                into_inline_value(&mut value);
+            }
             array.push(value);
         }
         Ok(Some(tombi_document::Value::Array(array)))
     }
 }
 
+/// The values in an array are written inline,
+/// so the nested tables and arrays of tables are turned into the inline ones.
+fn into_inline_value(value: &mut tombi_document::Value) {
+    match value {
+        tombi_document::Value::Array(array) => {
+            *array.kind_mut() = tombi_document::ArrayKind::Array;
+            array.values_mut().iter_mut().for_each(into_inline_value);
+        }
+        tombi_document::Value::Table(table) => {
+            *table.kind_mut() = tombi_document::TableKind::InlineTable;
+            table
+                .key_values_mut()
+                .values_mut()
+                .for_each(into_inline_value);
+        }
+        _ => {}
+    }
+}
+
 impl serde::ser::SerializeTuple for SerializeArray<'_> {
     type Ok = Option<tombi_document::Value>;
     type Error = crate::ser::Error;
@@ -501,6 +705,7 @@ impl serde::ser::SerializeTupleVariant for SerializeArray<'_> {
 // Map serialization
 pub struct SerializeTable<'a> {
     accessors: &'a [tombi_schema_store::Accessor],
+    style: Style,
     key: Option<tombi_document::Key>,
     key_values: Vec<(tombi_document::Key, tombi_document::Value)>,
 }
@@ -516,6 +721,7 @@ impl serde::ser::SerializeMap for SerializeTable<'_> {
         // Keys must be converted to strings
         match key.serialize(&mut ValueSerializer {
             accessors: self.accessors,
+            style: Style::default(),
         }) {
             Ok(Some(tombi_document::Value::String(string))) => {
                 self.key = Some(tombi_document::Key::from(string));
@@ -552,6 +758,7 @@ impl serde::ser::SerializeMap for SerializeTable<'_> {
         };
         let Some(value) = value.serialize(&mut ValueSerializer {
             accessors: self.accessors,
+            style: self.style,
         })?
         else {
             self.key = None;
@@ -563,7 +770,8 @@ impl serde::ser::SerializeMap for SerializeTable<'_> {
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        let mut table = tombi_document::Table::new(tombi_document::TableKind::Table);
+        let mut table =
+            tombi_document::Table::new(self.style.table_kind(self.key_values.is_empty()));
         for (key, value) in self.key_values {
             table.insert(key, value);
         }
@@ -1051,4 +1259,350 @@ zzz = "1.0"
 
         toml_text_assert_eq!(toml, expected);
     }
+
+    #[test]
+    fn test_to_string_without_runtime() {
+        #[derive(Serialize)]
+        struct Test {
+            name: String,
+            values: Vec<i64>,
+        }
+
+        let test = Test {
+            name: "tombi".to_string(),
+            values: vec![1, 2, 3],
+        };
+        let expected = r#"
+name = "tombi"
+values = [1, 2, 3]
+"#;
+
+        let toml = to_string(&test).expect("TOML serialization failed");
+        toml_text_assert_eq!(toml, expected);
+
+        let mut buffer = Vec::new();
+        to_writer(&mut buffer, &test).expect("TOML serialization failed");
+        toml_text_assert_eq!(std::string::String::from_utf8(buffer).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_to_string_with_schema_url() {
+        #[derive(Serialize)]
+        struct Pyproject {
+            #[serde(rename = "build-system")]
+            build_system: BuildSystem,
+            project: Project,
+        }
+
+        #[derive(Serialize)]
+        struct BuildSystem {
+            #[serde(rename = "build-backend")]
+            build_backend: String,
+            requires: Vec<String>,
+        }
+
+        #[derive(Serialize)]
+        struct Project {
+            version: String,
+            name: String,
+            dependencies: Vec<String>,
+        }
+
+        let pyproject = Pyproject {
+            build_system: BuildSystem {
+                build_backend: "hatchling.build".to_string(),
+                requires: vec!["hatchling".to_string()],
+            },
+            project: Project {
+                version: "0.1.0".to_string(),
+                name: "tombi".to_string(),
+                dependencies: vec!["requests".to_string(), "attrs".to_string()],
+            },
+        };
+        let schema_url =
+            tombi_schema_store::SchemaUrl::from_file_path(tombi_test_lib::pyproject_schema_path())
+                .unwrap();
+
+        let toml = Serializer::builder()
+            .schema_url(&schema_url)
+            .offline(true)
+            .build()
+            .to_string(&pyproject)
+            .expect("TOML serialization failed");
+
+        let expected = r#"
+[project]
+name = "tombi"
+version = "0.1.0"
+dependencies = ["attrs", "requests"]
+
+[build-system]
+requires = ["hatchling"]
+build-backend = "hatchling.build"
+"#;
+
+        toml_text_assert_eq!(toml, expected);
+    }
+
+    #[test]
+    fn test_to_string_with_schema_url_and_overrides() {
+        #[derive(Serialize)]
+        struct Pyproject {
+            project: Project,
+        }
+
+        #[derive(Serialize)]
+        struct Project {
+            name: String,
+            dependencies: Vec<String>,
+        }
+
+        let pyproject = Pyproject {
+            project: Project {
+                name: "tombi".to_string(),
+                dependencies: vec!["attrs".to_string(), "requests".to_string()],
+            },
+        };
+        let schema_url =
+            tombi_schema_store::SchemaUrl::from_file_path(tombi_test_lib::pyproject_schema_path())
+                .unwrap();
+        let schema_store = SchemaStore::new_with_options(tombi_schema_store::Options {
+            offline: Some(true),
+            ..Default::default()
+        });
+        futures::executor::block_on(schema_store.load_overrides(&[tombi_config::OverrideItem {
+            include: vec!["*.toml".to_string()],
+            keys: vec!["project.dependencies".to_string()],
+            table_keys_order: None,
+            array_values_order: Some(tombi_x_keyword::ArrayValuesOrder::Descending),
+        }]));
+
+        let toml = Serializer::builder()
+            .schema_store(&schema_store)
+            .schema_url(&schema_url)
+            .source_path(std::path::Path::new("pyproject.toml"))
+            .build()
+            .to_string(&pyproject)
+            .expect("TOML serialization failed");
+
+        let expected = r#"
+[project]
+name = "tombi"
+dependencies = ["requests", "attrs"]
+"#;
+
+        toml_text_assert_eq!(toml, expected);
+    }
+
+    #[test]
+    fn test_serialize_with_style_attributes() {
+        #[derive(Serialize)]
+        struct Test {
+            #[serde(with = "crate::literal")]
+            path: String,
+            #[serde(with = "crate::literal")]
+            quoted: String,
+            #[serde(with = "crate::multiline")]
+            message: String,
+            #[serde(with = "crate::inline")]
+            dependency: Dependency,
+            #[serde(with = "crate::dotted")]
+            package: Package,
+            #[serde(with = "crate::inline")]
+            bin: Vec<Bin>,
+            profile: Dependency,
+        }
+
+        #[derive(Serialize)]
+        struct Dependency {
+            version: String,
+            features: Vec<String>,
+        }
+
+        #[derive(Serialize)]
+        struct Package {
+            name: String,
+            metadata: Dependency,
+        }
+
+        #[derive(Serialize)]
+        struct Bin {
+            name: String,
+        }
+
+        let test = Test {
+            path: r"C:\Users".to_string(),
+            quoted: "tombi's".to_string(),
+            message: "Hello,\n\"\"\"World\"\"\"".to_string(),
+            dependency: Dependency {
+                version: "1.0".to_string(),
+                features: vec!["derive".to_string()],
+            },
+            package: Package {
+                name: "tombi".to_string(),
+                metadata: Dependency {
+                    version: "0.1.0".to_string(),
+                    features: vec![],
+                },
+            },
+            bin: vec![
+                Bin {
+                    name: "a".to_string(),
+                },
+                Bin {
+                    name: "b".to_string(),
+                },
+            ],
+            profile: Dependency {
+                version: "2.0".to_string(),
+                features: vec![],
+            },
+        };
+
+        let expected = r#"
+path = 'C:\Users'
+quoted = "tombi's"
+message = """
+Hello,
+""\"World""\""""
+dependency = { version = "1.0", features = ["derive"] }
+package.name = "tombi"
+package.metadata.version = "0.1.0"
+package.metadata.features = []
+bin = [{ name = "a" }, { name = "b" }]
+
+[profile]
+version = "2.0"
+features = []
+"#;
+
+        let toml = to_string(&test).expect("TOML serialization failed");
+        toml_text_assert_eq!(toml, expected);
+    }
+
+    #[test]
+    fn test_serialize_with_style_options() {
+        #[derive(Serialize)]
+        struct Test {
+            description: String,
+            pattern: String,
+            server: Server,
+        }
+
+        #[derive(Serialize)]
+        struct Server {
+            host: String,
+        }
+
+        let test = Test {
+            description: "line1\nline2\n".to_string(),
+            pattern: r"^\w+$".to_string(),
+            server: Server {
+                host: "localhost".to_string(),
+            },
+        };
+
+        let expected = r#"
+description = '''
+line1
+line2
+'''
+pattern = '^\w+$'
+server = { host = 'localhost' }
+"#;
+
+        let toml = Serializer::builder()
+            .offline(true)
+            .inline_tables(true)
+            .literal_strings(true)
+            .multiline_strings(true)
+            .build()
+            .to_string(&test)
+            .expect("TOML serialization failed");
+        toml_text_assert_eq!(toml, expected);
+    }
+
+    #[test]
+    fn test_serialize_with_config_format_options() {
+        #[derive(Serialize)]
+        struct Test {
+            name: String,
+            version: String,
+        }
+
+        let test = Test {
+            name: "tombi".to_string(),
+            version: "0.1.0".to_string(),
+        };
+        let mut config = tombi_config::Config::default();
+        config.format = Some(tombi_config::FormatOptions {
+            key_value_equal_alignment: Some(true),
+            ..tombi_config::FormatOptions::default()
+        });
+        let expected = r#"
+name    = "tombi"
+version = "0.1.0"
+"#;
+
+        let toml = Serializer::builder()
+            .config(&config)
+            .offline(true)
+            .build()
+            .to_string(&test)
+            .expect("TOML serialization failed");
+        toml_text_assert_eq!(toml, expected);
+    }
+
+    #[test]
+    fn test_serialize_nested_array() {
+        #[derive(Serialize)]
+        struct Test {
+            matrix: Vec<Vec<i64>>,
+            points: Vec<Vec<Point>>,
+        }
+
+        #[derive(Serialize)]
+        struct Point {
+            x: i64,
+        }
+
+        let test = Test {
+            matrix: vec![vec![1, 2], vec![3]],
+            points: vec![vec![Point { x: 1 }], vec![]],
+        };
+        let expected = r#"
+matrix = [[1, 2], [3]]
+points = [[{ x = 1 }], []]
+"#;
+
+        let toml = to_string(&test).expect("TOML serialization failed");
+        toml_text_assert_eq!(toml, expected);
+    }
+
+    #[test]
+    fn test_serialize_float() {
+        #[derive(Serialize)]
+        struct Test {
+            integral: f64,
+            large: f64,
+            nan: f64,
+            neg_inf: f64,
+        }
+
+        let test = Test {
+            integral: 1.0,
+            large: 1e300,
+            nan: f64::NAN,
+            neg_inf: f64::NEG_INFINITY,
+        };
+        let expected = r#"
+integral = 1.0
+large = 1e300
+nan = nan
+neg_inf = -inf
+"#;
+
+        let toml = to_string(&test).expect("TOML serialization failed");
+        toml_text_assert_eq!(toml, expected);
+    }
 }
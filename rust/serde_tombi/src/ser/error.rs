@@ -42,6 +42,12 @@ pub enum Error {
 
     #[error("{0}")]
     Serde(std::string::String),
+
+    #[error(
+        "Serialized document is not valid TOML: {}",
+        .0.iter().map(tombi_diagnostic::Diagnostic::message).collect::<Vec<_>>().join(", ")
+    )]
+    InvalidToml(Vec<tombi_diagnostic::Diagnostic>),
 }
 
 impl serde::ser::Error for Error {
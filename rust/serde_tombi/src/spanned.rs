@@ -0,0 +1,261 @@
+pub(crate) const NAME: &str = "$__serde_tombi_private_Spanned";
+pub(crate) const KEY_RANGE_FIELD: &str = "$__serde_tombi_private_key_range";
+pub(crate) const RANGE_FIELD: &str = "$__serde_tombi_private_range";
+pub(crate) const VALUE_FIELD: &str = "$__serde_tombi_private_value";
+pub(crate) const FIELDS: &[&str] = &[KEY_RANGE_FIELD, RANGE_FIELD, VALUE_FIELD];
+
+/// A deserialized value with the ranges of its key and value in the source text.
+///
+/// The ranges are captured only when deserializing from the TOML text or the document tree,
+/// such as [crate::from_str] and [crate::from_document_tree].
+/// The serialization is transparent, so the value is serialized as is.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_tombi::Spanned;
+///
+/// #[derive(Deserialize)]
+/// struct Config {
+///     port: Spanned<u16>,
+/// }
+///
+/// let config: Config = serde_tombi::from_str("port = 80").unwrap();
+///
+/// assert_eq!(*config.port.get_ref(), 80);
+/// assert_eq!(config.port.range().start.column, 7);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spanned<T> {
+    key_range: Option<tombi_text::Range>,
+    range: tombi_text::Range,
+    value: T,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(key_range: Option<tombi_text::Range>, range: tombi_text::Range, value: T) -> Self {
+        Self {
+            key_range,
+            range,
+            value,
+        }
+    }
+
+    /// The range of the key.
+    ///
+    /// Returns `None` for the values without a key, such as the items of an array.
+    #[inline]
+    pub fn key_range(&self) -> Option<tombi_text::Range> {
+        self.key_range
+    }
+
+    /// The range of the value.
+    #[inline]
+    pub fn range(&self) -> tombi_text::Range {
+        self.range
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Spanned<T> {}
+
+impl<T: std::hash::Hash> std::hash::Hash for Spanned<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T> serde::Serialize for Spanned<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T> serde::Deserialize<'de> for Spanned<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SpannedVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T> serde::de::Visitor<'de> for SpannedVisitor<T>
+        where
+            T: serde::Deserialize<'de>,
+        {
+            type Value = Spanned<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a spanned value")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Spanned<T>, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut key_range = None;
+                let mut range = None;
+                while let Some(field) = map.next_key::<std::string::String>()? {
+                    match field.as_str() {
+                        KEY_RANGE_FIELD => key_range = Some(array_to_range(map.next_value()?)),
+                        RANGE_FIELD => range = Some(array_to_range(map.next_value()?)),
+                        VALUE_FIELD => {
+                            let Some(range) = range else {
+                                return Err(serde::de::Error::missing_field(RANGE_FIELD));
+                            };
+                            return Ok(Spanned::new(key_range, range, map.next_value()?));
+                        }
+                        _ => return Err(serde::de::Error::custom(
+                            "Spanned is only supported when deserializing from the document tree",
+                        )),
+                    }
+                }
+
+                Err(serde::de::Error::missing_field(VALUE_FIELD))
+            }
+        }
+
+        deserializer.deserialize_struct(NAME, FIELDS, SpannedVisitor(std::marker::PhantomData))
+    }
+}
+
+pub(crate) fn range_to_array(range: tombi_text::Range) -> [u32; 4] {
+    [
+        range.start.line,
+        range.start.column,
+        range.end.line,
+        range.end.column,
+    ]
+}
+
+fn array_to_range([start_line, start_column, end_line, end_column]: [u32; 4]) -> tombi_text::Range {
+    tombi_text::Range::new(
+        tombi_text::Position::new(start_line, start_column),
+        tombi_text::Position::new(end_line, end_column),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use tombi_test_lib::toml_text_assert_eq;
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> tombi_text::Range {
+        tombi_text::Range::new(
+            tombi_text::Position::new(start.0, start.1),
+            tombi_text::Position::new(end.0, end.1),
+        )
+    }
+
+    #[test]
+    fn test_spanned_key_value() {
+        #[derive(Debug, Deserialize)]
+        struct Server {
+            host: Spanned<String>,
+            port: Spanned<u16>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Test {
+            server: Spanned<Server>,
+        }
+
+        let toml = r#"[server]
+host = "localhost"
+port = 8080
+"#;
+
+        let test: Test = crate::from_str(toml).expect("TOML deserialization failed");
+        let server = test.server.get_ref();
+
+        pretty_assertions::assert_eq!(server.host.get_ref(), "localhost");
+        pretty_assertions::assert_eq!(server.host.key_range(), Some(range((1, 0), (1, 4))));
+        pretty_assertions::assert_eq!(server.host.range(), range((1, 7), (1, 18)));
+        pretty_assertions::assert_eq!(*server.port.get_ref(), 8080);
+        pretty_assertions::assert_eq!(server.port.key_range(), Some(range((2, 0), (2, 4))));
+        pretty_assertions::assert_eq!(server.port.range(), range((2, 7), (2, 11)));
+        pretty_assertions::assert_eq!(test.server.key_range(), Some(range((0, 1), (0, 7))));
+    }
+
+    #[test]
+    fn test_spanned_array_values() {
+        #[derive(Debug, Deserialize)]
+        struct Test {
+            values: Vec<Spanned<i64>>,
+        }
+
+        let toml = r#"values = [1, 22]"#;
+
+        let test: Test = crate::from_str(toml).expect("TOML deserialization failed");
+
+        pretty_assertions::assert_eq!(test.values[0].key_range(), None);
+        pretty_assertions::assert_eq!(test.values[0].range(), range((0, 10), (0, 11)));
+        pretty_assertions::assert_eq!(test.values[1].range(), range((0, 13), (0, 15)));
+    }
+
+    #[test]
+    fn test_spanned_round_trip() {
+        #[derive(Debug, Deserialize, Serialize, PartialEq)]
+        struct Test {
+            name: Spanned<String>,
+            values: Spanned<Vec<i64>>,
+        }
+
+        let toml = r#"
+name = "tombi"
+values = [1, 2, 3]
+"#;
+
+        let test: Test = crate::from_str(toml).expect("TOML deserialization failed");
+        let serialized = crate::to_string(&test).expect("TOML serialization failed");
+
+        toml_text_assert_eq!(serialized, toml);
+        pretty_assertions::assert_eq!(
+            crate::from_str::<Test>(&serialized).expect("TOML deserialization failed"),
+            test
+        );
+    }
+
+    #[test]
+    fn test_spanned_from_document() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Test {
+            name: Spanned<String>,
+        }
+
+        let document = crate::to_document(&maplit::hashmap! { "name" => "tombi" }).unwrap();
+
+        assert!(crate::from_document::<Test>(document).is_err());
+    }
+}
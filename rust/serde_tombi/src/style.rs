@@ -0,0 +1,171 @@
+//! Serde helpers to choose how the field is written in TOML.
+//!
+//! The helpers are used with `#[serde(with = "...")]`,
+//! and work as the plain values for the other serializers and all deserializers.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Dependency {
+//!     version: String,
+//!     features: Vec<String>,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct Manifest {
+//!     #[serde(with = "serde_tombi::literal")]
+//!     pattern: String,
+//!     #[serde(with = "serde_tombi::inline")]
+//!     serde: Dependency,
+//! }
+//!
+//! let manifest = Manifest {
+//!     pattern: r"^\d+$".to_string(),
+//!     serde: Dependency {
+//!         version: "1.0".to_string(),
+//!         features: vec!["derive".to_string()],
+//!     },
+//! };
+//!
+//! assert_eq!(
+//!     serde_tombi::to_string(&manifest).unwrap(),
+//!     r#"pattern = '^\d+$'
+//! serde = { version = "1.0", features = ["derive"] }
+//! "#
+//! );
+//! ```
+
+pub(crate) const INLINE_NAME: &str = "$__serde_tombi_private_Inline";
+pub(crate) const DOTTED_NAME: &str = "$__serde_tombi_private_Dotted";
+pub(crate) const LITERAL_NAME: &str = "$__serde_tombi_private_Literal";
+pub(crate) const MULTILINE_NAME: &str = "$__serde_tombi_private_Multiline";
+
+macro_rules! style_module {
+    ($(#[$attr:meta])* $module:ident, $name:ident) => {
+        $(#[$attr])*
+        pub mod $module {
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: ?Sized + serde::Serialize,
+                S: serde::Serializer,
+            {
+                serializer.serialize_newtype_struct(super::$name, value)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: serde::Deserialize<'de>,
+                D: serde::Deserializer<'de>,
+            {
+                T::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+style_module!(
+    /// Write the tables as inline tables, and the arrays of tables as arrays of inline tables.
+    ///
+    /// Applies to all the values nested in the field.
+    inline,
+    INLINE_NAME
+);
+
+style_module!(
+    /// Write the tables as dotted keys, such as `package.name = "tombi"`.
+    ///
+    /// Applies to all the values nested in the field.
+    /// The arrays of tables and the empty tables are written inline, as dotted keys cannot express them.
+    dotted,
+    DOTTED_NAME
+);
+
+style_module!(
+    /// Write the strings as literal strings, such as `'C:\Users'`.
+    ///
+    /// The strings that literal strings cannot contain, such as `'`, are written as basic strings.
+    literal,
+    LITERAL_NAME
+);
+
+style_module!(
+    /// Write the strings containing line breaks as multi-line strings.
+    ///
+    /// Combine with [literal] for multi-line literal strings.
+    multiline,
+    MULTILINE_NAME
+);
+
+/// The style of the values, inherited by the nested values.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct Style {
+    pub inline: bool,
+    pub dotted: bool,
+    pub literal: bool,
+    pub multiline: bool,
+}
+
+impl Style {
+    pub(crate) fn with_newtype_name(self, name: &str) -> Option<Self> {
+        match name {
+            INLINE_NAME => Some(Self {
+                inline: true,
+                ..self
+            }),
+            DOTTED_NAME => Some(Self {
+                dotted: true,
+                ..self
+            }),
+            LITERAL_NAME => Some(Self {
+                literal: true,
+                ..self
+            }),
+            MULTILINE_NAME => Some(Self {
+                multiline: true,
+                ..self
+            }),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn table_kind(&self, is_empty: bool) -> tombi_document::TableKind {
+        if self.inline || (self.dotted && is_empty) {
+            tombi_document::TableKind::InlineTable
+        } else if self.dotted {
+            tombi_document::TableKind::KeyValue
+        } else {
+            tombi_document::TableKind::Table
+        }
+    }
+
+    pub(crate) fn array_kind(&self) -> tombi_document::ArrayKind {
+        if self.inline || self.dotted {
+            tombi_document::ArrayKind::Array
+        } else {
+            tombi_document::ArrayKind::ArrayOfTable
+        }
+    }
+
+    pub(crate) fn string_kind(&self, value: &str) -> tombi_document::StringKind {
+        let multiline = self.multiline && value.contains('\n');
+        let literal = self.literal
+            && value
+                .chars()
+                .all(|c| c == '\t' || (multiline && c == '\n') || !c.is_control())
+            && if multiline {
+                !value.contains("'''")
+            } else {
+                !value.contains('\'')
+            };
+
+        match (multiline, literal) {
+            (false, false) => tombi_document::StringKind::BasicString,
+            (false, true) => tombi_document::StringKind::LiteralString,
+            (true, false) => tombi_document::StringKind::MultiLineBasicString,
+            (true, true) => tombi_document::StringKind::MultiLineLiteralString,
+        }
+    }
+}
@@ -70,6 +70,9 @@ pub fn run(args: impl Into<Args>) -> Result<(), crate::Error> {
         command::TomlCommand::Format(args) => command::format::run(args, offline),
         command::TomlCommand::Lint(args) => command::lint::run(args, offline),
         command::TomlCommand::Lsp(args) => command::lsp::run(args, offline),
+        command::TomlCommand::Schema(args) => command::schema::run(args, offline),
+        command::TomlCommand::Cache(args) => command::cache::run(args),
+        command::TomlCommand::Convert(args) => command::convert::run(args, offline),
     }
 }
 
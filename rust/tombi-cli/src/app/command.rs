@@ -1,6 +1,9 @@
+pub mod cache;
+pub mod convert;
 pub mod format;
 pub mod lint;
 pub mod lsp;
+pub mod schema;
 
 #[derive(clap::Subcommand)]
 pub enum TomlCommand {
@@ -12,4 +15,10 @@ pub enum TomlCommand {
 
     #[command(alias = "serve")]
     Lsp(lsp::Args),
+
+    Schema(schema::Args),
+
+    Cache(cache::Args),
+
+    Convert(convert::Args),
 }
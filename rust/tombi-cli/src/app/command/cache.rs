@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use nu_ansi_term::{Color, Style};
+
+/// Manage the cache of remote schemas and catalogs.
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    #[command(subcommand)]
+    subcommand: CacheCommand,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum CacheCommand {
+    /// List the cached schemas and catalogs.
+    #[command(alias = "ls")]
+    List,
+
+    /// Remove all the cached schemas and catalogs.
+    Clear,
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run(args: Args) -> Result<(), crate::Error> {
+    let Some(cache_dir_path) = tombi_schema_store::cache::get_cache_dir_path() else {
+        return Err(crate::Error::CacheDirNotFound);
+    };
+    tracing::debug!("cache directory: {:?}", cache_dir_path);
+
+    match args.subcommand {
+        CacheCommand::List => {
+            let cache_ttl = match serde_tombi::config::load() {
+                Ok(config) => config
+                    .schema
+                    .as_ref()
+                    .and_then(|schema_options| schema_options.cache_ttl())
+                    .map(Duration::from_secs)
+                    .unwrap_or(tombi_schema_store::cache::DEFAULT_CACHE_TTL),
+                Err(err) => {
+                    tracing::warn!("{err}");
+                    tombi_schema_store::cache::DEFAULT_CACHE_TTL
+                }
+            };
+
+            let entries = tombi_schema_store::cache::list_cache(&cache_dir_path)?;
+            for entry in &entries {
+                let status = if entry.metadata.is_expired(cache_ttl) {
+                    Color::Yellow.paint("expired")
+                } else {
+                    Color::Green.paint("fresh")
+                };
+                println!(
+                    "{} ({} bytes, {})",
+                    Style::new().bold().paint(&entry.metadata.url),
+                    entry.size(),
+                    status
+                );
+            }
+
+            match entries.len() {
+                0 => eprintln!("No cached files in {:?}", cache_dir_path),
+                1 => eprintln!("1 cached file in {:?}", cache_dir_path),
+                n => eprintln!("{n} cached files in {:?}", cache_dir_path),
+            }
+        }
+        CacheCommand::Clear => match tombi_schema_store::cache::clear_cache(&cache_dir_path)? {
+            0 => eprintln!("No cached files to remove"),
+            1 => eprintln!("1 cached file removed"),
+            n => eprintln!("{n} cached files removed"),
+        },
+    }
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use std::io::Read;
+
+use tombi_config::TomlVersion;
+use tombi_diagnostic::{printer::Pretty, Diagnostic, Print, SetDiagnostics};
+use tombi_document::JsonEncoding;
+
+/// Convert the documents between TOML, JSON and YAML.
+///
+/// Writes the converted document to stdout.
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Path to the document. If omitted, reads from stdin.
+    file: Option<std::path::PathBuf>,
+
+    /// Format of the input document.
+    ///
+    /// If omitted, inferred from the file extension.
+    /// Without the file extension, TOML is converted to the other formats,
+    /// and the other formats are converted to TOML.
+    #[arg(long, value_enum)]
+    from: Option<Format>,
+
+    /// Format of the output document.
+    #[arg(long, value_enum)]
+    to: Format,
+
+    /// Encode the TOML values in JSON as the tagged objects of the toml-test encoding,
+    /// such as `{"type": "integer", "value": "42"}`.
+    ///
+    /// Keeps the types of the date-times and the precision of the integers.
+    #[arg(long)]
+    tagged: bool,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl Format {
+    fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run(args: Args, offline: bool) -> Result<(), crate::Error> {
+    match inner_run(args, Pretty, offline) {
+        Ok(Some(output)) => print!("{output}"),
+        Ok(None) => std::process::exit(1),
+        Err(error) => {
+            tracing::error!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+fn inner_run<P>(
+    args: Args,
+    mut printer: P,
+    offline: bool,
+) -> Result<Option<String>, Box<dyn std::error::Error>>
+where
+    Diagnostic: Print<P>,
+{
+    let config = serde_tombi::config::load()?;
+    let toml_version = config.toml_version.unwrap_or_default();
+
+    let source = match &args.file {
+        Some(path) => std::fs::read_to_string(path).map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => crate::Error::FileNotFound(path.to_owned()),
+            _ => crate::Error::Io(err),
+        })?,
+        None => {
+            let mut source = String::new();
+            std::io::stdin().read_to_string(&mut source)?;
+            source
+        }
+    };
+
+    let from = args
+        .from
+        .or_else(|| args.file.as_deref().and_then(Format::from_path))
+        .unwrap_or(match args.to {
+            Format::Toml => Format::Json,
+            Format::Json | Format::Yaml => Format::Toml,
+        });
+    let encoding = if args.tagged {
+        JsonEncoding::Tagged
+    } else {
+        JsonEncoding::Plain
+    };
+
+    let document = match read_document(&source, from, encoding, toml_version) {
+        Ok(document) => document,
+        Err(ReadError::Parse(diagnostics)) => {
+            if let Some(path) = &args.file {
+                diagnostics
+                    .into_iter()
+                    .map(|diagnostic| diagnostic.with_source_file(path))
+                    .collect()
+            } else {
+                diagnostics
+            }
+            .print(&mut printer);
+            return Ok(None);
+        }
+        Err(ReadError::Convert(error)) => return Err(convert_error(&error).into()),
+    };
+
+    Ok(Some(write_document(
+        &document, args.to, encoding, &config, offline,
+    )?))
+}
+
+enum ReadError {
+    /// The TOML document has the syntax errors.
+    Parse(Vec<Diagnostic>),
+    /// The document cannot be represented in TOML.
+    Convert(tombi_document::de::Error),
+}
+
+fn read_document(
+    source: &str,
+    from: Format,
+    encoding: JsonEncoding,
+    toml_version: TomlVersion,
+) -> Result<tombi_document::Document, ReadError> {
+    match from {
+        Format::Toml => parse_toml(source, toml_version).map_err(ReadError::Parse),
+        Format::Json => tombi_document::Document::try_from_json_str(source, encoding)
+            .map_err(ReadError::Convert),
+        Format::Yaml => {
+            tombi_document::Document::try_from_yaml_str(source).map_err(ReadError::Convert)
+        }
+    }
+}
+
+fn write_document(
+    document: &tombi_document::Document,
+    to: Format,
+    encoding: JsonEncoding,
+    config: &tombi_config::Config,
+    offline: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    match to {
+        Format::Toml => Ok(serde_tombi::Serializer::builder()
+            .config(config)
+            .offline(offline)
+            .build()
+            .to_string(document)?),
+        Format::Json => {
+            let mut output = Vec::new();
+            document.to_json_writer(&mut output, encoding)?;
+            output.push(b'\n');
+            Ok(String::from_utf8(output)?)
+        }
+        Format::Yaml => Ok(document.to_yaml_string()),
+    }
+}
+
+fn parse_toml(
+    source: &str,
+    toml_version: TomlVersion,
+) -> Result<tombi_document::Document, Vec<Diagnostic>> {
+    use tombi_document::IntoDocument;
+    use tombi_document_tree::IntoDocumentTreeAndErrors;
+
+    let mut diagnostics = Vec::new();
+    let root = match tombi_parser::parse(source, toml_version).try_into_root() {
+        Ok(root) => root,
+        Err(errors) => {
+            errors.set_diagnostics(&mut diagnostics);
+            return Err(diagnostics);
+        }
+    };
+    let (document_tree, errors) = root.into_document_tree_and_errors(toml_version).into();
+    if !errors.is_empty() {
+        errors.set_diagnostics(&mut diagnostics);
+        return Err(diagnostics);
+    }
+
+    Ok(document_tree.into_document(toml_version))
+}
+
+fn convert_error(error: &tombi_document::de::Error) -> String {
+    if error.path().is_empty() {
+        error.message()
+    } else {
+        format!(
+            "{} for `{}`",
+            error.message(),
+            tombi_document::de::join_path(error.path())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(source: &str, from: Format, to: Format, encoding: JsonEncoding) -> String {
+        let document = read_document(source, from, encoding, TomlVersion::default())
+            .unwrap_or_else(|_| panic!("failed to read {from:?}"));
+        write_document(
+            &document,
+            to,
+            encoding,
+            &tombi_config::Config::default(),
+            true,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn json_floats_round_trip() {
+        let toml = convert(
+            r#"{"f": 1.0, "g": 1e300, "h": -0.5}"#,
+            Format::Json,
+            Format::Toml,
+            JsonEncoding::Plain,
+        );
+        pretty_assertions::assert_eq!(toml, "f = 1.0\ng = 1e300\nh = -0.5\n");
+
+        let json = convert(&toml, Format::Toml, Format::Json, JsonEncoding::Plain);
+        pretty_assertions::assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!({"f": 1.0, "g": 1e300, "h": -0.5})
+        );
+    }
+
+    #[test]
+    fn tagged_special_floats_round_trip() {
+        let source = serde_json::json!({
+            "nan": {"type": "float", "value": "nan"},
+            "inf": {"type": "float", "value": "inf"},
+            "neg_inf": {"type": "float", "value": "-inf"},
+        })
+        .to_string();
+
+        let toml = convert(&source, Format::Json, Format::Toml, JsonEncoding::Tagged);
+        pretty_assertions::assert_eq!(toml, "nan = nan\ninf = inf\nneg_inf = -inf\n");
+
+        let json = convert(&toml, Format::Toml, Format::Json, JsonEncoding::Tagged);
+        pretty_assertions::assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&source).unwrap()
+        );
+    }
+
+    #[test]
+    fn yaml_floats_round_trip() {
+        let toml = convert(
+            "f: 1.0\nnan: .nan\ninf: -.inf\n",
+            Format::Yaml,
+            Format::Toml,
+            JsonEncoding::Plain,
+        );
+        pretty_assertions::assert_eq!(toml, "f = 1.0\nnan = nan\ninf = -inf\n");
+
+        let yaml = convert(&toml, Format::Toml, Format::Yaml, JsonEncoding::Plain);
+        pretty_assertions::assert_eq!(yaml, "f: 1.0\nnan: .nan\ninf: -.inf\n");
+    }
+
+    #[test]
+    fn json_integer_out_of_range() {
+        let Err(ReadError::Convert(error)) = read_document(
+            r#"{"big": 18446744073709551615}"#,
+            Format::Json,
+            JsonEncoding::Plain,
+            TomlVersion::default(),
+        ) else {
+            panic!("expected a conversion error");
+        };
+        pretty_assertions::assert_eq!(
+            convert_error(&error),
+            "integer 18446744073709551615 is out of the range of TOML integers for `big`"
+        );
+    }
+}
@@ -16,6 +16,13 @@ pub struct Args {
     /// Check only and don't overwrite files.
     #[arg(long, default_value_t = false)]
     check: bool,
+
+    /// Verify that the result does not change when formatted again,
+    /// and that the data of the document does not change by formatting.
+    ///
+    /// Files failing the verification are not overwritten.
+    #[arg(long, default_value_t = false)]
+    verify: bool,
 }
 
 #[tracing::instrument(level = "debug", skip_all)]
@@ -78,6 +85,7 @@ where
         tombi_schema_store::SchemaStore::new_with_options(tombi_schema_store::Options {
             offline: offline.then_some(true),
             strict: schema_options.and_then(|schema_options| schema_options.strict()),
+            cache: tombi_schema_store::CacheOptions::from_schema_options(schema_options),
         });
 
     let Ok(runtime) = tokio::runtime::Builder::new_multi_thread()
@@ -121,6 +129,7 @@ where
                     printer,
                     toml_version,
                     args.check,
+                    args.verify,
                     &format_options,
                     &schema_store,
                 )
@@ -151,6 +160,7 @@ where
                                             Some(&source_path),
                                             toml_version,
                                             args.check,
+                                            args.verify,
                                             &format_options,
                                             &schema_store,
                                         )
@@ -207,6 +217,7 @@ async fn format_stdin<P>(
     mut printer: P,
     toml_version: TomlVersion,
     check: bool,
+    verify: bool,
     format_options: &FormatOptions,
     schema_store: &tombi_schema_store::SchemaStore,
 ) -> Result<bool, ()>
@@ -227,6 +238,25 @@ where
         .await
         {
             Ok(formatted) => {
+                if verify {
+                    let errors = verify_formatted(
+                        &source,
+                        &formatted,
+                        None,
+                        toml_version,
+                        format_options,
+                        schema_store,
+                    )
+                    .await;
+                    if !errors.is_empty() {
+                        print!("{source}");
+                        for error in errors {
+                            error.into_error().print(&mut printer);
+                        }
+                        return Err(());
+                    }
+                }
+
                 if check {
                     if source != formatted {
                         crate::error::NotFormattedError::from(file.source())
@@ -258,6 +288,7 @@ async fn format_file<P>(
     source_path: Option<&std::path::Path>,
     toml_version: TomlVersion,
     check: bool,
+    verify: bool,
     format_options: &FormatOptions,
     schema_store: &tombi_schema_store::SchemaStore,
 ) -> Result<bool, ()>
@@ -278,6 +309,24 @@ where
         .await
         {
             Ok(formatted) => {
+                if verify {
+                    let errors = verify_formatted(
+                        &source,
+                        &formatted,
+                        source_path,
+                        toml_version,
+                        format_options,
+                        schema_store,
+                    )
+                    .await;
+                    if !errors.is_empty() {
+                        for error in errors {
+                            error.into_error().print(&mut printer);
+                        }
+                        return Err(());
+                    }
+                }
+
                 if source != formatted {
                     if check {
                         crate::error::NotFormattedError::from(file.source())
@@ -313,6 +362,66 @@ where
     Err(())
 }
 
+/// Verify that the formatted text is stable and has the same data as the source.
+async fn verify_formatted(
+    source: &str,
+    formatted: &str,
+    source_path: Option<&std::path::Path>,
+    toml_version: TomlVersion,
+    format_options: &FormatOptions,
+    schema_store: &tombi_schema_store::SchemaStore,
+) -> Vec<crate::error::VerifyError> {
+    let mut errors = vec![];
+
+    let reformatted = tombi_formatter::Formatter::new(
+        toml_version,
+        FormatDefinitions::default(),
+        format_options,
+        source_path.map(itertools::Either::Right),
+        schema_store,
+    )
+    .format(formatted)
+    .await;
+
+    if reformatted.as_deref() != Ok(formatted) {
+        errors.push(crate::error::VerifyError::NotIdempotent {
+            source_path: source_path.map(ToOwned::to_owned),
+        });
+    }
+
+    if let (Some(source_document), Some(formatted_document)) = (
+        parse_document(source, toml_version),
+        parse_document(formatted, toml_version),
+    ) {
+        errors.extend(
+            source_document
+                .diff(&formatted_document)
+                .into_iter()
+                .map(|difference| crate::error::VerifyError::DataChanged {
+                    source_path: source_path.map(ToOwned::to_owned),
+                    difference: Box::new(difference),
+                }),
+        );
+    }
+
+    errors
+}
+
+fn parse_document(source: &str, toml_version: TomlVersion) -> Option<tombi_document::Document> {
+    use tombi_document::IntoDocument;
+    use tombi_document_tree::IntoDocumentTreeAndErrors;
+
+    let root = tombi_parser::parse(source, toml_version)
+        .try_into_root()
+        .ok()?;
+    let (document_tree, errors) = root.into_document_tree_and_errors(toml_version).into();
+    if !errors.is_empty() {
+        return None;
+    }
+
+    Some(document_tree.into_document(toml_version))
+}
+
 enum FormatFile {
     Stdin(tokio::io::Stdin),
     File {
@@ -64,6 +64,7 @@ where
         tombi_schema_store::SchemaStore::new_with_options(tombi_schema_store::Options {
             offline: offline.then_some(true),
             strict: schema_options.and_then(|schema_options| schema_options.strict()),
+            cache: tombi_schema_store::CacheOptions::from_schema_options(schema_options),
         });
 
     let Ok(runtime) = tokio::runtime::Builder::new_multi_thread()
@@ -198,15 +199,29 @@ where
 {
     let mut source = String::new();
     if reader.read_to_string(&mut source).await.is_ok() {
-        match tombi_linter::Linter::new(
-            toml_version,
-            lint_options,
-            source_path.map(itertools::Either::Right),
-            schema_store,
-        )
-        .lint(&source)
-        .await
+        let result = if source_path
+            .is_some_and(|source_path| tombi_linter::is_json_schema_file(source_path, &source))
         {
+            tombi_linter::JsonSchemaLinter::new(
+                source_path.map(itertools::Either::Right),
+                schema_store,
+            )
+            .lint(&source)
+            .await
+        } else if let Some(source_path) = source_path.filter(|path| is_json_file(path)) {
+            tracing::warn!("{source_path:?} is not a JSON Schema file, so it is not linted");
+            return true;
+        } else {
+            tombi_linter::Linter::new(
+                toml_version,
+                lint_options,
+                source_path.map(itertools::Either::Right),
+                schema_store,
+            )
+            .lint(&source)
+            .await
+        };
+        match result {
             Ok(()) => {
                 return true;
             }
@@ -223,3 +238,8 @@ where
     }
     false
 }
+
+fn is_json_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "json")
+}
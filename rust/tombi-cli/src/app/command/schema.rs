@@ -0,0 +1,19 @@
+pub mod vendor;
+
+/// Manage JSON Schemas.
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    #[command(subcommand)]
+    subcommand: SchemaCommand,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum SchemaCommand {
+    Vendor(vendor::Args),
+}
+
+pub fn run(args: Args, offline: bool) -> Result<(), crate::Error> {
+    match args.subcommand {
+        SchemaCommand::Vendor(args) => vendor::run(args, offline),
+    }
+}
@@ -0,0 +1,154 @@
+use itertools::{Either, Itertools};
+use tombi_diagnostic::{printer::Pretty, Print};
+use tombi_schema_store::SchemaUrl;
+
+use crate::app::arg;
+
+/// Vendor the schemas of TOML files for hermetic builds.
+///
+/// Writes the schemas matched by the TOML files, including the transitive `$ref`s,
+/// and a catalog of them into the output directory.
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// Paths or glob patterns to TOML documents.
+    files: Vec<String>,
+
+    /// Directory to write the vendored schemas and the catalog.
+    #[arg(short, long)]
+    output: std::path::PathBuf,
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub fn run(args: Args, offline: bool) -> Result<(), crate::Error> {
+    match inner_run(args, Pretty, offline) {
+        Ok(result) => {
+            match result.schemas.len() {
+                0 => eprintln!("No schemas vendored"),
+                1 => eprintln!("1 schema vendored"),
+                n => eprintln!("{n} schemas vendored"),
+            }
+            eprintln!(
+                "Set `schema.catalog.paths` to {:?} to use the vendored schemas",
+                std::env::current_dir()
+                    .ok()
+                    .and_then(|current_dir| result.catalog_path.strip_prefix(current_dir).ok())
+                    .unwrap_or(&result.catalog_path)
+            );
+        }
+        Err(error) => {
+            tracing::error!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+fn inner_run<P>(
+    args: Args,
+    mut printer: P,
+    offline: bool,
+) -> Result<tombi_schema_store::VendorResult, Box<dyn std::error::Error>>
+where
+    crate::Error: Print<P>,
+{
+    let (config, config_path) = serde_tombi::config::load_with_path()?;
+
+    let schema_options = config.schema.as_ref();
+    let schema_store =
+        tombi_schema_store::SchemaStore::new_with_options(tombi_schema_store::Options {
+            offline: offline.then_some(true),
+            strict: schema_options.and_then(|schema_options| schema_options.strict()),
+            cache: tombi_schema_store::CacheOptions::from_schema_options(schema_options),
+        });
+
+    let Ok(runtime) = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    else {
+        tracing::error!("Failed to create tokio runtime");
+        std::process::exit(1);
+    };
+
+    runtime.block_on(async {
+        schema_store
+            .load_config(&config, config_path.as_deref())
+            .await?;
+
+        let include_patterns: Option<Vec<&str>> = config
+            .include
+            .as_deref()
+            .map(|p| p.iter().map(|s| s.as_str()).collect());
+        let exclude_patterns: Option<Vec<&str>> = config
+            .exclude
+            .as_deref()
+            .map(|p| p.iter().map(|s| s.as_str()).collect());
+
+        let input = arg::FileInput::new(
+            &args.files,
+            include_patterns.as_ref().map(|v| &v[..]),
+            exclude_patterns.as_ref().map(|v| &v[..]),
+        );
+
+        let mut schema_urls: Vec<SchemaUrl> = Vec::new();
+        let source_paths = match input {
+            arg::FileInput::Stdin => Vec::with_capacity(0),
+            arg::FileInput::Files(files) => files,
+        };
+        for source_path in source_paths {
+            let source_path = match source_path {
+                Ok(source_path) => source_path,
+                Err(err) => {
+                    err.print(&mut printer);
+                    continue;
+                }
+            };
+            let source = match std::fs::read_to_string(&source_path) {
+                Ok(source) => source,
+                Err(err) => {
+                    crate::Error::Io(err).print(&mut printer);
+                    continue;
+                }
+            };
+            let Some(parsed) = tombi_parser::parse_document_header_comments(&source)
+                .cast::<tombi_ast::Root>()
+            else {
+                continue;
+            };
+
+            match schema_store
+                .resolve_source_schema_from_ast(&parsed.tree(), Some(Either::Right(&source_path)))
+                .await
+            {
+                Ok(Some(source_schema)) => {
+                    if let Some(root_schema) = source_schema.root_schema {
+                        tracing::debug!("{:?} uses {}", source_path, root_schema.schema_url);
+                        schema_urls.push(root_schema.schema_url);
+                    }
+                    for (accessors, schema_url) in source_schema
+                        .sub_schema_url_map
+                        .into_iter()
+                        .sorted_by_key(|(_, schema_url)| schema_url.to_string())
+                    {
+                        tracing::warn!(
+                            "sub schema {} for `{}` is vendored, but a catalog can not apply it. Update `[[schemas]]` to use the vendored file.",
+                            schema_url,
+                            tombi_schema_store::SchemaAccessors::new(accessors)
+                        );
+                        schema_urls.push(schema_url);
+                    }
+                }
+                Ok(None) => {}
+                Err((err, _)) => {
+                    tracing::error!("{:?}: {}", source_path, err);
+                }
+            }
+        }
+
+        let schema_urls = schema_urls.into_iter().unique().collect_vec();
+
+        Ok(schema_store
+            .vendor_schemas(&schema_urls, &args.output)
+            .await?)
+    })
+}
@@ -10,10 +10,14 @@ use tombi_diagnostic::{
 pub enum Error {
     #[error(transparent)]
     NotFormatted(#[from] NotFormattedError),
+    #[error(transparent)]
+    Verify(#[from] VerifyError),
     #[error("{0:?} file not found")]
     FileNotFound(PathBuf),
     #[error("{0:?} is invalid glob pattern")]
     GlobPatternInvalid(String),
+    #[error("cache directory not found")]
+    CacheDirNotFound,
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
@@ -61,6 +65,45 @@ impl std::fmt::Display for NotFormattedError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum VerifyError {
+    NotIdempotent {
+        source_path: Option<PathBuf>,
+    },
+    DataChanged {
+        source_path: Option<PathBuf>,
+        difference: Box<tombi_document::Difference>,
+    },
+}
+
+impl VerifyError {
+    #[inline]
+    pub fn into_error(self) -> Error {
+        Error::Verify(self)
+    }
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotIdempotent {
+                source_path: Some(path),
+            } => write!(f, "{:?} changes when formatted again", path),
+            Self::NotIdempotent { source_path: None } => {
+                write!(f, "Input changes when formatted again")
+            }
+            Self::DataChanged {
+                source_path: Some(path),
+                difference,
+            } => write!(f, "{:?} data changes by formatting: {}", path, difference),
+            Self::DataChanged {
+                source_path: None,
+                difference,
+            } => write!(f, "Input data changes by formatting: {}", difference),
+        }
+    }
+}
+
 impl Print<Pretty> for Error {
     fn print(&self, _printer: &mut Pretty) {
         self.print(&mut Simple);